@@ -1,33 +1,158 @@
 /* tslint:disable */
 /* eslint-disable */
 
+/**
+ * Returns the reader's current state as a plain object, or `null` before the first frame.
+ */
+export function get_state(): any;
+
+/**
+ * Queues a PDF for reading; bytes without a PDF header are read as plain text.
+ */
 export function pass_file_to_bevy(data: Uint8Array): void;
 
+/**
+ * Adds a TTF or OTF font to the font selector under `name` and switches the reader to it.
+ * Uploaded fonts last until the page is reloaded.
+ */
+export function pass_font_to_bevy(name: string, data: Uint8Array): void;
+
+/**
+ * Queues a file whose format is sniffed (DOCX, EPUB, HTML) or chosen from `name` (`.md`), otherwise PDF.
+ */
+export function pass_named_file_to_bevy(name: string, data: Uint8Array): void;
+
+/**
+ * Queues text (e.g. clipboard contents) to read as a plain-text document. Blank text is
+ * ignored, leaving the open document as it is.
+ */
+export function pass_text_to_bevy(text: string): void;
+
+/**
+ * Jumps to a document-wide word index and shows it at once, the same way the position
+ * slider does (the position left goes on the Back history). Out-of-range indices are ignored.
+ */
+export function seek(word_index: number): void;
+
+/**
+ * Jumps to `word` of `page` (both counted from 0 and clamped to the document) and shows it
+ * at once; the position left goes on the Back history.
+ */
+export function seek_to(page: number, word: number): void;
+
+/**
+ * Word indices (document-wide, in reading order) that fire the `set_on_cue` callback when
+ * shown, e.g. chapter starts. Replaces any earlier list; an empty array clears them.
+ */
+export function set_cues(indices: any): void;
+
+/**
+ * Replaces the text shown while no document is loaded (e.g. a localized prompt).
+ */
+export function set_idle_message(text: string): void;
+
+/**
+ * Registers a function called as `(wordIndex, text)` when a word from `set_cues` comes on
+ * screen, once per cue each time it's reached (a seek landing on it counts).
+ */
+export function set_on_cue(callback: Function): void;
+
+/**
+ * Registers a function called with `{ code, message }` when an upload or a book fetch fails.
+ * `code` is one of `NOT_A_PDF`, `ENCRYPTED`, `NO_TEXT`, `IMAGE_ONLY` (a PDF without a text
+ * layer, usually a scan), `NETWORK_ERROR` or `UNSUPPORTED`; `message` is the
+ * English text the built-in UI shows.
+ */
+export function set_on_error(callback: Function): void;
+
+/**
+ * Registers a function called with no arguments when the last word of the document has been read.
+ */
+export function set_on_finished(callback: Function): void;
+
+/**
+ * Registers a function called with the same object as `get_state` whenever any of it
+ * changes: a new word, a seek, play or pause, a speed change. Frames where nothing an
+ * external UI shows has changed make no call.
+ */
+export function set_on_state_change(callback: Function): void;
+
+/**
+ * Registers a function called as `(wordIndex, text)` whenever a new chunk appears, whether
+ * from playback, a manual step or a seek. `wordIndex` is the document-wide index of the
+ * chunk's first word; at most one call is made per frame.
+ */
+export function set_on_word(callback: Function): void;
+
+/**
+ * Plays or pauses, like the Play button; playing a finished document starts it over.
+ * Ignored (like the other playback commands) while no document is open.
+ */
+export function set_playing(playing: boolean): void;
+
+/**
+ * Hand-tuned display times in milliseconds, one entry per word of the open document in
+ * reading order; `null` entries keep the computed timing and an empty array clears them all.
+ */
+export function set_word_durations(durations: any): void;
+
+/**
+ * Sets the reading speed, clamped to the range of the speed slider.
+ */
+export function set_wpm(wpm: number): void;
+
+/**
+ * Starts the reader with default settings.
+ */
 export function start(): void;
 
+/**
+ * Starts the reader with an options object, e.g.
+ * `{ wpm: 400, font: "Tinos-Regular.ttf", theme: "light", minimalUi: true }`.
+ */
+export function start_with_options(options: any): void;
+
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
+  readonly get_state: () => any;
   readonly pass_file_to_bevy: (a: number, b: number) => void;
+  readonly pass_font_to_bevy: (a: number, b: number, c: number, d: number) => void;
+  readonly pass_named_file_to_bevy: (a: number, b: number, c: number, d: number) => void;
+  readonly pass_text_to_bevy: (a: number, b: number) => void;
+  readonly set_cues: (a: any) => [number, number];
+  readonly set_idle_message: (a: number, b: number) => void;
+  readonly set_playing: (a: number) => void;
+  readonly set_word_durations: (a: any) => [number, number];
+  readonly start_with_options: (a: any) => [number, number];
+  readonly seek: (a: number) => void;
+  readonly set_wpm: (a: number) => void;
+  readonly seek_to: (a: number, b: number) => void;
   readonly start: () => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c: (a: number, b: number, c: any) => void;
-  readonly wasm_bindgen__closure__destroy__h1a8fb39631bd491a: (a: number, b: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879: (a: number, b: number, c: any) => void;
-  readonly wasm_bindgen__closure__destroy__h4f2bd173df1da506: (a: number, b: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__hc71bc67148a08ade: (a: number, b: number, c: any, d: any) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h250f8e94eda4717d: (a: number, b: number, c: any) => void;
-  readonly wasm_bindgen__closure__destroy__hab830b49c801659d: (a: number, b: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__hb72e83c42a35b6d3: (a: number, b: number, c: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h098fcee782c0060d: (a: number, b: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h984e9ad68e88966b: (a: number, b: number) => void;
-  readonly wasm_bindgen__closure__destroy__hf5d5175b3da177d2: (a: number, b: number) => void;
+  readonly set_on_cue: (a: any) => void;
+  readonly set_on_error: (a: any) => void;
+  readonly set_on_finished: (a: any) => void;
+  readonly set_on_state_change: (a: any) => void;
+  readonly set_on_word: (a: any) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__hbd8338392215d188: (a: number, b: number) => void;
+  readonly wasm_bindgen__closure__destroy__hebbd0fb9aab39dd4: (a: number, b: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5: (a: number, b: number, c: any) => void;
+  readonly wasm_bindgen__closure__destroy__h0ffb0ee49133294d: (a: number, b: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595: (a: number, b: number, c: any) => void;
+  readonly wasm_bindgen__closure__destroy__h185ec2a936a30949: (a: number, b: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__he6a15e6844de0a80: (a: number, b: number, c: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__hac443c6a9e971143: (a: number, b: number, c: any, d: any) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__hcb8556fa57d47c44: (a: number, b: number, c: any) => void;
+  readonly wasm_bindgen__closure__destroy__hae0e942d542a5a54: (a: number, b: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__h394ee6265a05d489: (a: number, b: number) => void;
   readonly __wbindgen_malloc: (a: number, b: number) => number;
   readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __externref_table_alloc: () => number;
   readonly __wbindgen_externrefs: WebAssembly.Table;
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+  readonly __externref_table_dealloc: (a: number) => void;
   readonly __wbindgen_start: () => void;
 }
 
@@ -278,6 +278,12 @@ function passStringToWasm0(arg, malloc, realloc) {
     return ptr;
 }
 
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_externrefs.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
 let cachedTextDecoder = new TextDecoder('utf-8', { ignoreBOM: true, fatal: true });
 cachedTextDecoder.decode();
 const MAX_SAFARI_DECODE_BYTES = 2146435072;
@@ -307,32 +313,32 @@ if (!('encodeInto' in cachedTextEncoder)) {
 
 let WASM_VECTOR_LEN = 0;
 
-function wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c(arg0, arg1, arg2) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c(arg0, arg1, arg2);
+function wasm_bindgen__convert__closures_____invoke__hbd8338392215d188(arg0, arg1) {
+    wasm.wasm_bindgen__convert__closures_____invoke__hbd8338392215d188(arg0, arg1);
 }
 
-function wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879(arg0, arg1, arg2) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879(arg0, arg1, arg2);
+function wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5(arg0, arg1, arg2) {
+    wasm.wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5(arg0, arg1, arg2);
 }
 
-function wasm_bindgen__convert__closures_____invoke__hc71bc67148a08ade(arg0, arg1, arg2, arg3) {
-    wasm.wasm_bindgen__convert__closures_____invoke__hc71bc67148a08ade(arg0, arg1, arg2, arg3);
+function wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595(arg0, arg1, arg2) {
+    wasm.wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595(arg0, arg1, arg2);
 }
 
-function wasm_bindgen__convert__closures_____invoke__h250f8e94eda4717d(arg0, arg1, arg2) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h250f8e94eda4717d(arg0, arg1, arg2);
+function wasm_bindgen__convert__closures_____invoke__he6a15e6844de0a80(arg0, arg1, arg2) {
+    wasm.wasm_bindgen__convert__closures_____invoke__he6a15e6844de0a80(arg0, arg1, isLikeNone(arg2) ? 0 : addToExternrefTable0(arg2));
 }
 
-function wasm_bindgen__convert__closures_____invoke__hb72e83c42a35b6d3(arg0, arg1, arg2) {
-    wasm.wasm_bindgen__convert__closures_____invoke__hb72e83c42a35b6d3(arg0, arg1, isLikeNone(arg2) ? 0 : addToExternrefTable0(arg2));
+function wasm_bindgen__convert__closures_____invoke__hac443c6a9e971143(arg0, arg1, arg2, arg3) {
+    wasm.wasm_bindgen__convert__closures_____invoke__hac443c6a9e971143(arg0, arg1, arg2, arg3);
 }
 
-function wasm_bindgen__convert__closures_____invoke__h098fcee782c0060d(arg0, arg1) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h098fcee782c0060d(arg0, arg1);
+function wasm_bindgen__convert__closures_____invoke__hcb8556fa57d47c44(arg0, arg1, arg2) {
+    wasm.wasm_bindgen__convert__closures_____invoke__hcb8556fa57d47c44(arg0, arg1, arg2);
 }
 
-function wasm_bindgen__convert__closures_____invoke__h984e9ad68e88966b(arg0, arg1) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h984e9ad68e88966b(arg0, arg1);
+function wasm_bindgen__convert__closures_____invoke__h394ee6265a05d489(arg0, arg1) {
+    wasm.wasm_bindgen__convert__closures_____invoke__h394ee6265a05d489(arg0, arg1);
 }
 
 const __wbindgen_enum_GamepadMappingType = ["", "standard"];
@@ -344,6 +350,16 @@ const __wbindgen_enum_ResizeObserverBoxOptions = ["border-box", "content-box", "
 const __wbindgen_enum_VisibilityState = ["hidden", "visible"];
 
 /**
+ * Returns the reader's current state as a plain object, or `null` before the first frame.
+ * @returns {any}
+ */
+export function get_state() {
+    const ret = wasm.get_state();
+    return ret;
+}
+
+/**
+ * Queues a PDF for reading; bytes without a PDF header are read as plain text.
  * @param {Uint8Array} data
  */
 export function pass_file_to_bevy(data) {
@@ -352,10 +368,181 @@ export function pass_file_to_bevy(data) {
     wasm.pass_file_to_bevy(ptr0, len0);
 }
 
+/**
+ * Adds a TTF or OTF font to the font selector under `name` and switches the reader to it.
+ * Uploaded fonts last until the page is reloaded.
+ * @param {string} name
+ * @param {Uint8Array} data
+ */
+export function pass_font_to_bevy(name, data) {
+    const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    wasm.pass_font_to_bevy(ptr0, len0, ptr1, len1);
+}
+
+/**
+ * Queues a file whose format is sniffed (DOCX, EPUB, HTML) or chosen from `name` (`.md`), otherwise PDF.
+ * @param {string} name
+ * @param {Uint8Array} data
+ */
+export function pass_named_file_to_bevy(name, data) {
+    const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    wasm.pass_named_file_to_bevy(ptr0, len0, ptr1, len1);
+}
+
+/**
+ * Queues text (e.g. clipboard contents) to read as a plain-text document. Blank text is
+ * ignored, leaving the open document as it is.
+ * @param {string} text
+ */
+export function pass_text_to_bevy(text) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    wasm.pass_text_to_bevy(ptr0, len0);
+}
+
+/**
+ * Jumps to a document-wide word index and shows it at once, the same way the position
+ * slider does (the position left goes on the Back history). Out-of-range indices are ignored.
+ * @param {number} word_index
+ */
+export function seek(word_index) {
+    wasm.seek(word_index);
+}
+
+/**
+ * Jumps to `word` of `page` (both counted from 0 and clamped to the document) and shows it
+ * at once; the position left goes on the Back history.
+ * @param {number} page
+ * @param {number} word
+ */
+export function seek_to(page, word) {
+    wasm.seek_to(page, word);
+}
+
+/**
+ * Word indices (document-wide, in reading order) that fire the `set_on_cue` callback when
+ * shown, e.g. chapter starts. Replaces any earlier list; an empty array clears them.
+ * @param {any} indices
+ */
+export function set_cues(indices) {
+    const ret = wasm.set_cues(indices);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Replaces the text shown while no document is loaded (e.g. a localized prompt).
+ * @param {string} text
+ */
+export function set_idle_message(text) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    wasm.set_idle_message(ptr0, len0);
+}
+
+/**
+ * Registers a function called as `(wordIndex, text)` when a word from `set_cues` comes on
+ * screen, once per cue each time it's reached (a seek landing on it counts).
+ * @param {Function} callback
+ */
+export function set_on_cue(callback) {
+    wasm.set_on_cue(callback);
+}
+
+/**
+ * Registers a function called with `{ code, message }` when an upload or a book fetch fails.
+ * `code` is one of `NOT_A_PDF`, `ENCRYPTED`, `NO_TEXT`, `IMAGE_ONLY` (a PDF without a text
+ * layer, usually a scan), `NETWORK_ERROR` or `UNSUPPORTED`; `message` is the
+ * English text the built-in UI shows.
+ * @param {Function} callback
+ */
+export function set_on_error(callback) {
+    wasm.set_on_error(callback);
+}
+
+/**
+ * Registers a function called with no arguments when the last word of the document has been read.
+ * @param {Function} callback
+ */
+export function set_on_finished(callback) {
+    wasm.set_on_finished(callback);
+}
+
+/**
+ * Registers a function called with the same object as `get_state` whenever any of it
+ * changes: a new word, a seek, play or pause, a speed change. Frames where nothing an
+ * external UI shows has changed make no call.
+ * @param {Function} callback
+ */
+export function set_on_state_change(callback) {
+    wasm.set_on_state_change(callback);
+}
+
+/**
+ * Registers a function called as `(wordIndex, text)` whenever a new chunk appears, whether
+ * from playback, a manual step or a seek. `wordIndex` is the document-wide index of the
+ * chunk's first word; at most one call is made per frame.
+ * @param {Function} callback
+ */
+export function set_on_word(callback) {
+    wasm.set_on_word(callback);
+}
+
+/**
+ * Plays or pauses, like the Play button; playing a finished document starts it over.
+ * Ignored (like the other playback commands) while no document is open.
+ * @param {boolean} playing
+ */
+export function set_playing(playing) {
+    wasm.set_playing(playing);
+}
+
+/**
+ * Hand-tuned display times in milliseconds, one entry per word of the open document in
+ * reading order; `null` entries keep the computed timing and an empty array clears them all.
+ * @param {any} durations
+ */
+export function set_word_durations(durations) {
+    const ret = wasm.set_word_durations(durations);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Sets the reading speed, clamped to the range of the speed slider.
+ * @param {number} wpm
+ */
+export function set_wpm(wpm) {
+    wasm.set_wpm(wpm);
+}
+
+/**
+ * Starts the reader with default settings.
+ */
 export function start() {
     wasm.start();
 }
 
+/**
+ * Starts the reader with an options object, e.g.
+ * `{ wpm: 400, font: "Tinos-Regular.ttf", theme: "light", minimalUi: true }`.
+ * @param {any} options
+ */
+export function start_with_options(options) {
+    const ret = wasm.start_with_options(options);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
 const EXPECTED_RESPONSE_TYPES = new Set(['basic', 'cors', 'default']);
 
 async function __wbg_load(module, imports) {
@@ -391,6 +578,14 @@ async function __wbg_load(module, imports) {
 function __wbg_get_imports() {
     const imports = {};
     imports.wbg = {};
+    imports.wbg.__wbg_Error_52673b7de5a0ca89 = function(arg0, arg1) {
+        const ret = Error(getStringFromWasm0(arg0, arg1));
+        return ret;
+    };
+    imports.wbg.__wbg_Number_2d1dcfcf4ec51736 = function(arg0) {
+        const ret = Number(arg0);
+        return ret;
+    };
     imports.wbg.__wbg_Window_02fa58b243352adf = function(arg0) {
         const ret = arg0.Window;
         return ret;
@@ -403,6 +598,12 @@ function __wbg_get_imports() {
         const ret = arg0.WorkerGlobalScope;
         return ret;
     };
+    imports.wbg.__wbg___wbindgen_bigint_get_as_i64_6e32f5e6aff02e1d = function(arg0, arg1) {
+        const v = arg1;
+        const ret = typeof(v) === 'bigint' ? v : undefined;
+        getDataViewMemory0().setBigInt64(arg0 + 8 * 1, isLikeNone(ret) ? BigInt(0) : ret, true);
+        getDataViewMemory0().setInt32(arg0 + 4 * 0, !isLikeNone(ret), true);
+    };
     imports.wbg.__wbg___wbindgen_boolean_get_dea25b33882b895b = function(arg0) {
         const v = arg0;
         const ret = typeof(v) === 'boolean' ? v : undefined;
@@ -415,6 +616,14 @@ function __wbg_get_imports() {
         getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
         getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
     };
+    imports.wbg.__wbg___wbindgen_in_0d3e1e8f0c669317 = function(arg0, arg1) {
+        const ret = arg0 in arg1;
+        return ret;
+    };
+    imports.wbg.__wbg___wbindgen_is_bigint_0e1a2e3f55cfae27 = function(arg0) {
+        const ret = typeof(arg0) === 'bigint';
+        return ret;
+    };
     imports.wbg.__wbg___wbindgen_is_function_8d400b8b1af978cd = function(arg0) {
         const ret = typeof(arg0) === 'function';
         return ret;
@@ -436,6 +645,14 @@ function __wbg_get_imports() {
         const ret = arg0 === undefined;
         return ret;
     };
+    imports.wbg.__wbg___wbindgen_jsval_eq_b6101cc9cef1fe36 = function(arg0, arg1) {
+        const ret = arg0 === arg1;
+        return ret;
+    };
+    imports.wbg.__wbg___wbindgen_jsval_loose_eq_766057600fdd1b0d = function(arg0, arg1) {
+        const ret = arg0 == arg1;
+        return ret;
+    };
     imports.wbg.__wbg___wbindgen_number_get_9619185a74197f95 = function(arg0, arg1) {
         const obj = arg1;
         const ret = typeof(obj) === 'number' ? obj : undefined;
@@ -643,6 +860,10 @@ function __wbg_get_imports() {
         const ret = arg0.call(arg1);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_call_c8baa5c5e72d274e = function() { return handleError(function (arg0, arg1, arg2, arg3) {
+        const ret = arg0.call(arg1, arg2, arg3);
+        return ret;
+    }, arguments) };
     imports.wbg.__wbg_cancelAnimationFrame_1c2a3faf7be5aedd = function() { return handleError(function (arg0, arg1) {
         arg0.cancelAnimationFrame(arg1);
     }, arguments) };
@@ -686,6 +907,9 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_clear_52caf9271911674b = function(arg0, arg1) {
         arg0.clear(arg1 >>> 0);
     };
+    imports.wbg.__wbg_click_3a8e35c38329dd3a = function(arg0) {
+        arg0.click();
+    };
     imports.wbg.__wbg_clientWaitSync_42970d3aaa2e5351 = function(arg0, arg1, arg2, arg3) {
         const ret = arg0.clientWaitSync(arg1, arg2 >>> 0, arg3 >>> 0);
         return ret;
@@ -1013,6 +1237,10 @@ function __wbg_get_imports() {
         const ret = arg0.document;
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_done_62ea16af4ce34b24 = function(arg0) {
+        const ret = arg0.done;
+        return ret;
+    };
     imports.wbg.__wbg_drawArraysInstancedANGLE_5802f710395d6947 = function(arg0, arg1, arg2, arg3, arg4) {
         arg0.drawArraysInstancedANGLE(arg1 >>> 0, arg2, arg3, arg4);
     };
@@ -1049,9 +1277,17 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_enable_52598759008d46ee = function(arg0, arg1) {
         arg0.enable(arg1 >>> 0);
     };
+    imports.wbg.__wbg_encodeURIComponent_fe8578929b74aa6c = function(arg0, arg1) {
+        const ret = encodeURIComponent(getStringFromWasm0(arg0, arg1));
+        return ret;
+    };
     imports.wbg.__wbg_endQuery_81a855457c9a8807 = function(arg0, arg1) {
         arg0.endQuery(arg1 >>> 0);
     };
+    imports.wbg.__wbg_entries_83c79938054e065f = function(arg0) {
+        const ret = Object.entries(arg0);
+        return ret;
+    };
     imports.wbg.__wbg_error_7534b8e9a36f1ab4 = function(arg0, arg1) {
         let deferred0_0;
         let deferred0_1;
@@ -1092,6 +1328,10 @@ function __wbg_get_imports() {
         const ret = arg0.fetch(getStringFromWasm0(arg1, arg2));
         return ret;
     };
+    imports.wbg.__wbg_files_aa1f009258eadae6 = function(arg0) {
+        const ret = arg0.files;
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    };
     imports.wbg.__wbg_focus_220a53e22147dc0f = function() { return handleError(function (arg0) {
         arg0.focus();
     }, arguments) };
@@ -1161,6 +1401,10 @@ function __wbg_get_imports() {
         getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
         getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
     }, arguments) };
+    imports.wbg.__wbg_getDate_b8071ea9fc4f6838 = function(arg0) {
+        const ret = arg0.getDate();
+        return ret;
+    };
     imports.wbg.__wbg_getElementById_e05488d2143c2b21 = function(arg0, arg1, arg2) {
         const ret = arg0.getElementById(getStringFromWasm0(arg1, arg2));
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
@@ -1169,6 +1413,10 @@ function __wbg_get_imports() {
         const ret = arg0.getExtension(getStringFromWasm0(arg1, arg2));
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     }, arguments) };
+    imports.wbg.__wbg_getFullYear_6ac412e8eee86879 = function(arg0) {
+        const ret = arg0.getFullYear();
+        return ret;
+    };
     imports.wbg.__wbg_getGamepads_0a2243e96f36011e = function() { return handleError(function (arg0) {
         const ret = arg0.getGamepads();
         return ret;
@@ -1177,6 +1425,17 @@ function __wbg_get_imports() {
         const ret = arg0.getIndexedParameter(arg1 >>> 0, arg2 >>> 0);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_getItem_1340bfc9a10d5991 = function() { return handleError(function (arg0, arg1, arg2, arg3) {
+        const ret = arg1.getItem(getStringFromWasm0(arg2, arg3));
+        var ptr1 = isLikeNone(ret) ? 0 : passStringToWasm0(ret, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        var len1 = WASM_VECTOR_LEN;
+        getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
+        getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
+    }, arguments) };
+    imports.wbg.__wbg_getMonth_48a392071f9e5017 = function(arg0) {
+        const ret = arg0.getMonth();
+        return ret;
+    };
     imports.wbg.__wbg_getOwnPropertyDescriptor_b6aa5a2fa50d52c7 = function(arg0, arg1) {
         const ret = Object.getOwnPropertyDescriptor(arg0, arg1);
         return ret;
@@ -1278,6 +1537,18 @@ function __wbg_get_imports() {
         const ret = arg0[arg1 >>> 0];
         return ret;
     };
+    imports.wbg.__wbg_get_89bca58298277b24 = function(arg0, arg1) {
+        const ret = arg0[arg1 >>> 0];
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    };
+    imports.wbg.__wbg_get_af9dab7e9603ea93 = function() { return handleError(function (arg0, arg1) {
+        const ret = Reflect.get(arg0, arg1);
+        return ret;
+    }, arguments) };
+    imports.wbg.__wbg_get_with_ref_key_1dc361bd10053bfe = function(arg0, arg1) {
+        const ret = arg0[arg1];
+        return ret;
+    };
     imports.wbg.__wbg_height_3a0b31e52a5b7f17 = function(arg0) {
         const ret = arg0.height;
         return ret;
@@ -1325,6 +1596,19 @@ function __wbg_get_imports() {
         const ret = arg0.inlineSize;
         return ret;
     };
+    imports.wbg.__wbg_insertAdjacentHTML_00b079f78a367d15 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4) {
+        arg0.insertAdjacentHTML(getStringFromWasm0(arg1, arg2), getStringFromWasm0(arg3, arg4));
+    }, arguments) };
+    imports.wbg.__wbg_instanceof_ArrayBuffer_f3320d2419cd0355 = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof ArrayBuffer;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_DomException_d430cd4fb5284a83 = function(arg0) {
         let result;
         try {
@@ -1335,6 +1619,16 @@ function __wbg_get_imports() {
         const ret = result;
         return ret;
     };
+    imports.wbg.__wbg_instanceof_HtmlAnchorElement_2ac07b5cf25eac0c = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof HTMLAnchorElement;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_HtmlCanvasElement_c4251b1b6a15edcc = function(arg0) {
         let result;
         try {
@@ -1345,6 +1639,16 @@ function __wbg_get_imports() {
         const ret = result;
         return ret;
     };
+    imports.wbg.__wbg_instanceof_HtmlElement_20a3acb594113d73 = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof HTMLElement;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_HtmlInputElement_46b31917ce88698f = function(arg0) {
         let result;
         try {
@@ -1365,6 +1669,16 @@ function __wbg_get_imports() {
         const ret = result;
         return ret;
     };
+    imports.wbg.__wbg_instanceof_Uint8Array_da54ccc9d3e09434 = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof Uint8Array;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_WebGl2RenderingContext_121e4c8c95b128ef = function(arg0) {
         let result;
         try {
@@ -1388,6 +1702,10 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_invalidateFramebuffer_e2d4d1747d73b885 = function() { return handleError(function (arg0, arg1, arg2) {
         arg0.invalidateFramebuffer(arg1 >>> 0, arg2);
     }, arguments) };
+    imports.wbg.__wbg_isArray_51fd9e6422c0a395 = function(arg0) {
+        const ret = Array.isArray(arg0);
+        return ret;
+    };
     imports.wbg.__wbg_isComposing_f44397d3c48338be = function(arg0) {
         const ret = arg0.isComposing;
         return ret;
@@ -1400,6 +1718,10 @@ function __wbg_get_imports() {
         const ret = arg0.isIntersecting;
         return ret;
     };
+    imports.wbg.__wbg_isSafeInteger_ae7d3f054d55fa16 = function(arg0) {
+        const ret = Number.isSafeInteger(arg0);
+        return ret;
+    };
     imports.wbg.__wbg_isSecureContext_0defe0b227a0ff2a = function(arg0) {
         const ret = arg0.isSecureContext;
         return ret;
@@ -1408,6 +1730,10 @@ function __wbg_get_imports() {
         const ret = Object.is(arg0, arg1);
         return ret;
     };
+    imports.wbg.__wbg_iterator_27b7c8b35ab3e86b = function() {
+        const ret = Symbol.iterator;
+        return ret;
+    };
     imports.wbg.__wbg_keyCode_9a2cb918794208e5 = function(arg0) {
         const ret = arg0.keyCode;
         return ret;
@@ -1419,6 +1745,13 @@ function __wbg_get_imports() {
         getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
         getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
     };
+    imports.wbg.__wbg_language_763ea76470ed849b = function(arg0, arg1) {
+        const ret = arg1.language;
+        var ptr1 = isLikeNone(ret) ? 0 : passStringToWasm0(ret, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        var len1 = WASM_VECTOR_LEN;
+        getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
+        getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
+    };
     imports.wbg.__wbg_length_22ac23eaec9d8053 = function(arg0) {
         const ret = arg0.length;
         return ret;
@@ -1433,6 +1766,10 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_linkProgram_93f76a2f5030041e = function(arg0, arg1) {
         arg0.linkProgram(arg1);
     };
+    imports.wbg.__wbg_localStorage_e7a9e9fee8fc608d = function() { return handleError(function (arg0) {
+        const ret = arg0.localStorage;
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    }, arguments) };
     imports.wbg.__wbg_location_0ef648bbeb3e599c = function(arg0) {
         const ret = arg0.location;
         return ret;
@@ -1532,6 +1869,10 @@ function __wbg_get_imports() {
         const ret = arg0.navigator;
         return ret;
     };
+    imports.wbg.__wbg_new_0_23cedd11d9b40c9d = function() {
+        const ret = new Date();
+        return ret;
+    };
     imports.wbg.__wbg_new_137453588c393c59 = function() { return handleError(function () {
         const ret = new MessageChannel();
         return ret;
@@ -1596,6 +1937,14 @@ function __wbg_get_imports() {
         const ret = new ImageData(getClampedArrayU8FromWasm0(arg0, arg1), arg2 >>> 0);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_next_138a17bbf04e926c = function(arg0) {
+        const ret = arg0.next;
+        return ret;
+    };
+    imports.wbg.__wbg_next_3cfe5c0fe2a4cc53 = function() { return handleError(function (arg0) {
+        const ret = arg0.next();
+        return ret;
+    }, arguments) };
     imports.wbg.__wbg_node_e1f24f89a7336c2e = function(arg0) {
         const ret = arg0.node;
         return ret;
@@ -1633,6 +1982,10 @@ function __wbg_get_imports() {
         const ret = arg0.offsetY;
         return ret;
     };
+    imports.wbg.__wbg_ok_dd98ecb60d721e20 = function(arg0) {
+        const ret = arg0.ok;
+        return ret;
+    };
     imports.wbg.__wbg_open_c565053c17d497bd = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4) {
         const ret = arg0.open(getStringFromWasm0(arg1, arg2), getStringFromWasm0(arg3, arg4));
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
@@ -1834,6 +2187,9 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_setAttribute_34747dd193f45828 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4) {
         arg0.setAttribute(getStringFromWasm0(arg1, arg2), getStringFromWasm0(arg3, arg4));
     }, arguments) };
+    imports.wbg.__wbg_setItem_1167ad38996d6426 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4) {
+        arg0.setItem(getStringFromWasm0(arg1, arg2), getStringFromWasm0(arg3, arg4));
+    }, arguments) };
     imports.wbg.__wbg_setPointerCapture_c611f4bcb7e9081e = function() { return handleError(function (arg0, arg1) {
         arg0.setPointerCapture(arg1);
     }, arguments) };
@@ -1848,10 +2204,16 @@ function __wbg_get_imports() {
         const ret = arg0.setTimeout(arg1);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_set_3f1d0b984ed272ed = function(arg0, arg1, arg2) {
+        arg0[arg1] = arg2;
+    };
     imports.wbg.__wbg_set_781438a03c0c3c81 = function() { return handleError(function (arg0, arg1, arg2) {
         const ret = Reflect.set(arg0, arg1, arg2);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_set_accept_4af7309453c16421 = function(arg0, arg1, arg2) {
+        arg0.accept = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_set_autofocus_ae8f5acfea79d602 = function() { return handleError(function (arg0, arg1) {
         arg0.autofocus = arg1 !== 0;
     }, arguments) };
@@ -1867,6 +2229,9 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_set_cursor_1424d80cf8ee0da5 = function(arg0, arg1, arg2) {
         arg0.cursor = getStringFromWasm0(arg1, arg2);
     };
+    imports.wbg.__wbg_set_download_8403fd66b94b25a2 = function(arg0, arg1, arg2) {
+        arg0.download = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_set_duration_91a970f42915997a = function(arg0, arg1) {
         arg0.duration = arg1;
     };
@@ -1879,12 +2244,21 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_set_hidden_6508822431bec7bb = function(arg0, arg1) {
         arg0.hidden = arg1 !== 0;
     };
+    imports.wbg.__wbg_set_href_25c4bcdcbfb4459b = function(arg0, arg1, arg2) {
+        arg0.href = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_set_id_702da6e1bcec3b45 = function(arg0, arg1, arg2) {
         arg0.id = getStringFromWasm0(arg1, arg2);
     };
+    imports.wbg.__wbg_set_innerText_b0abb40240106cb9 = function(arg0, arg1, arg2) {
+        arg0.innerText = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_set_iterations_ef8a200dad4272f0 = function(arg0, arg1) {
         arg0.iterations = arg1;
     };
+    imports.wbg.__wbg_set_onchange_ceed61dcb89e1290 = function(arg0, arg1) {
+        arg0.onchange = arg1;
+    };
     imports.wbg.__wbg_set_onended_851034e3704c5393 = function(arg0, arg1) {
         arg0.onended = arg1;
     };
@@ -2077,6 +2451,14 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_texSubImage3D_e878e89d319561b4 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8, arg9, arg10, arg11) {
         arg0.texSubImage3D(arg1 >>> 0, arg2, arg3, arg4, arg5, arg6, arg7, arg8, arg9 >>> 0, arg10 >>> 0, arg11);
     }, arguments) };
+    imports.wbg.__wbg_text_1405c5ad750e7b12 = function(arg0) {
+        const ret = arg0.text();
+        return ret;
+    };
+    imports.wbg.__wbg_text_51046bb33d257f63 = function() { return handleError(function (arg0) {
+        const ret = arg0.text();
+        return ret;
+    }, arguments) };
     imports.wbg.__wbg_then_429f7caf1026411d = function(arg0, arg1, arg2) {
         const ret = arg0.then(arg1, arg2);
         return ret;
@@ -2223,6 +2605,10 @@ function __wbg_get_imports() {
         getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
         getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
     };
+    imports.wbg.__wbg_value_57b7b035e117f7ee = function(arg0) {
+        const ret = arg0.value;
+        return ret;
+    };
     imports.wbg.__wbg_value_ee261c29fa562a8d = function(arg0) {
         const ret = arg0.value;
         return ret;
@@ -2310,19 +2696,19 @@ function __wbg_get_imports() {
         const ret = arg0.y;
         return ret;
     };
-    imports.wbg.__wbindgen_cast_0eb080afd7246eba = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("Array<any>"), NamedExternref("ResizeObserver")], shim_idx: 4745, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__hc71bc67148a08ade);
+    imports.wbg.__wbindgen_cast_09f32cf227bdaf00 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 2503, function: Function { arguments: [NamedExternref("InputEvent")], shim_idx: 2504, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h185ec2a936a30949, wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_18bb6524d3a2dc62 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 1578, function: Function { arguments: [NamedExternref("InputEvent")], shim_idx: 1579, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h4f2bd173df1da506, wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879);
+    imports.wbg.__wbindgen_cast_0ea37c593e6a3e38 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("Array<any>")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_20170f0e003527d2 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("FocusEvent")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_0fc4ed603869fa17 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [], shim_idx: 6102, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h394ee6265a05d489);
         return ret;
     };
     imports.wbg.__wbindgen_cast_2241b6af4c4b2941 = function(arg0, arg1) {
@@ -2330,24 +2716,34 @@ function __wbg_get_imports() {
         const ret = getStringFromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_33798a58de1d3fc9 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [Option(NamedExternref("Blob"))], shim_idx: 4747, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__hb72e83c42a35b6d3);
+    imports.wbg.__wbindgen_cast_4457923e0a7eee54 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("FocusEvent")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
+        return ret;
+    };
+    imports.wbg.__wbindgen_cast_4625c577ab2ec9ee = function(arg0) {
+        // Cast intrinsic for `U64 -> Externref`.
+        const ret = BigInt.asUintN(64, arg0);
+        return ret;
+    };
+    imports.wbg.__wbindgen_cast_551cfa05cdcf0ae2 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [Option(NamedExternref("Blob"))], shim_idx: 6093, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__he6a15e6844de0a80);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_46d882d4a9d654c8 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("PointerEvent")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_60cc0e1f428c9479 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("Array<any>"), NamedExternref("ResizeObserver")], shim_idx: 6099, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__hac443c6a9e971143);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_61f43bf78239e6a9 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("PageTransitionEvent")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_64358d751d5fe664 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 2503, function: Function { arguments: [NamedExternref("TouchEvent")], shim_idx: 2504, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h185ec2a936a30949, wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_76f1e1156881983f = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [], shim_idx: 4743, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h098fcee782c0060d);
+    imports.wbg.__wbindgen_cast_7994bcb11dc0900a = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 105336, function: Function { arguments: [Externref], shim_idx: 105337, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__hae0e942d542a5a54, wasm_bindgen__convert__closures_____invoke__hcb8556fa57d47c44);
         return ret;
     };
     imports.wbg.__wbindgen_cast_7c316abdc43840a3 = function(arg0, arg1) {
@@ -2355,9 +2751,9 @@ function __wbg_get_imports() {
         const ret = getArrayU32FromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_933a41d8f5ccfe44 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 57478, function: Function { arguments: [], shim_idx: 57479, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__hf5d5175b3da177d2, wasm_bindgen__convert__closures_____invoke__h984e9ad68e88966b);
+    imports.wbg.__wbindgen_cast_9497861328d29932 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("WheelEvent")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
     imports.wbg.__wbindgen_cast_9575fb55a66c262b = function(arg0, arg1) {
@@ -2365,9 +2761,14 @@ function __wbg_get_imports() {
         const ret = getArrayI32FromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_b667423d3ab37ba6 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("Array<any>")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_aa28f53b64bd8747 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 57574, function: Function { arguments: [], shim_idx: 57575, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__hebbd0fb9aab39dd4, wasm_bindgen__convert__closures_____invoke__hbd8338392215d188);
+        return ret;
+    };
+    imports.wbg.__wbindgen_cast_ba6a21cdd8282499 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 2503, function: Function { arguments: [NamedExternref("ClipboardEvent")], shim_idx: 2504, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h185ec2a936a30949, wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595);
         return ret;
     };
     imports.wbg.__wbindgen_cast_bbb4883c6389f1de = function(arg0, arg1) {
@@ -2375,14 +2776,14 @@ function __wbg_get_imports() {
         const ret = getArrayU16FromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_bfb3808d494d594f = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 1578, function: Function { arguments: [NamedExternref("TouchEvent")], shim_idx: 1579, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h4f2bd173df1da506, wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879);
+    imports.wbg.__wbindgen_cast_bd0f6d878b5b9a8e = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("PointerEvent")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_c90bc8327e40fb63 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 1578, function: Function { arguments: [NamedExternref("ClipboardEvent")], shim_idx: 1579, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h4f2bd173df1da506, wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879);
+    imports.wbg.__wbindgen_cast_c42aea2b8337a447 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("PageTransitionEvent")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
     imports.wbg.__wbindgen_cast_cb9088102bce6b30 = function(arg0, arg1) {
@@ -2395,9 +2796,9 @@ function __wbg_get_imports() {
         const ret = getArrayF32FromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_d2c3ae47ae385333 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 1578, function: Function { arguments: [NamedExternref("CompositionEvent")], shim_idx: 1579, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h4f2bd173df1da506, wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879);
+    imports.wbg.__wbindgen_cast_cfaa7765f4ae360b = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 2503, function: Function { arguments: [NamedExternref("CompositionEvent")], shim_idx: 2504, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h185ec2a936a30949, wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595);
         return ret;
     };
     imports.wbg.__wbindgen_cast_d6cd19b81560fd6e = function(arg0) {
@@ -2405,14 +2806,14 @@ function __wbg_get_imports() {
         const ret = arg0;
         return ret;
     };
-    imports.wbg.__wbindgen_cast_d7d7dc13fc81593f = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("KeyboardEvent")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_d8a328a006b47ecd = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("KeyboardEvent")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_da55ccb073cc82d3 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("Event")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
+    imports.wbg.__wbindgen_cast_dc9b6822b64ccd12 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 6092, function: Function { arguments: [NamedExternref("Event")], shim_idx: 6095, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h0ffb0ee49133294d, wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5);
         return ret;
     };
     imports.wbg.__wbindgen_cast_e47ceb6027f5c92c = function(arg0, arg1) {
@@ -2420,16 +2821,6 @@ function __wbg_get_imports() {
         const ret = getArrayI16FromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_f4911e1c6b2534bf = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 4739, function: Function { arguments: [NamedExternref("WheelEvent")], shim_idx: 4740, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__h1a8fb39631bd491a, wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c);
-        return ret;
-    };
-    imports.wbg.__wbindgen_cast_fa311538b85845f1 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 106557, function: Function { arguments: [Externref], shim_idx: 106558, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, wasm.wasm_bindgen__closure__destroy__hab830b49c801659d, wasm_bindgen__convert__closures_____invoke__h250f8e94eda4717d);
-        return ret;
-    };
     imports.wbg.__wbindgen_cast_feefb5fadd6457fd = function(arg0, arg1) {
         // Cast intrinsic for `Ref(Slice(I8)) -> NamedExternref("Int8Array")`.
         const ret = getArrayI8FromWasm0(arg0, arg1);
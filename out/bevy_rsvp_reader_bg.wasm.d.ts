@@ -1,23 +1,41 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
+export const get_state: () => any;
 export const pass_file_to_bevy: (a: number, b: number) => void;
+export const pass_font_to_bevy: (a: number, b: number, c: number, d: number) => void;
+export const pass_named_file_to_bevy: (a: number, b: number, c: number, d: number) => void;
+export const pass_text_to_bevy: (a: number, b: number) => void;
+export const set_cues: (a: any) => [number, number];
+export const set_idle_message: (a: number, b: number) => void;
+export const set_playing: (a: number) => void;
+export const set_word_durations: (a: any) => [number, number];
+export const start_with_options: (a: any) => [number, number];
+export const seek: (a: number) => void;
+export const set_wpm: (a: number) => void;
+export const seek_to: (a: number, b: number) => void;
 export const start: () => void;
-export const wasm_bindgen__convert__closures_____invoke__h1a41cd8edd1ec62c: (a: number, b: number, c: any) => void;
-export const wasm_bindgen__closure__destroy__h1a8fb39631bd491a: (a: number, b: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__h541e3716cb8ad879: (a: number, b: number, c: any) => void;
-export const wasm_bindgen__closure__destroy__h4f2bd173df1da506: (a: number, b: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__hc71bc67148a08ade: (a: number, b: number, c: any, d: any) => void;
-export const wasm_bindgen__convert__closures_____invoke__h250f8e94eda4717d: (a: number, b: number, c: any) => void;
-export const wasm_bindgen__closure__destroy__hab830b49c801659d: (a: number, b: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__hb72e83c42a35b6d3: (a: number, b: number, c: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__h098fcee782c0060d: (a: number, b: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__h984e9ad68e88966b: (a: number, b: number) => void;
-export const wasm_bindgen__closure__destroy__hf5d5175b3da177d2: (a: number, b: number) => void;
+export const set_on_cue: (a: any) => void;
+export const set_on_error: (a: any) => void;
+export const set_on_finished: (a: any) => void;
+export const set_on_state_change: (a: any) => void;
+export const set_on_word: (a: any) => void;
+export const wasm_bindgen__convert__closures_____invoke__hbd8338392215d188: (a: number, b: number) => void;
+export const wasm_bindgen__closure__destroy__hebbd0fb9aab39dd4: (a: number, b: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__h2670e8f55de8eee5: (a: number, b: number, c: any) => void;
+export const wasm_bindgen__closure__destroy__h0ffb0ee49133294d: (a: number, b: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__h280a10d8adfe1595: (a: number, b: number, c: any) => void;
+export const wasm_bindgen__closure__destroy__h185ec2a936a30949: (a: number, b: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__he6a15e6844de0a80: (a: number, b: number, c: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__hac443c6a9e971143: (a: number, b: number, c: any, d: any) => void;
+export const wasm_bindgen__convert__closures_____invoke__hcb8556fa57d47c44: (a: number, b: number, c: any) => void;
+export const wasm_bindgen__closure__destroy__hae0e942d542a5a54: (a: number, b: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__h394ee6265a05d489: (a: number, b: number) => void;
 export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __externref_table_alloc: () => number;
 export const __wbindgen_externrefs: WebAssembly.Table;
 export const __wbindgen_exn_store: (a: number) => void;
 export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const __externref_table_dealloc: (a: number) => void;
 export const __wbindgen_start: () => void;
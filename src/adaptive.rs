@@ -0,0 +1,194 @@
+// Adaptive speed (opt-in training aid): frequent pauses or rewinds within a short window of
+// reading lower the WPM a little, while a long stretch without intervention nudges it up.
+// Every change is announced, since a speed that moves on its own is otherwise surprising.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::i18n::{tr, Lang};
+use crate::{OverlayMessage, RsvpState, MAX_WPM, MIN_WPM};
+
+// Interventions counted over this much reading time...
+const WINDOW_SECS: f32 = 30.0;
+// ...and this many of them slow the reader down
+const INTERVENTIONS_TO_SLOW: usize = 3;
+// Uninterrupted reading before speeding up
+const CALM_SECS: f32 = 60.0;
+const SLOW_FACTOR: f32 = 0.95;
+const SPEED_UP_FACTOR: f32 = 1.03;
+
+#[derive(Resource)]
+pub struct AdaptiveSpeed {
+    pub enabled: bool,
+    pub min_wpm: f32,
+    pub max_wpm: f32,
+    // Reading-clock times of recent pauses and rewinds
+    interventions: VecDeque<f32>,
+    // Seconds spent playing, the clock the window is measured on
+    clock: f32,
+    calm_secs: f32,
+    was_playing: bool,
+    last_flat: usize,
+    // A new document resets the position without anyone rewinding
+    last_total: usize,
+}
+
+impl Default for AdaptiveSpeed {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_wpm: 200.0,
+            max_wpm: 600.0,
+            interventions: VecDeque::new(),
+            clock: 0.0,
+            calm_secs: 0.0,
+            was_playing: false,
+            last_flat: 0,
+            last_total: 0,
+        }
+    }
+}
+
+impl AdaptiveSpeed {
+    /// A pause or rewind just happened.
+    fn record_intervention(&mut self) {
+        self.interventions.push_back(self.clock);
+        self.calm_secs = 0.0;
+    }
+
+    /// Advances the reading clock by `delta` seconds of playback and returns the new WPM
+    /// if `wpm` should change.
+    fn adjust(&mut self, wpm: f32, delta: f32) -> Option<f32> {
+        self.clock += delta;
+        self.calm_secs += delta;
+        while self.interventions.front().is_some_and(|t| self.clock - t > WINDOW_SECS) {
+            self.interventions.pop_front();
+        }
+
+        let (low, high) = (self.min_wpm.min(self.max_wpm), self.max_wpm.max(self.min_wpm));
+        let target = if self.interventions.len() >= INTERVENTIONS_TO_SLOW {
+            self.interventions.clear();
+            self.calm_secs = 0.0;
+            wpm * SLOW_FACTOR
+        } else if self.calm_secs >= CALM_SECS {
+            self.calm_secs = 0.0;
+            wpm * SPEED_UP_FACTOR
+        } else {
+            return None;
+        };
+        let target = target.clamp(low, high).round();
+        (target != wpm).then_some(target)
+    }
+}
+
+pub fn adaptive_speed_system(
+    time: Res<Time>,
+    mut rsvp: ResMut<RsvpState>,
+    mut adaptive: ResMut<AdaptiveSpeed>,
+    mut overlay: ResMut<OverlayMessage>,
+) {
+    let flat = rsvp.flat_index(rsvp.current_page_index, rsvp.current_word_index);
+    let playing = rsvp.is_playing;
+    let paused = adaptive.was_playing && !playing && !rsvp.finished;
+    let rewound = flat < adaptive.last_flat && rsvp.total_words == adaptive.last_total;
+    adaptive.was_playing = playing;
+    adaptive.last_flat = flat;
+    adaptive.last_total = rsvp.total_words;
+
+    if !adaptive.enabled {
+        return;
+    }
+    if paused || rewound {
+        adaptive.record_intervention();
+    }
+    if !playing {
+        return;
+    }
+
+    if let Some(wpm) = adaptive.adjust(rsvp.wpm, time.delta_secs()) {
+        rsvp.wpm = wpm;
+        overlay.show(format!("{}: {:.0} WPM", tr(rsvp.lang, "Adaptive speed"), wpm), 1.5);
+    }
+}
+
+/// Playback-panel controls: the toggle, plus the bounds while it is on.
+pub fn adaptive_controls(ui: &mut egui::Ui, adaptive: &mut AdaptiveSpeed, lang: Lang) {
+    ui.checkbox(&mut adaptive.enabled, tr(lang, "Adapt speed to my pauses"));
+    if adaptive.enabled {
+        ui.add(egui::Slider::new(&mut adaptive.min_wpm, MIN_WPM..=MAX_WPM).text(tr(lang, "Min")));
+        ui.add(egui::Slider::new(&mut adaptive.max_wpm, MIN_WPM..=MAX_WPM).text(tr(lang, "Max")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn adaptive() -> AdaptiveSpeed {
+        AdaptiveSpeed { enabled: true, min_wpm: 200.0, max_wpm: 600.0, ..default() }
+    }
+
+    #[test]
+    fn a_burst_of_pauses_slows_down() {
+        let mut a = adaptive();
+        for _ in 0..INTERVENTIONS_TO_SLOW {
+            assert_eq!(a.adjust(400.0, 2.0), None);
+            a.record_intervention();
+        }
+        assert_eq!(a.adjust(400.0, 0.1), Some(380.0));
+        // The burst is used up
+        assert_eq!(a.adjust(380.0, 0.1), None);
+    }
+
+    #[test]
+    fn pauses_spread_past_the_window_dont_count() {
+        let mut a = adaptive();
+        for _ in 0..6 {
+            a.record_intervention();
+            assert_eq!(a.adjust(400.0, WINDOW_SECS * 0.6), None);
+        }
+    }
+
+    #[test]
+    fn calm_reading_speeds_up_within_the_bounds() {
+        let mut a = adaptive();
+        assert_eq!(a.adjust(400.0, CALM_SECS - 1.0), None);
+        assert_eq!(a.adjust(400.0, 1.0), Some(412.0));
+        assert_eq!(a.adjust(600.0, CALM_SECS), None);
+
+        let mut a = AdaptiveSpeed { min_wpm: 390.0, ..adaptive() };
+        for _ in 0..INTERVENTIONS_TO_SLOW {
+            a.record_intervention();
+        }
+        assert_eq!(a.adjust(400.0, 0.1), Some(390.0));
+    }
+
+    #[test]
+    fn the_system_counts_pauses_while_reading() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+        world.insert_resource(time);
+        world.init_resource::<OverlayMessage>();
+        world.insert_resource(adaptive());
+        let mut rsvp = RsvpState { wpm: 400.0, ..default() };
+        rsvp.set_pages(vec![vec!["word".to_string(); 100]]);
+        world.insert_resource(rsvp);
+
+        for _ in 0..INTERVENTIONS_TO_SLOW {
+            world.resource_mut::<RsvpState>().is_playing = true;
+            world.run_system_once(adaptive_speed_system).unwrap();
+            world.resource_mut::<RsvpState>().is_playing = false;
+            world.run_system_once(adaptive_speed_system).unwrap();
+        }
+        world.resource_mut::<RsvpState>().is_playing = true;
+        world.run_system_once(adaptive_speed_system).unwrap();
+        assert_eq!(world.resource::<RsvpState>().wpm, 380.0);
+    }
+}
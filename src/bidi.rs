@@ -0,0 +1,61 @@
+// Mixed-direction text. Bevy's text layout already runs the Unicode bidi algorithm on each
+// line, but takes the line's base direction from its first strong character. A chunk cut
+// out of a paragraph can open with a word of the other direction (a Latin term starting a
+// run inside an Arabic sentence), which flips the order of the whole chunk. Prefixing an
+// invisible directional mark pins the chunk to the direction of the page it came from.
+// Words stay in logical order everywhere else, so the ORP pivot is chosen on logical
+// characters and only the final layout is visual.
+
+use unicode_bidi::{get_base_direction, Direction};
+
+const LRM: char = '\u{200E}';
+const RLM: char = '\u{200F}';
+
+/// Direction of the first word on the page with a strong character (`Mixed` if none has one).
+pub fn page_direction(words: &[String]) -> Direction {
+    words
+        .iter()
+        .map(|w| get_base_direction(w.as_str()))
+        .find(|d| *d != Direction::Mixed)
+        .unwrap_or(Direction::Mixed)
+}
+
+/// Mark to put in front of `text` so it lays out in `base` direction, when its own first
+/// strong character would pick the other one.
+pub fn direction_mark(text: &str, base: &Direction) -> Option<char> {
+    match (get_base_direction(text), base) {
+        (Direction::Ltr, Direction::Rtl) => Some(RLM),
+        (Direction::Rtl, Direction::Ltr) => Some(LRM),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn numbers_dont_decide_a_page_direction() {
+        // Digits are weak; the first Arabic word decides
+        assert_eq!(page_direction(&words("2024 في عام PDF")), Direction::Rtl);
+        assert_eq!(page_direction(&words("3.14 is pi")), Direction::Ltr);
+        assert_eq!(page_direction(&words("1 2 3")), Direction::Mixed);
+        assert_eq!(page_direction(&[]), Direction::Mixed);
+    }
+
+    #[test]
+    fn chunks_opening_against_the_page_get_a_mark() {
+        // A Latin term with a number inside an Arabic sentence
+        assert_eq!(direction_mark("PDF3 في", &Direction::Rtl), Some(RLM));
+        // Arabic with digits attached, on an English page
+        assert_eq!(direction_mark("العدد123 is", &Direction::Ltr), Some(LRM));
+        // Already in the page's direction, or digits only: nothing to pin
+        assert_eq!(direction_mark("العدد123", &Direction::Rtl), None);
+        assert_eq!(direction_mark("123 456", &Direction::Rtl), None);
+        assert_eq!(direction_mark("2024 العام", &Direction::Rtl), None);
+    }
+}
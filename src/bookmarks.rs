@@ -0,0 +1,124 @@
+// Named bookmarks, stored per document (keyed like the document preferences) as JSON.
+// They can also be exported to a file and imported back, tagged with the document key so
+// a file made for another document is refused instead of landing on wrong positions.
+
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::doc_prefs::DocumentPrefs;
+use crate::download;
+use crate::i18n::tr;
+use crate::toast::Toasts;
+use crate::{storage, RsvpState};
+
+const KEY_PREFIX: &str = "rsvp_bookmarks_";
+
+// Text of a picked import file, waiting for `bookmark_import_system`
+static IMPORT_QUEUE: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Bookmark {
+    pub page: usize,
+    pub word: usize,
+    pub label: String,
+}
+
+/// On-disk shape of an exported bookmark file.
+#[derive(Serialize, Deserialize)]
+struct BookmarkFile {
+    document: String,
+    bookmarks: Vec<Bookmark>,
+}
+
+pub fn load(doc_key: &str) -> Vec<Bookmark> {
+    storage::load(&format!("{}{}", KEY_PREFIX, doc_key))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(doc_key: &str, bookmarks: &[Bookmark]) {
+    if let Ok(json) = serde_json::to_string(bookmarks) {
+        storage::save(&format!("{}{}", KEY_PREFIX, doc_key), &json);
+    }
+}
+
+/// Offers the bookmarks as a downloaded `bookmarks.json`.
+pub fn export(doc_key: &str, bookmarks: &[Bookmark]) {
+    let file = BookmarkFile { document: doc_key.to_string(), bookmarks: bookmarks.to_vec() };
+    match serde_json::to_string_pretty(&file) {
+        Ok(json) => download::offer("bookmarks.json", "application/json", &json),
+        Err(e) => error!("Could not export bookmarks: {}", e),
+    }
+}
+
+/// Opens the browser's file picker; the chosen file arrives in `bookmark_import_system`.
+#[cfg(target_arch = "wasm32")]
+pub fn pick_import_file() {
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else { return };
+    let Some(input) = document.create_element("input").ok().and_then(|i| i.dyn_into::<web_sys::HtmlInputElement>().ok()) else { return };
+    input.set_type("file");
+    input.set_accept(".json,application/json");
+
+    let picked = input.clone();
+    let on_change = Closure::once(move || {
+        let Some(file) = picked.files().and_then(|files| files.get(0)) else { return };
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(text) = wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                *IMPORT_QUEUE.lock().unwrap() = text.as_string();
+            }
+        });
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    on_change.forget();
+    input.click();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pick_import_file() {
+    warn!("Importing bookmarks needs the web build");
+}
+
+/// Parses an exported file for the document `doc_key`, clamping positions to `rsvp.pages`.
+fn parse_import(json: &str, doc_key: &str, rsvp: &RsvpState) -> Result<Vec<Bookmark>, &'static str> {
+    let file: BookmarkFile = serde_json::from_str(json).map_err(|_| "Not a bookmark file")?;
+    if file.document != doc_key {
+        return Err("These bookmarks belong to a different document");
+    }
+    if rsvp.pages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut bookmarks = file.bookmarks;
+    for bookmark in &mut bookmarks {
+        bookmark.page = bookmark.page.min(rsvp.pages.len() - 1);
+        bookmark.word = bookmark.word.min(rsvp.pages[bookmark.page].len().saturating_sub(1));
+    }
+    Ok(bookmarks)
+}
+
+pub fn bookmark_import_system(
+    mut rsvp: ResMut<RsvpState>,
+    prefs: Res<DocumentPrefs>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(json) = IMPORT_QUEUE.lock().unwrap().take() else { return };
+    let lang = rsvp.lang;
+    let Some(key) = prefs.key() else {
+        toasts.error(tr(lang, "Open a document before importing bookmarks"));
+        return;
+    };
+
+    match parse_import(&json, key, &rsvp) {
+        Ok(bookmarks) => {
+            toasts.success(format!("{}: {}", tr(lang, "Bookmarks imported"), bookmarks.len()));
+            save(key, &bookmarks);
+            rsvp.bookmarks = bookmarks;
+        }
+        Err(e) => toasts.error(tr(lang, e)),
+    }
+}
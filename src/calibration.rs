@@ -0,0 +1,176 @@
+// Speed calibration: plays a bundled passage at rising speeds and asks after each run
+// whether the reader kept up. Speeds climb until the first miss, then bisect between the
+// fastest comfortable speed and the slowest missed one. The result becomes the default WPM.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::i18n::tr;
+use crate::{open_document, storage, ReaderDisplay, RsvpState, WordFade, MAX_WPM, MIN_WPM};
+
+pub const DEFAULT_WPM_KEY: &str = "rsvp_default_wpm";
+
+const START_WPM: f32 = 250.0;
+const STEP_WPM: f32 = 100.0;
+// Stop bisecting once the comfortable and missed speeds are this close
+const CONVERGED_WPM: f32 = 30.0;
+
+// Rotated between runs so a passage isn't being recognized rather than read
+const PASSAGES: &[&str] = &[
+    "The harbor was quiet before dawn. A few boats rocked against the pier while gulls \
+     waited on the posts for the first catch. Down the street, a baker pulled trays from \
+     the oven and set them on the sill to cool, and the smell of bread drifted to the water.",
+    "Most maps leave out what the traveler needs most: where the road is steep, where the \
+     wells are dry, and which bridges wash out in spring. Old guides kept those notes in \
+     the margins, and the best of them read more like letters than like charts.",
+    "A good experiment changes one thing at a time. If the seedlings on the window grow \
+     taller than the ones by the door, the light may be the reason, but so may the warmth \
+     of the glass. Only a second trial, with the pots swapped, can tell the two apart.",
+];
+
+enum Phase {
+    Idle,
+    Reading(f32),
+    Asking(f32),
+    Done(f32),
+}
+
+#[derive(Resource)]
+pub struct Calibration {
+    phase: Phase,
+    // Fastest speed the reader kept up with, and slowest they didn't
+    kept_up: Option<f32>,
+    missed: Option<f32>,
+    runs: usize,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self { phase: Phase::Idle, kept_up: None, missed: None, runs: 0 }
+    }
+}
+
+impl Calibration {
+    pub fn is_active(&self) -> bool {
+        !matches!(self.phase, Phase::Idle)
+    }
+
+    pub fn start(&mut self) {
+        *self = Self { phase: Phase::Reading(START_WPM), ..default() };
+    }
+
+    fn cancel(&mut self) {
+        self.phase = Phase::Idle;
+    }
+
+    /// Records the answer for a run at `wpm` and moves to the next run or the result.
+    fn answer(&mut self, wpm: f32, kept_up: bool) {
+        if kept_up {
+            self.kept_up = Some(wpm);
+        } else {
+            self.missed = Some(wpm);
+        }
+        self.phase = match self.next_speed() {
+            Some(next) => Phase::Reading(next),
+            None => Phase::Done(self.recommended()),
+        };
+    }
+
+    fn next_speed(&self) -> Option<f32> {
+        match (self.kept_up, self.missed) {
+            // Still climbing
+            (Some(low), None) if low >= MAX_WPM => None,
+            (Some(low), None) => Some((low + STEP_WPM).min(MAX_WPM)),
+            (None, Some(high)) if high <= MIN_WPM => None,
+            (None, Some(high)) => Some(round_to_five((high / 2.0).max(MIN_WPM))),
+            (Some(low), Some(high)) if high - low <= CONVERGED_WPM => None,
+            (Some(low), Some(high)) => Some(round_to_five((low + high) / 2.0)),
+            (None, None) => Some(START_WPM),
+        }
+    }
+
+    fn recommended(&self) -> f32 {
+        self.kept_up.unwrap_or(MIN_WPM)
+    }
+}
+
+fn round_to_five(wpm: f32) -> f32 {
+    (wpm / 5.0).round() * 5.0
+}
+
+fn play_run(calibration: &mut Calibration, rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay, wpm: f32) {
+    let passage = PASSAGES[calibration.runs % PASSAGES.len()];
+    calibration.runs += 1;
+    let words = passage.split_whitespace().map(String::from).collect();
+    open_document(rsvp, fade, display, vec![words], Vec::new());
+    rsvp.wpm = wpm;
+    rsvp.effective_wpm = wpm;
+}
+
+pub fn calibration_system(
+    mut contexts: EguiContexts,
+    mut calibration: ResMut<Calibration>,
+    mut rsvp: ResMut<RsvpState>,
+    mut fade: ResMut<WordFade>,
+    mut display: ResMut<ReaderDisplay>,
+    mut started: Local<bool>,
+) {
+    let lang = rsvp.lang;
+    match calibration.phase {
+        Phase::Idle => {
+            *started = false;
+            return;
+        }
+        Phase::Reading(wpm) if !*started => {
+            play_run(&mut calibration, &mut rsvp, &mut fade, &mut display, wpm);
+            *started = true;
+        }
+        Phase::Reading(wpm) if rsvp.finished => {
+            calibration.phase = Phase::Asking(wpm);
+            *started = false;
+        }
+        _ => {}
+    }
+
+    let mut open = true;
+    egui::Window::new(tr(lang, "Speed calibration"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut open)
+        .show(contexts.ctx_mut(), |ui| match calibration.phase {
+            Phase::Idle => {}
+            Phase::Reading(wpm) => {
+                ui.label(format!("{} {:.0} WPM…", tr(lang, "Reading at"), wpm));
+            }
+            Phase::Asking(wpm) => {
+                ui.label(format!("{:.0} WPM: {}", wpm, tr(lang, "Did you keep up?")));
+                ui.horizontal(|ui| {
+                    if ui.button(tr(lang, "Yes")).clicked() {
+                        calibration.answer(wpm, true);
+                    }
+                    if ui.button(tr(lang, "No")).clicked() {
+                        calibration.answer(wpm, false);
+                    }
+                    if ui.button(tr(lang, "Replay")).clicked() {
+                        calibration.phase = Phase::Reading(wpm);
+                    }
+                });
+            }
+            Phase::Done(wpm) => {
+                ui.label(format!("{}: {:.0} WPM", tr(lang, "Recommended speed"), wpm));
+                if ui.button(tr(lang, "Use as my default")).clicked() {
+                    rsvp.wpm = wpm;
+                    rsvp.effective_wpm = wpm;
+                    storage::save(DEFAULT_WPM_KEY, &wpm.to_string());
+                    calibration.cancel();
+                }
+                if ui.button(tr(lang, "Start over")).clicked() {
+                    calibration.start();
+                }
+            }
+        });
+    if !open {
+        calibration.cancel();
+    }
+}
@@ -0,0 +1,131 @@
+// Comprehension checks (study aid): every N paragraphs read, playback pauses on a short
+// self-check question with Continue and Rewind buttons. Paragraph ends come from the
+// parsers' `paragraph_end` flags, so formats without paragraph structure never prompt.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::i18n::{tr, Lang};
+use crate::{jump_to, ReaderDisplay, RsvpState, WordFade};
+
+const PROMPTS: &[&str] = &[
+    "Could you summarize the last section in a sentence?",
+    "What was the main point of what you just read?",
+    "Which detail from the last section stood out?",
+];
+
+#[derive(Resource)]
+pub struct ComprehensionCheck {
+    pub enabled: bool,
+    pub every_paragraphs: usize,
+    paragraphs: usize,
+    // Flat index where the current section began, for Rewind
+    section_start: usize,
+    last_chunk: Option<(usize, usize, usize)>,
+    last_total: usize,
+    asked: usize,
+    // Showing a prompt; playback stays paused until it's answered
+    prompting: bool,
+}
+
+impl Default for ComprehensionCheck {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            every_paragraphs: 5,
+            paragraphs: 0,
+            section_start: 0,
+            last_chunk: None,
+            last_total: 0,
+            asked: 0,
+            prompting: false,
+        }
+    }
+}
+
+impl ComprehensionCheck {
+    fn start_section(&mut self, flat: usize) {
+        self.paragraphs = 0;
+        self.section_start = flat;
+    }
+}
+
+pub fn comprehension_system(
+    mut contexts: EguiContexts,
+    mut rsvp: ResMut<RsvpState>,
+    mut check: ResMut<ComprehensionCheck>,
+    mut display: ResMut<ReaderDisplay>,
+    mut fade: ResMut<WordFade>,
+) {
+    if !check.enabled {
+        check.prompting = false;
+        return;
+    }
+
+    if !check.prompting && rsvp.displayed_chunk != check.last_chunk {
+        let previous = check.last_chunk;
+        check.last_chunk = rsvp.displayed_chunk;
+        let Some((page, start, end)) = rsvp.displayed_chunk else { return };
+        let first = rsvp.flat_index(page, start);
+
+        // A new document or a jump back starts the count over
+        let moved_back = previous.is_some_and(|(p, s, _)| first < rsvp.flat_index(p, s));
+        if rsvp.total_words != check.last_total || moved_back {
+            check.last_total = rsvp.total_words;
+            check.start_section(first);
+        }
+
+        if end > start && rsvp.word_style(page, end - 1).paragraph_end && rsvp.is_playing {
+            check.paragraphs += 1;
+            if check.paragraphs >= check.every_paragraphs.max(1) {
+                check.prompting = true;
+                rsvp.is_playing = false;
+            }
+        }
+    }
+
+    if !check.prompting {
+        return;
+    }
+    let lang = rsvp.lang;
+    let mut answer = None;
+    egui::Window::new(tr(lang, "Quick check"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr(lang, PROMPTS[check.asked % PROMPTS.len()]));
+            ui.horizontal(|ui| {
+                if ui.button(tr(lang, "Continue")).clicked() {
+                    answer = Some(false);
+                }
+                if ui.button(tr(lang, "Rewind")).clicked() {
+                    answer = Some(true);
+                }
+            });
+        });
+
+    let Some(rewind) = answer else { return };
+    check.prompting = false;
+    check.asked += 1;
+    if rewind {
+        let (page, word) = rsvp.position_from_flat(check.section_start);
+        jump_to(&mut rsvp, &mut display, &mut fade, page, word);
+        // The section is read again before the next prompt
+        check.last_chunk = rsvp.displayed_chunk;
+        let start = check.section_start;
+        check.start_section(start);
+    } else {
+        let next = rsvp.flat_index(rsvp.current_page_index, rsvp.current_word_index);
+        check.start_section(next);
+    }
+    rsvp.is_playing = true;
+}
+
+/// Playback-panel controls: the toggle and the interval.
+pub fn comprehension_controls(ui: &mut egui::Ui, check: &mut ComprehensionCheck, lang: Lang) {
+    ui.checkbox(&mut check.enabled, tr(lang, "Comprehension checks"));
+    if check.enabled {
+        ui.add(egui::Slider::new(&mut check.every_paragraphs, 1..=20).text(tr(lang, "Paragraphs between checks")));
+    }
+}
@@ -0,0 +1,209 @@
+// Definitions: while paused, the words on screen get "Define" buttons that look the word up
+// in a free online dictionary and show the senses in a small window. Lookups are cached per
+// word for the session; offline or unknown words just show the failure in the window.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::i18n::tr;
+use crate::RsvpState;
+
+// Senses shown per word; the API can return dozens
+#[cfg(target_arch = "wasm32")]
+const MAX_SENSES: usize = 6;
+
+// Finished lookups, written by the fetch tasks and drained by `definitions_system`
+static LOOKUP_RESULTS: Mutex<Vec<(String, Result<Vec<Sense>, String>)>> = Mutex::new(Vec::new());
+
+pub struct Sense {
+    part_of_speech: String,
+    definition: String,
+}
+
+enum Lookup {
+    Loading,
+    Found(Vec<Sense>),
+    Failed(String),
+}
+
+#[derive(Resource, Default)]
+pub struct Definitions {
+    pub enabled: bool,
+    cache: HashMap<String, Lookup>,
+    // Word whose definition window is open
+    shown: Option<String>,
+}
+
+impl Definitions {
+    fn define(&mut self, word: String) {
+        if !matches!(self.cache.get(&word), Some(Lookup::Found(_))) {
+            self.cache.insert(word.clone(), Lookup::Loading);
+            spawn_lookup(word.clone());
+        }
+        self.shown = Some(word);
+    }
+}
+
+/// The dictionary form of a displayed token: edge punctuation dropped and lowercased, or
+/// `None` when nothing word-like is left.
+fn lookup_key(token: &str) -> Option<String> {
+    let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+    (word.chars().any(char::is_alphabetic)).then(|| word.to_lowercase())
+}
+
+// Response shape of dictionaryapi.dev: entries -> meanings -> definitions
+#[cfg(target_arch = "wasm32")]
+#[derive(serde::Deserialize)]
+struct Entry {
+    meanings: Vec<Meaning>,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meaning {
+    part_of_speech: String,
+    definitions: Vec<Definition>,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(serde::Deserialize)]
+struct Definition {
+    definition: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+fn parse_senses(json: &str) -> Result<Vec<Sense>, String> {
+    let entries: Vec<Entry> = serde_json::from_str(json).map_err(|_| "No definition found".to_string())?;
+    let senses: Vec<Sense> = entries
+        .into_iter()
+        .flat_map(|entry| entry.meanings)
+        .flat_map(|meaning| {
+            let part_of_speech = meaning.part_of_speech;
+            meaning.definitions.into_iter().map(move |d| Sense { part_of_speech: part_of_speech.clone(), definition: d.definition })
+        })
+        .take(MAX_SENSES)
+        .collect();
+    if senses.is_empty() {
+        return Err("No definition found".to_string());
+    }
+    Ok(senses)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn_lookup(word: String) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = fetch_definition(&word).await;
+        LOOKUP_RESULTS.lock().unwrap().push((word, result));
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_definition(word: &str) -> Result<Vec<Sense>, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("No window")?;
+    let url = format!("https://api.dictionaryapi.dev/api/v2/entries/en/{}", String::from(js_sys::encode_uri_component(word)));
+    let response = JsFuture::from(window.fetch_with_str(&url))
+        .await
+        .map_err(|_| "Dictionary unreachable (offline?)".to_string())?;
+    let response: web_sys::Response = response.dyn_into().map_err(|_| "Unexpected fetch result")?;
+    // The API answers unknown words with a 404 and an explanation object
+    if response.status() == 404 {
+        return Err("No definition found".to_string());
+    }
+    if !response.ok() {
+        return Err(format!("Dictionary returned HTTP {}", response.status()));
+    }
+    let text = response.text().map_err(|e| format!("{:?}", e))?;
+    let json = JsFuture::from(text)
+        .await
+        .map_err(|e| format!("Could not read response: {:?}", e))?
+        .as_string()
+        .ok_or_else(|| "Response was not text".to_string())?;
+    parse_senses(&json)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_lookup(word: String) {
+    LOOKUP_RESULTS.lock().unwrap().push((word, Err("Looking up definitions needs the web build".to_string())));
+}
+
+pub fn definitions_system(mut contexts: EguiContexts, rsvp: Res<RsvpState>, mut definitions: ResMut<Definitions>) {
+    for (word, result) in LOOKUP_RESULTS.lock().unwrap().drain(..) {
+        let lookup = match result {
+            Ok(senses) => Lookup::Found(senses),
+            Err(e) => Lookup::Failed(e),
+        };
+        definitions.cache.insert(word, lookup);
+    }
+
+    if !definitions.enabled {
+        definitions.shown = None;
+        return;
+    }
+    let lang = rsvp.lang;
+    let ctx = contexts.ctx_mut();
+
+    // Offered only while paused on real text
+    let on_screen: Vec<String> = match rsvp.displayed_chunk {
+        Some((page, start, end)) if !rsvp.is_playing && rsvp.document_loaded && page < rsvp.pages.len() => {
+            let mut words: Vec<String> = rsvp.pages[page][start..end.min(rsvp.pages[page].len())]
+                .iter()
+                .filter_map(|w| lookup_key(w))
+                .collect();
+            words.dedup();
+            words
+        }
+        _ => Vec::new(),
+    };
+    if on_screen.is_empty() {
+        definitions.shown = None;
+        return;
+    }
+
+    egui::Area::new(egui::Id::new("define_buttons"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -100.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for word in &on_screen {
+                    if ui.button(format!("{} “{}”", tr(lang, "Define"), word)).clicked() {
+                        definitions.define(word.clone());
+                    }
+                }
+            });
+        });
+
+    let Some(word) = definitions.shown.clone() else { return };
+    let mut open = true;
+    let mut retry = false;
+    egui::Window::new(&word)
+        .id(egui::Id::new("definition"))
+        .collapsible(false)
+        .resizable(false)
+        .default_width(300.0)
+        .open(&mut open)
+        .show(ctx, |ui| match definitions.cache.get(&word) {
+            Some(Lookup::Found(senses)) => {
+                for sense in senses {
+                    ui.label(format!("({}) {}", sense.part_of_speech, sense.definition));
+                }
+            }
+            Some(Lookup::Failed(e)) => {
+                ui.label(e.as_str());
+                retry = ui.button(tr(lang, "Try again")).clicked();
+            }
+            Some(Lookup::Loading) | None => {
+                ui.spinner();
+            }
+        });
+    if retry {
+        definitions.define(word);
+    } else if !open {
+        definitions.shown = None;
+    }
+}
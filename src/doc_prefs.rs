@@ -0,0 +1,204 @@
+// Per-document reading preferences (speed, font, text size and words per frame) and reading
+// position, keyed by a hash of the uploaded file so reopening a document restores them.
+// Documents seen for the first time keep whatever global values are active and start from
+// the top.
+
+use bevy::prelude::*;
+
+use crate::{current_position, storage, RsvpState};
+
+const KEY_PREFIX: &str = "rsvp_doc_prefs_";
+const POSITION_KEY_PREFIX: &str = "rsvp_doc_position_";
+const USE_GLOBAL_KEY: &str = "rsvp_use_global_settings";
+const SAVE_INTERVAL_SECS: f32 = 2.0;
+
+/// Stable identifier for a document: FNV-1a over its bytes, as hex.
+pub fn document_key(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SavedPrefs {
+    pub wpm: f32,
+    pub font: String,
+    // `None` when saved before these were remembered; the global values stay then
+    pub font_size: Option<f32>,
+    pub words_per_frame: Option<usize>,
+}
+
+impl SavedPrefs {
+    /// Format: "wpm|size|words|font", with the font last since it's free text. The older
+    /// "wpm|font" still loads.
+    fn parse(saved: &str) -> Option<Self> {
+        let (wpm, rest) = saved.split_once('|')?;
+        let wpm = wpm.parse().ok()?;
+        let mut fields = rest.splitn(3, '|');
+        if let (Some(size), Some(words), Some(font)) = (fields.next(), fields.next(), fields.next()) {
+            if let (Ok(size), Ok(words)) = (size.parse(), words.parse()) {
+                return Some(Self { wpm, font: font.to_string(), font_size: Some(size), words_per_frame: Some(words) });
+            }
+        }
+        Some(Self { wpm, font: rest.to_string(), font_size: None, words_per_frame: None })
+    }
+
+    fn serialize(&self) -> String {
+        match (self.font_size, self.words_per_frame) {
+            (Some(size), Some(words)) => format!("{}|{}|{}|{}", self.wpm, size, words, self.font),
+            _ => format!("{}|{}", self.wpm, self.font),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct DocumentPrefs {
+    // When set, saved per-document values (settings and reading position alike) are neither
+    // applied nor overwritten; the global ones always win and every document starts from
+    // the top, and switching back finds each document's values as they were.
+    pub use_global: bool,
+    key: Option<String>,
+    // What storage currently holds for `key`, so unchanged settings aren't rewritten
+    saved: Option<SavedPrefs>,
+    // (page, word) storage holds for `key`
+    saved_position: Option<(usize, usize)>,
+    save_timer: f32,
+}
+
+impl Default for DocumentPrefs {
+    fn default() -> Self {
+        Self {
+            use_global: storage::load(USE_GLOBAL_KEY).is_some_and(|v| v == "1"),
+            key: None,
+            saved: None,
+            saved_position: None,
+            save_timer: 0.0,
+        }
+    }
+}
+
+impl DocumentPrefs {
+    /// Key of the open document, if it came from an upload.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    pub fn save_use_global(&self) {
+        storage::save(USE_GLOBAL_KEY, if self.use_global { "1" } else { "0" });
+    }
+
+    /// Makes `key` the current document and returns the saved preferences to apply, if any.
+    pub fn open(&mut self, key: String) -> Option<SavedPrefs> {
+        self.saved = storage::load(&format!("{}{}", KEY_PREFIX, key)).and_then(|saved| SavedPrefs::parse(&saved));
+        // Format: "page|word"
+        self.saved_position = storage::load(&format!("{}{}", POSITION_KEY_PREFIX, key)).and_then(|saved| {
+            let (page, word) = saved.split_once('|')?;
+            Some((page.parse().ok()?, word.parse().ok()?))
+        });
+        self.key = Some(key);
+        self.save_timer = 0.0;
+        if self.use_global {
+            return None;
+        }
+        self.saved.clone()
+    }
+
+    /// Where reading stopped last time the document was open, as (page, word); `None` while
+    /// `use_global` is on.
+    pub fn saved_position(&self) -> Option<(usize, usize)> {
+        self.saved_position.filter(|_| !self.use_global)
+    }
+}
+
+pub fn document_prefs_system(time: Res<Time>, rsvp: Res<RsvpState>, mut prefs: ResMut<DocumentPrefs>) {
+    let Some(key) = prefs.key.clone() else { return };
+
+    prefs.save_timer += time.delta_secs();
+    if prefs.save_timer < SAVE_INTERVAL_SECS {
+        return;
+    }
+    prefs.save_timer = 0.0;
+    if prefs.use_global {
+        return;
+    }
+
+    // Nothing on screen yet means the document has only just been opened
+    if rsvp.displayed_chunk.is_some() {
+        let position = current_position(&rsvp);
+        if prefs.saved_position != Some(position) {
+            storage::save(&format!("{}{}", POSITION_KEY_PREFIX, key), &format!("{}|{}", position.0, position.1));
+            prefs.saved_position = Some(position);
+        }
+    }
+
+    let current = SavedPrefs {
+        wpm: rsvp.wpm,
+        font: rsvp.current_font_name.clone(),
+        font_size: Some(rsvp.font_size),
+        words_per_frame: Some(rsvp.words_per_frame),
+    };
+    if prefs.saved.as_ref() != Some(&current) {
+        storage::save(&format!("{}{}", KEY_PREFIX, key), &current.serialize());
+        prefs.saved = Some(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn store(key: &str, prefs: &str, position: &str) {
+        storage::save(&format!("{}{}", KEY_PREFIX, key), prefs);
+        storage::save(&format!("{}{}", POSITION_KEY_PREFIX, key), position);
+    }
+
+    fn prefs(use_global: bool) -> DocumentPrefs {
+        DocumentPrefs { use_global, ..default() }
+    }
+
+    #[test]
+    fn per_document_values_apply_unless_global_settings_are_forced() {
+        store("precedence", "420|90|2|Tinos-Regular.ttf", "3|17");
+
+        let mut per_document = prefs(false);
+        let saved = per_document.open("precedence".to_string()).unwrap();
+        assert_eq!((saved.wpm, saved.font_size, saved.words_per_frame), (420.0, Some(90.0), Some(2)));
+        assert_eq!(saved.font, "Tinos-Regular.ttf");
+        assert_eq!(per_document.saved_position(), Some((3, 17)));
+
+        let mut global = prefs(true);
+        assert!(global.open("precedence".to_string()).is_none());
+        assert_eq!(global.saved_position(), None);
+    }
+
+    #[test]
+    fn forced_global_settings_leave_saved_values_alone() {
+        store("untouched", "420|90|2|Tinos-Regular.ttf", "3|17");
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(SAVE_INTERVAL_SECS + 1.0));
+        world.insert_resource(time);
+        let mut rsvp = RsvpState { wpm: 250.0, ..default() };
+        rsvp.set_pages(vec![vec!["word".to_string()]]);
+        rsvp.displayed_chunk = Some((0, 0, 1));
+        world.insert_resource(rsvp);
+
+        let mut global = prefs(true);
+        global.open("untouched".to_string());
+        world.insert_resource(global);
+        world.run_system_once(document_prefs_system).unwrap();
+        assert_eq!(storage::load(&format!("{}untouched", KEY_PREFIX)).unwrap(), "420|90|2|Tinos-Regular.ttf");
+        assert_eq!(storage::load(&format!("{}untouched", POSITION_KEY_PREFIX)).unwrap(), "3|17");
+
+        // Per-document memory saves the reader's own values again
+        world.resource_mut::<DocumentPrefs>().use_global = false;
+        world.run_system_once(document_prefs_system).unwrap();
+        assert!(storage::load(&format!("{}untouched", KEY_PREFIX)).unwrap().starts_with("250|"));
+        assert_eq!(storage::load(&format!("{}untouched", POSITION_KEY_PREFIX)).unwrap(), "0|0");
+    }
+}
@@ -0,0 +1,83 @@
+// Word (.docx) documents: the archive's word/document.xml is walked in order, paragraphs
+// end blocks, headings and bold/italic runs carry over as word styles, and explicit page
+// breaks start a new page.
+
+use std::io::{Cursor, Read};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::ZipArchive;
+
+use crate::styled_text::StyledTextBuilder;
+use crate::WordStyle;
+
+const DOCUMENT_PART: &str = "word/document.xml";
+
+/// Whether `bytes` are a zip archive (`PK` magic) containing a Word document body.
+pub fn is_docx(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"PK\x03\x04")
+        && ZipArchive::new(Cursor::new(bytes)).is_ok_and(|mut zip| zip.by_name(DOCUMENT_PART).is_ok())
+}
+
+fn attr(e: &BytesStart, name: &str) -> Option<String> {
+    let attr = e.try_get_attribute(name).ok()??;
+    Some(String::from_utf8_lossy(&attr.value).into_owned())
+}
+
+/// Toggle properties like `<w:b/>` are on unless explicitly `w:val="0"`/`"false"`.
+fn toggled_on(e: &BytesStart) -> bool {
+    !matches!(attr(e, "w:val").as_deref(), Some("0" | "false" | "off"))
+}
+
+pub fn parse_docx(bytes: &[u8]) -> Result<(Vec<Vec<String>>, Vec<Vec<WordStyle>>), String> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Damaged DOCX archive: {}", e))?;
+    let mut xml = String::new();
+    zip.by_name(DOCUMENT_PART)
+        .map_err(|e| format!("DOCX has no {}: {}", DOCUMENT_PART, e))?
+        .read_to_string(&mut xml)
+        .map_err(|e| format!("Could not read {}: {}", DOCUMENT_PART, e))?;
+
+    let mut reader = Reader::from_str(&xml);
+    let mut b = StyledTextBuilder::default();
+    let mut in_text = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| format!("Malformed DOCX XML: {}", e))?;
+        match event {
+            // `<w:t/>` has no text and no end tag, so only a real start tag opens text
+            Event::Start(e) if e.name().as_ref() == b"w:t" => in_text = true,
+            Event::Start(e) | Event::Empty(e) => match e.name().as_ref() {
+                // Runs carry their own formatting; reset it before reading this run's w:rPr
+                b"w:r" => {
+                    b.strong_depth = 0;
+                    b.emphasis_depth = 0;
+                }
+                b"w:b" => b.strong_depth = usize::from(toggled_on(&e)),
+                b"w:i" => b.emphasis_depth = usize::from(toggled_on(&e)),
+                b"w:pStyle" => {
+                    b.heading = attr(&e, "w:val").is_some_and(|style| style.starts_with("Heading") || style == "Title");
+                }
+                b"w:tab" => b.flush_word(),
+                b"w:br" if attr(&e, "w:type").as_deref() == Some("page") => b.end_page(),
+                b"w:br" | b"w:cr" => b.flush_word(),
+                _ => {}
+            },
+            Event::Text(t) if in_text => {
+                let text = t.unescape().map_err(|e| format!("Malformed DOCX text: {}", e))?;
+                b.push_text(&text);
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"w:t" => in_text = false,
+                b"w:p" => {
+                    b.end_block();
+                    b.heading = false;
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(b.finish())
+}
@@ -0,0 +1,29 @@
+// Saving generated text as a file. The web build hands the browser a Blob through a
+// temporary download link; native builds write the file into the working directory.
+
+#[cfg(target_arch = "wasm32")]
+pub fn offer(file_name: &str, mime: &str, text: &str) {
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else { return };
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(text));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options) else { return };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else { return };
+
+    if let Some(anchor) = document.create_element("a").ok().and_then(|a| a.dyn_into::<web_sys::HtmlAnchorElement>().ok()) {
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+    }
+    let _ = web_sys::Url::revoke_object_url(&url);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn offer(file_name: &str, _mime: &str, text: &str) {
+    match std::fs::write(file_name, text) {
+        Ok(()) => bevy::log::info!("Saved {}", file_name),
+        Err(e) => bevy::log::error!("Could not save {}: {}", file_name, e),
+    }
+}
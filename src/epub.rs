@@ -0,0 +1,143 @@
+// EPUB books: a zip of XHTML chapters. META-INF/container.xml points at the package
+// document, whose spine gives the reading order of the manifest's chapter files. Each
+// chapter's visible text is read the way HTML uploads are, and starts a page of its own.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use bevy::log::warn;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::ZipArchive;
+
+use crate::html::push_html;
+use crate::styled_text::StyledTextBuilder;
+use crate::WordStyle;
+
+const CONTAINER_PART: &str = "META-INF/container.xml";
+const CHAPTER_TYPES: &[&str] = &["application/xhtml+xml", "text/html"];
+
+type Archive<'a> = ZipArchive<Cursor<&'a [u8]>>;
+
+/// Whether `bytes` are a zip archive (`PK` magic) laid out as an EPUB.
+pub fn is_epub(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"PK\x03\x04")
+        && ZipArchive::new(Cursor::new(bytes)).is_ok_and(|mut zip| zip.by_name(CONTAINER_PART).is_ok())
+}
+
+fn attr(e: &BytesStart, name: &str) -> Option<String> {
+    let attr = e.try_get_attribute(name).ok()??;
+    Some(attr.unescape_value().ok()?.into_owned())
+}
+
+fn read_part(zip: &mut Archive, path: &str) -> Result<String, String> {
+    let mut text = String::new();
+    zip.by_name(path)
+        .map_err(|e| format!("EPUB has no {}: {}", path, e))?
+        .read_to_string(&mut text)
+        .map_err(|e| format!("Could not read {}: {}", path, e))?;
+    Ok(text)
+}
+
+/// Hrefs are URLs: undoes percent-encoding (`Chapter%201.xhtml`) and drops any `#fragment`.
+fn decode_href(href: &str) -> String {
+    let href = href.split('#').next().unwrap_or_default();
+    let bytes = href.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(byte) if bytes[i] == b'%' => {
+                out.push(byte);
+                i += 3;
+            }
+            _ => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Archive path of `href`, which is relative to the package document in `base_dir`.
+fn resolve(base_dir: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Path of the package (.opf) document, from the container's first rootfile.
+fn package_path(container: &str) -> Result<String, String> {
+    let mut reader = Reader::from_str(container);
+    loop {
+        match reader.read_event().map_err(|e| format!("Malformed {}: {}", CONTAINER_PART, e))? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"rootfile" => {
+                if let Some(path) = attr(&e, "full-path") {
+                    return Ok(decode_href(&path));
+                }
+            }
+            Event::Eof => return Err("EPUB container lists no package document".to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Archive paths of the chapters, in spine order. Spine entries marked `linear="no"` (notes,
+/// pop-ups) are outside the main reading order and skipped.
+fn spine_chapters(package: &str, base_dir: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(package);
+    let mut manifest: HashMap<String, String> = HashMap::new();
+    let mut spine: Vec<String> = Vec::new();
+    loop {
+        match reader.read_event().map_err(|e| format!("Malformed EPUB package: {}", e))? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"item" => {
+                    let is_chapter = attr(&e, "media-type").is_some_and(|t| CHAPTER_TYPES.contains(&t.as_str()));
+                    if let (true, Some(id), Some(href)) = (is_chapter, attr(&e, "id"), attr(&e, "href")) {
+                        manifest.insert(id, resolve(base_dir, &decode_href(&href)));
+                    }
+                }
+                b"itemref" if attr(&e, "linear").as_deref() != Some("no") => {
+                    spine.extend(attr(&e, "idref"));
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(spine.iter().filter_map(|id| manifest.get(id).cloned()).collect())
+}
+
+/// Splits an EPUB into one page per chapter (more where a chapter has several `<h1>`s), with
+/// a parallel style for each word.
+pub fn parse_epub(bytes: &[u8]) -> Result<(Vec<Vec<String>>, Vec<Vec<WordStyle>>), String> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Damaged EPUB archive: {}", e))?;
+    let package_path = package_path(&read_part(&mut zip, CONTAINER_PART)?)?;
+    let base_dir = package_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let chapters = spine_chapters(&read_part(&mut zip, &package_path)?, base_dir)?;
+    if chapters.is_empty() {
+        return Err("EPUB spine lists no chapters".to_string());
+    }
+
+    let mut b = StyledTextBuilder::default();
+    for chapter in &chapters {
+        // One missing or unreadable chapter shouldn't cost the reader the rest of the book
+        match read_part(&mut zip, chapter) {
+            Ok(xhtml) => push_html(&xhtml, &mut b),
+            Err(e) => warn!("Skipping EPUB chapter: {}", e),
+        }
+        b.end_page();
+    }
+    Ok(b.finish())
+}
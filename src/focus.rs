@@ -0,0 +1,67 @@
+// Focus mode: a spotlight on the word. Everything outside a box around the reader text —
+// control panels, context lines, the page behind — is painted over with a dark veil. The
+// panel menu sits above the veil so the settings stay reachable.
+
+use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{ReaderAlign, ReaderText, RsvpState, LEFT_ALIGN_MARGIN_PERCENT};
+
+const FADE_SECS: f32 = 0.3;
+// Space left lit around the text, in font sizes
+const SPOTLIGHT_PAD: f32 = 0.5;
+
+pub fn focus_mode_system(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    rsvp: Res<RsvpState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    text: Query<&TextLayoutInfo, With<ReaderText>>,
+    // 0 = no veil, 1 = fully dimmed
+    mut strength: Local<f32>,
+) {
+    let target = if rsvp.focus_mode { 1.0 } else { 0.0 };
+    *strength = if rsvp.reduce_motion {
+        target
+    } else {
+        let step = time.delta_secs() / FADE_SECS;
+        if target > *strength { (*strength + step).min(target) } else { (*strength - step).max(target) }
+    };
+    if *strength <= 0.0 {
+        return;
+    }
+
+    let scale = windows.get_single().map_or(1.0, |w| w.scale_factor());
+    // Layout sizes are in physical pixels, egui works in logical points
+    let text_size = text.get_single().map_or(Vec2::ZERO, |info| info.size / scale);
+
+    let ctx = contexts.ctx_mut();
+    let screen = ctx.screen_rect();
+    let pad = rsvp.font_size * SPOTLIGHT_PAD;
+    let width = text_size.x.max(rsvp.font_size) + 2.0 * pad;
+    let height = text_size.y.max(rsvp.font_size) + 2.0 * pad;
+    let center_y = screen.top() + screen.height() * rsvp.vertical_position / 100.0;
+    let left = match rsvp.align {
+        ReaderAlign::Center => screen.center().x - width / 2.0,
+        ReaderAlign::Left => screen.left() + screen.width() * LEFT_ALIGN_MARGIN_PERCENT / 100.0 - pad,
+    };
+    let spot = egui::Rect::from_min_size(egui::pos2(left, center_y - height / 2.0), egui::vec2(width, height));
+
+    let alpha = (rsvp.focus_dim.clamp(0.0, 1.0) * *strength * 255.0) as u8;
+    let veil = egui::Color32::from_black_alpha(alpha);
+    // Painted after every panel and window, but under the Foreground panel menu
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Middle, egui::Id::new("focus_veil")));
+    let range = |a: f32, b: f32| egui::Rangef::new(a, b);
+    for rect in [
+        egui::Rect::from_x_y_ranges(screen.x_range(), range(screen.top(), spot.top())),
+        egui::Rect::from_x_y_ranges(screen.x_range(), range(spot.bottom(), screen.bottom())),
+        egui::Rect::from_x_y_ranges(range(screen.left(), spot.left()), spot.y_range()),
+        egui::Rect::from_x_y_ranges(range(spot.right(), screen.right()), spot.y_range()),
+    ] {
+        if rect.is_positive() {
+            painter.rect_filled(rect, 0.0, veil);
+        }
+    }
+}
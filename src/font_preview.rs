@@ -0,0 +1,71 @@
+// Font previews for the egui font selector. Bevy text and egui have separate font systems,
+// so once a Bevy `Font` asset finishes loading its bytes are also registered with egui
+// under a named family, letting each ComboBox entry be drawn in its own face.
+
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+#[derive(Resource, Default)]
+pub struct FontPreviews {
+    // Handles kept alive so every selectable font gets loaded, not only the active one
+    handles: Vec<(String, Handle<Font>)>,
+    definitions: Option<egui::FontDefinitions>,
+    // Sent to egui this frame; egui applies new fonts at the start of the next pass
+    pending: BTreeSet<String>,
+    ready: BTreeSet<String>,
+}
+
+impl FontPreviews {
+    pub fn track(&mut self, name: impl Into<String>, handle: Handle<Font>) {
+        let name = name.into();
+        if !self.handles.iter().any(|(n, _)| *n == name) {
+            self.handles.push((name, handle));
+        }
+    }
+
+    /// Label for a font entry, drawn in that font once egui knows about it.
+    pub fn label(&self, name: &str) -> egui::RichText {
+        let text = egui::RichText::new(name);
+        if self.ready.contains(name) {
+            text.family(egui::FontFamily::Name(name.into()))
+        } else {
+            text
+        }
+    }
+}
+
+pub fn register_font_previews_system(
+    mut contexts: EguiContexts,
+    fonts: Res<Assets<Font>>,
+    mut previews: ResMut<FontPreviews>,
+) {
+    let previews = &mut *previews;
+    let applied = std::mem::take(&mut previews.pending);
+    previews.ready.extend(applied);
+
+    let mut added = false;
+    for (name, handle) in &previews.handles {
+        if previews.ready.contains(name) {
+            continue;
+        }
+        let Some(font) = fonts.get(handle) else { continue };
+
+        let defs = previews.definitions.get_or_insert_with(egui::FontDefinitions::default);
+        defs.font_data.insert(name.clone(), egui::FontData::from_owned(font.data.to_vec()));
+        // Fall back to egui's proportional fonts for glyphs the face lacks
+        let mut family = vec![name.clone()];
+        family.extend(defs.families.get(&egui::FontFamily::Proportional).cloned().unwrap_or_default());
+        defs.families.insert(egui::FontFamily::Name(name.as_str().into()), family);
+
+        previews.pending.insert(name.clone());
+        added = true;
+    }
+
+    if added {
+        if let Some(defs) = previews.definitions.clone() {
+            contexts.ctx_mut().set_fonts(defs);
+        }
+    }
+}
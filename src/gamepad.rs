@@ -0,0 +1,83 @@
+// Controller input for reading away from the keyboard. The buttons drive the same helpers as
+// the keys and playback buttons: South plays and pauses (or steps in manual mode), the
+// shoulders turn pages, the triggers change speed, the D-pad steps words and Select goes
+// back after a jump. Any number of controllers can be connected; unplugging one while
+// reading pauses playback so the text doesn't run on unattended.
+
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::prelude::*;
+
+use crate::i18n::tr;
+use crate::toast::Toasts;
+use crate::{advance, go_back, jump_to, step_words, AdvanceMode, OverlayMessage, ReaderDisplay, RsvpState, WordFade, MAX_WPM, MIN_WPM};
+
+const WPM_STEP: f32 = 25.0;
+
+pub fn gamepad_input_system(
+    gamepads: Query<&Gamepad>,
+    mut connections: EventReader<GamepadConnectionEvent>,
+    mut rsvp: ResMut<RsvpState>,
+    mut display: ResMut<ReaderDisplay>,
+    mut fade: ResMut<WordFade>,
+    mut overlay: ResMut<OverlayMessage>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let lang = rsvp.lang;
+    for event in connections.read() {
+        match &event.connection {
+            GamepadConnection::Connected { name, .. } => toasts.info(format!("{}: {}", tr(lang, "Controller connected"), name)),
+            GamepadConnection::Disconnected => {
+                toasts.info(tr(lang, "Controller disconnected"));
+                if rsvp.is_playing {
+                    rsvp.is_playing = false;
+                }
+            }
+        }
+    }
+
+    if rsvp.pages.is_empty() {
+        return;
+    }
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::South) {
+            if rsvp.advance_mode == AdvanceMode::Manual {
+                if !rsvp.finished {
+                    advance(&mut rsvp, &mut fade, &mut display);
+                }
+            } else if rsvp.finished {
+                jump_to(&mut rsvp, &mut display, &mut fade, 0, 0);
+                rsvp.is_playing = true;
+            } else {
+                rsvp.is_playing = !rsvp.is_playing;
+            }
+        }
+
+        let page = rsvp.current_page_index;
+        if gamepad.just_pressed(GamepadButton::LeftTrigger) && page > 0 {
+            jump_to(&mut rsvp, &mut display, &mut fade, page - 1, 0);
+        }
+        if gamepad.just_pressed(GamepadButton::RightTrigger) && page + 1 < rsvp.pages.len() {
+            jump_to(&mut rsvp, &mut display, &mut fade, page + 1, 0);
+        }
+
+        let wpm_change = match (gamepad.just_pressed(GamepadButton::LeftTrigger2), gamepad.just_pressed(GamepadButton::RightTrigger2)) {
+            (true, false) => -WPM_STEP,
+            (false, true) => WPM_STEP,
+            _ => 0.0,
+        };
+        if wpm_change != 0.0 {
+            rsvp.wpm = (rsvp.wpm + wpm_change).clamp(MIN_WPM, MAX_WPM);
+            overlay.show(format!("{:.0} WPM", rsvp.wpm), 1.0);
+        }
+
+        if gamepad.just_pressed(GamepadButton::DPadLeft) {
+            step_words(&mut rsvp, &mut display, &mut fade, -1);
+        }
+        if gamepad.just_pressed(GamepadButton::DPadRight) {
+            step_words(&mut rsvp, &mut display, &mut fade, 1);
+        }
+        if gamepad.just_pressed(GamepadButton::Select) && !go_back(&mut rsvp, &mut display, &mut fade) {
+            overlay.show(tr(lang, "No earlier position"), 1.0);
+        }
+    }
+}
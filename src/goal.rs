@@ -0,0 +1,134 @@
+// Daily reading goal: words read today vs a target, persisted so reloads within the
+// same day keep the count, and reset when the local date changes.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::i18n::tr;
+use crate::{storage, OverlayMessage, RsvpState};
+
+const STORAGE_KEY: &str = "rsvp_daily_goal";
+// localStorage writes are cheap but not free; batch them
+const SAVE_INTERVAL_SECS: f32 = 2.0;
+
+#[derive(Resource)]
+pub struct ReadingGoal {
+    pub daily_word_goal: usize,
+    pub words_today: usize,
+    day: String,
+    reached: bool,
+    // RsvpState::words_advanced at the last frame, to count only new words
+    last_advanced: u64,
+    dirty: bool,
+    save_timer: f32,
+}
+
+impl Default for ReadingGoal {
+    fn default() -> Self {
+        let mut goal = Self {
+            daily_word_goal: 5000,
+            words_today: 0,
+            day: storage::today(),
+            reached: false,
+            last_advanced: 0,
+            dirty: false,
+            save_timer: 0.0,
+        };
+        goal.restore();
+        goal
+    }
+}
+
+impl ReadingGoal {
+    pub fn fraction(&self) -> f32 {
+        if self.daily_word_goal == 0 {
+            return 1.0;
+        }
+        (self.words_today as f32 / self.daily_word_goal as f32).min(1.0)
+    }
+
+    /// Format: "day|words_today|daily_word_goal".
+    fn restore(&mut self) {
+        let Some(saved) = storage::load(STORAGE_KEY) else { return };
+        let mut parts = saved.split('|');
+        let (Some(day), Some(count), Some(goal)) = (parts.next(), parts.next(), parts.next()) else { return };
+
+        if let Ok(goal) = goal.parse() {
+            self.daily_word_goal = goal;
+        }
+        // Yesterday's count doesn't carry over
+        if day == self.day {
+            self.words_today = count.parse().unwrap_or(0);
+            self.reached = self.words_today >= self.daily_word_goal;
+        }
+    }
+
+    fn save(&self) {
+        storage::save(STORAGE_KEY, &format!("{}|{}|{}", self.day, self.words_today, self.daily_word_goal));
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+}
+
+pub fn reading_goal_system(
+    time: Res<Time>,
+    rsvp: Res<RsvpState>,
+    mut goal: ResMut<ReadingGoal>,
+    mut overlay: ResMut<OverlayMessage>,
+) {
+    // Local midnight passed: start a new day
+    let today = storage::today();
+    if today != goal.day {
+        goal.day = today;
+        goal.words_today = 0;
+        goal.reached = false;
+        goal.dirty = true;
+    }
+
+    let new_words = rsvp.words_advanced.saturating_sub(goal.last_advanced);
+    goal.last_advanced = rsvp.words_advanced;
+    if new_words > 0 {
+        goal.words_today += new_words as usize;
+        goal.dirty = true;
+    }
+
+    if !goal.reached && goal.daily_word_goal > 0 && goal.words_today >= goal.daily_word_goal {
+        goal.reached = true;
+        let lang = rsvp.lang;
+        overlay.show(format!("{}: {} {}!", tr(lang, "Daily goal reached"), goal.daily_word_goal, tr(lang, "words")), 4.0);
+        goal.save();
+    }
+
+    goal.save_timer += time.delta_secs();
+    if goal.dirty && goal.save_timer >= SAVE_INTERVAL_SECS {
+        goal.save();
+        goal.dirty = false;
+        goal.save_timer = 0.0;
+    }
+}
+
+/// Small progress ring for the goal, drawn with the egui painter.
+pub fn goal_ring(ui: &mut egui::Ui, fraction: f32) {
+    let size = 36.0;
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+    let painter = ui.painter();
+    let center = rect.center();
+    let radius = size / 2.0 - 3.0;
+
+    painter.circle_stroke(center, radius, egui::Stroke::new(4.0, egui::Color32::from_gray(60)));
+
+    let steps = 48;
+    let end = (steps as f32 * fraction.clamp(0.0, 1.0)) as usize;
+    if end > 0 {
+        let points: Vec<egui::Pos2> = (0..=end)
+            .map(|i| {
+                // Start at 12 o'clock and go clockwise
+                let angle = -std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * i as f32 / steps as f32;
+                center + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(4.0, egui::Color32::from_rgb(76, 175, 80))));
+    }
+}
@@ -0,0 +1,158 @@
+// Project Gutenberg loader: fetches a book's plain-text edition by its ID and hands it to
+// the regular upload pipeline. Gutenberg serves the same book under a few file names, so
+// each known pattern is tried in turn.
+
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+
+use crate::i18n::tr;
+use crate::parse_error::ParseError;
+use crate::toast::Toasts;
+use crate::{notify_error, RsvpState};
+
+// Written by the fetch task when it's done: Ok once the text has been queued, or the error
+static FETCH_RESULT: Mutex<Option<Result<(), ParseError>>> = Mutex::new(None);
+
+#[derive(Resource, Default)]
+pub struct GutenbergFetch {
+    pub id: String,
+    pub loading: bool,
+}
+
+#[cfg(target_arch = "wasm32")]
+fn candidate_urls(id: u32) -> [String; 3] {
+    [
+        format!("https://www.gutenberg.org/cache/epub/{id}/pg{id}.txt"),
+        format!("https://www.gutenberg.org/files/{id}/{id}-0.txt"),
+        format!("https://www.gutenberg.org/files/{id}/{id}.txt"),
+    ]
+}
+
+impl GutenbergFetch {
+    /// Starts fetching the book in `id`; the result arrives through `gutenberg_fetch_system`.
+    pub fn start(&mut self) -> Result<(), &'static str> {
+        let id: u32 = self.id.trim().parse().map_err(|_| "Enter a numeric book ID")?;
+        self.loading = true;
+        spawn_fetch(id);
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn_fetch(id: u32) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut last_error = String::from("No URL succeeded");
+        for url in candidate_urls(id) {
+            match fetch_text(&url).await {
+                Ok(text) => {
+                    crate::queue_upload(&format!("gutenberg-{}.txt", id), text.as_bytes());
+                    *FETCH_RESULT.lock().unwrap() = Some(Ok(()));
+                    return;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        *FETCH_RESULT.lock().unwrap() = Some(Err(ParseError::NetworkError(last_error)));
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_text(url: &str) -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("No window")?;
+    // fetch follows Gutenberg's redirects on its own
+    let response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| format!("Network error: {:?}", e))?;
+    let response: web_sys::Response = response.dyn_into().map_err(|_| "Unexpected fetch result")?;
+    if !response.ok() {
+        return Err(format!("{} returned HTTP {}", url, response.status()));
+    }
+    let text = response.text().map_err(|e| format!("{:?}", e))?;
+    JsFuture::from(text)
+        .await
+        .map_err(|e| format!("Could not read response: {:?}", e))?
+        .as_string()
+        .ok_or_else(|| "Response was not text".to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_fetch(_id: u32) {
+    *FETCH_RESULT.lock().unwrap() = Some(Err(ParseError::NetworkError("Fetching books needs the web build".to_string())));
+}
+
+/// The book text between Gutenberg's `*** START OF ... ***` and the `*** END OF ... ***`
+/// marker line after it (either in any case), or the whole text when either is missing.
+pub fn trim_boilerplate(text: &str) -> &str {
+    // The first marker line at or after `from`, as its start and the start of the next line
+    let marker_line = |prefix: &str, from: usize| -> Option<(usize, usize)> {
+        let start = text[from..]
+            .match_indices("*** ")
+            .map(|(i, _)| from + i)
+            .find(|&i| text.get(i + 4..i + 4 + prefix.len()).is_some_and(|s| s.eq_ignore_ascii_case(prefix)))?;
+        let end = text[start..].find('\n').map_or(text.len(), |n| start + n + 1);
+        Some((start, end))
+    };
+
+    let Some((_, body_start)) = marker_line("START OF", 0) else { return text };
+    let Some((body_end, _)) = marker_line("END OF", body_start) else { return text };
+    text[body_start..body_end].trim()
+}
+
+pub fn gutenberg_fetch_system(
+    rsvp: Res<RsvpState>,
+    mut fetch: ResMut<GutenbergFetch>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(result) = FETCH_RESULT.lock().unwrap().take() else { return };
+    fetch.loading = false;
+    if let Err(e) = result {
+        toasts.error(format!("{}: {}", tr(rsvp.lang, "Could not load book"), e));
+        notify_error(&e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::trim_boilerplate;
+
+    const BOOK: &str = "The Project Gutenberg eBook of Moby Dick\n\
+        This eBook is for the use of anyone anywhere.\n\
+        *** START OF THE PROJECT GUTENBERG EBOOK MOBY DICK ***\n\
+        Call me Ishmael.\n\
+        *** END OF THE PROJECT GUTENBERG EBOOK MOBY DICK ***\n\
+        Updated editions will replace the previous one.\n";
+
+    #[test]
+    fn keeps_only_the_book_between_the_markers() {
+        assert_eq!(trim_boilerplate(BOOK), "Call me Ishmael.");
+    }
+
+    #[test]
+    fn markers_match_in_any_case() {
+        let text = "License\n*** start of this project gutenberg ebook ***\nBody text.\n*** end of this project gutenberg ebook ***\nLicense";
+        assert_eq!(trim_boilerplate(text), "Body text.");
+    }
+
+    #[test]
+    fn a_missing_marker_keeps_everything() {
+        let no_end = "Header\n*** START OF THE PROJECT GUTENBERG EBOOK X ***\nBody";
+        assert_eq!(trim_boilerplate(no_end), no_end);
+        let no_start = "Body\n*** END OF THE PROJECT GUTENBERG EBOOK X ***\nFooter";
+        assert_eq!(trim_boilerplate(no_start), no_start);
+        assert_eq!(trim_boilerplate("Plain text with *** stars ***"), "Plain text with *** stars ***");
+    }
+
+    #[test]
+    fn an_end_marker_before_the_start_is_skipped() {
+        let only_before = "*** END OF THE PROJECT GUTENBERG EBOOK X ***\n*** START OF THE PROJECT GUTENBERG EBOOK X ***\nBody";
+        assert_eq!(trim_boilerplate(only_before), only_before);
+        let text = "*** END OF THE PROJECT GUTENBERG EBOOK X ***\n\
+            *** START OF THE PROJECT GUTENBERG EBOOK X ***\nBody\n\
+            *** END OF THE PROJECT GUTENBERG EBOOK X ***\nFooter";
+        assert_eq!(trim_boilerplate(text), "Body");
+    }
+}
@@ -0,0 +1,75 @@
+// HTML documents (e.g. a saved web article): only visible text is read, block elements
+// end paragraphs, and headings/emphasis carry over as word styles. Each <h1> starts a page.
+
+use scraper::{ElementRef, Html};
+
+use crate::styled_text::StyledTextBuilder;
+use crate::WordStyle;
+
+const HIDDEN_ELEMENTS: &[&str] = &["head", "script", "style", "noscript", "template", "svg"];
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "section", "article", "aside", "header", "footer", "main", "nav",
+    "blockquote", "pre", "li", "dt", "dd", "tr", "caption", "figcaption",
+    "h1", "h2", "h3", "h4", "h5", "h6",
+];
+
+/// Whether `bytes` look like HTML rather than some other text format.
+pub fn looks_like_html(bytes: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]);
+    text.trim_start_matches('\u{feff}').trim_start().starts_with('<')
+}
+
+/// Splits HTML into pages of visible words plus a parallel style for each word.
+pub fn parse_html(text: &str) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let mut b = StyledTextBuilder::default();
+    push_html(text, &mut b);
+    b.finish()
+}
+
+/// Adds the visible words of an HTML document to `b`, for formats built out of several
+/// (EPUB chapters).
+pub fn push_html(text: &str, b: &mut StyledTextBuilder) {
+    let document = Html::parse_document(text);
+    walk(document.root_element(), b);
+}
+
+fn walk(element: ElementRef, b: &mut StyledTextBuilder) {
+    let name = element.value().name();
+    if HIDDEN_ELEMENTS.contains(&name) {
+        return;
+    }
+
+    let is_heading = matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6");
+    let is_strong = matches!(name, "strong" | "b");
+    let is_emphasis = matches!(name, "em" | "i");
+
+    if name == "h1" {
+        b.end_page();
+    }
+    if BLOCK_ELEMENTS.contains(&name) {
+        b.flush_word();
+    }
+    b.heading |= is_heading;
+    b.strong_depth += usize::from(is_strong);
+    b.emphasis_depth += usize::from(is_emphasis);
+
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            b.push_text(text);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            walk(child, b);
+        }
+    }
+
+    if is_heading {
+        b.heading = false;
+    }
+    b.strong_depth -= usize::from(is_strong);
+    b.emphasis_depth -= usize::from(is_emphasis);
+    if BLOCK_ELEMENTS.contains(&name) {
+        b.end_block();
+    } else if matches!(name, "br" | "td" | "th") {
+        b.flush_word();
+    }
+}
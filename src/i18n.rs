@@ -0,0 +1,331 @@
+// UI string translations. English text doubles as the lookup key (gettext style), so an
+// untranslated string simply falls back to English.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Es];
+
+    /// Name of the language in itself, for the selector.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Es => "Español",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Lang> {
+        let primary = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Lang::En),
+            "es" => Some(Lang::Es),
+            _ => None,
+        }
+    }
+
+    /// Browser UI language (`LANG` natively), falling back to English.
+    pub fn detect() -> Lang {
+        #[cfg(target_arch = "wasm32")]
+        let tag = web_sys::window().and_then(|w| w.navigator().language());
+        #[cfg(not(target_arch = "wasm32"))]
+        let tag = std::env::var("LANG").ok();
+
+        tag.and_then(|t| Lang::from_tag(&t)).unwrap_or_default()
+    }
+}
+
+// (English, Spanish)
+const STRINGS: &[(&str, &str)] = &[
+    ("Panels", "Paneles"),
+    ("Hide controls (F)", "Ocultar controles (F)"),
+    ("Show controls (F)", "Mostrar controles (F)"),
+    ("Press F to show controls", "Pulsa F para mostrar los controles"),
+    ("Dock in side panel", "Acoplar en panel lateral"),
+    ("Minimap", "Minimapa"),
+    ("Status bar", "Barra de estado"),
+    ("min left", "min restantes"),
+    ("done", "terminado"),
+    ("Playback", "Reproducción"),
+    ("Navigation", "Navegación"),
+    ("Pacing", "Ritmo"),
+    ("Typography", "Tipografía"),
+    ("Language", "Idioma"),
+    ("Read again", "Leer de nuevo"),
+    ("Play", "Reproducir"),
+    ("Pause", "Pausa"),
+    ("Restart", "Reiniciar"),
+    ("End", "Final"),
+    ("Speed", "Velocidad"),
+    ("Logarithmic speed slider", "Control de velocidad logarítmico"),
+    ("Ease into speed changes", "Cambiar de velocidad gradualmente"),
+    ("Calibrate speed…", "Calibrar velocidad…"),
+    ("Replaces the open document with a short test passage", "Sustituye el documento abierto por un breve texto de prueba"),
+    ("Speed calibration", "Calibración de velocidad"),
+    ("Reading at", "Leyendo a"),
+    ("Did you keep up?", "¿Pudiste seguir el ritmo?"),
+    ("Yes", "Sí"),
+    ("No", "No"),
+    ("Replay", "Repetir"),
+    ("Recommended speed", "Velocidad recomendada"),
+    ("Use as my default", "Usar como predeterminada"),
+    ("Start over", "Empezar de nuevo"),
+    ("Count down before playing", "Cuenta atrás antes de reproducir"),
+    ("Play backwards", "Reproducir hacia atrás"),
+    ("Steps back a word at a time, for proofreading", "Retrocede palabra a palabra, para corregir textos"),
+    ("Words to repeat on resume", "Palabras a repetir al reanudar"),
+    ("Define words while paused", "Definir palabras en pausa"),
+    ("Auto-pause when idle (min)", "Pausa automática por inactividad (min)"),
+    ("0 turns it off", "0 la desactiva"),
+    ("Paused — resume?", "En pausa — ¿continuar?"),
+    ("Resume", "Continuar"),
+    ("Define", "Definir"),
+    ("Try again", "Reintentar"),
+    ("Training plan", "Plan de entrenamiento"),
+    ("Reading statistics", "Estadísticas de lectura"),
+    ("Words read", "Palabras leídas"),
+    ("Reading time", "Tiempo de lectura"),
+    ("Average speed", "Velocidad media"),
+    ("Reset stats", "Restablecer estadísticas"),
+    ("Raises your target speed a little after each day's session.", "Sube un poco tu velocidad objetivo tras la sesión de cada día."),
+    ("Start training plan", "Empezar plan de entrenamiento"),
+    ("Read at target", "Leer al objetivo"),
+    ("End plan", "Terminar plan"),
+    ("Today's target", "Objetivo de hoy"),
+    ("Today's session is done.", "La sesión de hoy está hecha."),
+    ("Level", "Nivel"),
+    ("sessions", "sesiones"),
+    ("Sessions completed on practice days", "Sesiones completadas en días de práctica"),
+    ("Started at", "Empezó a"),
+    ("Session complete! Next target", "¡Sesión completada! Próximo objetivo"),
+    ("Suggest speed on open", "Sugerir velocidad al abrir"),
+    ("Suggested speed for this document", "Velocidad sugerida para este documento"),
+    ("Use it", "Usarla"),
+    ("Comprehension checks", "Comprobaciones de comprensión"),
+    ("Paragraphs between checks", "Párrafos entre comprobaciones"),
+    ("Quick check", "Comprobación rápida"),
+    ("Could you summarize the last section in a sentence?", "¿Podrías resumir la última sección en una frase?"),
+    ("What was the main point of what you just read?", "¿Cuál era la idea principal de lo que acabas de leer?"),
+    ("Which detail from the last section stood out?", "¿Qué detalle de la última sección te llamó la atención?"),
+    ("Continue", "Continuar"),
+    ("Rewind", "Retroceder"),
+    ("Adapt speed to my pauses", "Adaptar la velocidad a mis pausas"),
+    ("Adaptive speed", "Velocidad adaptativa"),
+    ("adaptive", "adaptativa"),
+    ("Min", "Mín"),
+    ("Max", "Máx"),
+    ("Manual advance (Space / →)", "Avance manual (Espacio / →)"),
+    ("Today", "Hoy"),
+    ("words", "palabras"),
+    ("Goal", "Meta"),
+    ("Daily goal reached", "Meta diaria alcanzada"),
+    ("Using saved settings for this document", "Usando los ajustes guardados para este documento"),
+    ("Loaded", "Cargado"),
+    ("pages", "páginas"),
+    ("Parsing…", "Analizando…"),
+    ("Could not open file", "No se pudo abrir el archivo"),
+    ("That document is already open", "Ese documento ya está abierto"),
+    ("Parts of this file aren't UTF-8 and show as �; save it as UTF-8 for clean text", "Partes de este archivo no están en UTF-8 y se ven como �; guárdalo como UTF-8 para un texto limpio"),
+    ("Continuing from page", "Continuando desde la página"),
+    ("Document contained no text", "El documento no contiene texto"),
+    ("Use global settings for all documents", "Usar la configuración global en todos los documentos"),
+    ("Page", "Página"),
+    ("PDF page", "Página del PDF"),
+    ("Show position in corner", "Mostrar posición en la esquina"),
+    ("Undo speed (Z)", "Deshacer velocidad (Z)"),
+    ("Set speed", "Fijar velocidad"),
+    ("Ramp up speed while reading", "Aumentar la velocidad al leer"),
+    ("Start", "Inicio"),
+    ("Target", "Objetivo"),
+    ("WPM per minute", "PPM por minuto"),
+    ("No earlier speed", "No hay velocidad anterior"),
+    ("Controller connected", "Mando conectado"),
+    ("Controller disconnected", "Mando desconectado"),
+    ("No earlier position", "No hay posición anterior"),
+    ("Word", "Palabra"),
+    ("Page Progress", "Progreso de página"),
+    ("Document", "Documento"),
+    ("left", "restante"),
+    ("pass", "pasada"),
+    ("Bookmarks", "Marcadores"),
+    ("Contents", "Contenido"),
+    ("Add", "Añadir"),
+    ("Delete", "Eliminar"),
+    ("Export", "Exportar"),
+    ("Import", "Importar"),
+    ("Not a bookmark file", "No es un archivo de marcadores"),
+    ("These bookmarks belong to a different document", "Estos marcadores pertenecen a otro documento"),
+    ("Open a document before importing bookmarks", "Abre un documento antes de importar marcadores"),
+    ("Bookmarks imported", "Marcadores importados"),
+    ("Bookmark added", "Marcador añadido"),
+    ("Bookmarks exported", "Marcadores exportados"),
+    ("By sentence", "Por frase"),
+    ("Export subtitles", "Exportar subtítulos"),
+    ("Times the document at the current speed and settings", "Calcula los tiempos del documento con la velocidad y los ajustes actuales"),
+    ("Subtitles exported", "Subtítulos exportados"),
+    ("Gutenberg ID", "ID de Gutenberg"),
+    ("Load", "Cargar"),
+    ("Enter a numeric book ID", "Introduce un ID numérico de libro"),
+    ("Could not load book", "No se pudo cargar el libro"),
+    ("Trim Gutenberg license text", "Recortar la licencia de Gutenberg"),
+    ("Paste or type text to read", "Pega o escribe un texto para leer"),
+    ("Load text", "Cargar texto"),
+    ("All pages on upload", "Todas las páginas al subir"),
+    ("Split pages into words as needed", "Dividir páginas en palabras según se necesiten"),
+    ("Extract PDF pages as needed", "Extraer páginas del PDF según se necesiten"),
+    ("Skip PDF page numbers and headers", "Omitir números de página y encabezados del PDF"),
+    ("Applies to PDFs opened afterwards; turn off if numbers on their own line matter", "Se aplica a los PDF que se abran después; desactívalo si importan los números en su propia línea"),
+    ("Document language", "Idioma del documento"),
+    ("Characters per chunk", "Caracteres por fragmento"),
+    ("Applies to documents opened afterwards", "Se aplica a los documentos que se abran después"),
+    ("Sets text direction and font; CJK character chunking applies to documents opened afterwards", "Define la dirección del texto y la fuente; la división en caracteres CJK se aplica a los documentos que se abran después"),
+    ("Auto", "Automático"),
+    ("unknown", "desconocido"),
+    ("Switched to a font for", "Fuente cambiada para"),
+    ("No bundled font covers", "Ninguna fuente incluida cubre"),
+    ("English", "Inglés"),
+    ("Spanish", "Español"),
+    ("French", "Francés"),
+    ("German", "Alemán"),
+    ("Italian", "Italiano"),
+    ("Portuguese", "Portugués"),
+    ("Dutch", "Neerlandés"),
+    ("Russian", "Ruso"),
+    ("Greek", "Griego"),
+    ("Arabic", "Árabe"),
+    ("Hebrew", "Hebreo"),
+    ("Hindi", "Hindi"),
+    ("Chinese", "Chino"),
+    ("Japanese", "Japonés"),
+    ("Korean", "Coreano"),
+    ("Advanced", "Avanzado"),
+    ("Words per page (text)", "Palabras por página (texto)"),
+    ("Words on page", "Palabras en la página"),
+    ("Total words", "Palabras totales"),
+    ("Reading time at", "Tiempo de lectura a"),
+    ("Words Per Frame", "Palabras por cuadro"),
+    ("Sentences", "Oraciones"),
+    ("Line mode (verse)", "Modo por líneas (verso)"),
+    ("Keep sentences apart", "No mezclar oraciones"),
+    ("Join tiny words to the next", "Unir palabras diminutas a la siguiente"),
+    ("Timing Model", "Modelo de tiempo"),
+    ("Flat", "Uniforme"),
+    ("Word length", "Longitud de palabra"),
+    ("Syllables", "Sílabas"),
+    ("Pauses", "Pausas"),
+    ("Headings", "Títulos"),
+    ("Speed through short words", "Pasar rápido por palabras cortas"),
+    ("Pace by word frequency", "Ritmo según la frecuencia de las palabras"),
+    ("Common words go by faster and rare ones slower (English only)", "Las palabras comunes pasan más rápido y las raras más despacio (solo inglés)"),
+    ("Slow down on names & acronyms", "Más lento en nombres y siglas"),
+    ("Slow down on numbers", "Más lento en números"),
+    ("Clean up math & formulas", "Limpiar matemáticas y fórmulas"),
+    ("Rejoins split formulas like H₂O and x² and pauses on math symbols. Applies to documents opened afterwards",
+     "Vuelve a unir fórmulas separadas como H₂O y x² y se detiene en los símbolos matemáticos. Se aplica a los documentos que se abran después"),
+    ("Extra pause on long words (ms)", "Pausa extra en palabras largas (ms)"),
+    ("Letters", "Letras"),
+    ("Min Word Time (ms)", "Tiempo mínimo por palabra (ms)"),
+    ("Minimum word time caps the speed at", "El tiempo mínimo por palabra limita la velocidad a"),
+    ("Max Word Time (ms)", "Tiempo máximo por palabra (ms)"),
+    ("Blank gap between sentences", "Pausa en blanco entre oraciones"),
+    ("At the end of a page", "Al final de una página"),
+    ("Go on to the next page", "Pasar a la página siguiente"),
+    ("Pause at each page", "Pausar en cada página"),
+    ("Don't repeat", "No repetir"),
+    ("Repeat page", "Repetir página"),
+    ("Repeat document", "Repetir documento"),
+    ("Repeat from A to B", "Repetir de A a B"),
+    ("Set A", "Fijar A"),
+    ("Set B", "Fijar B"),
+    ("Clear", "Borrar"),
+    ("Count repeated words in goals and stats", "Contar las palabras repetidas en metas y estadísticas"),
+    ("Continuous", "Continuo"),
+    ("Pause between pages (ms)", "Pausa entre páginas (ms)"),
+    ("Show page number", "Mostrar número de página"),
+    ("Show bold and italic from the source", "Mostrar negrita y cursiva del original"),
+    ("Highlight pivot letter", "Resaltar letra pivote"),
+    ("Only pivot the middle word", "Solo la palabra central"),
+    ("Keep the pivot letter in one place", "Mantener la letra pivote en su sitio"),
+    ("Pivot position", "Posición del pivote"),
+    ("How far into each word the pivot letter sits", "Qué tan adentro de cada palabra queda la letra pivote"),
+    ("Tint difficult words", "Colorear palabras difíciles"),
+    ("Intensity", "Intensidad"),
+    ("Show surrounding words", "Mostrar palabras alrededor"),
+    ("Preview upcoming words", "Vista previa de las próximas palabras"),
+    ("Words", "Palabras"),
+    ("Before", "Antes"),
+    ("After", "Después"),
+    ("Line spacing", "Interlineado"),
+    ("Words across page breaks", "Palabras entre páginas"),
+    ("Read", "Leídas"),
+    ("Upcoming", "Próximas"),
+    ("Column width", "Ancho de columna"),
+    ("Fade between words", "Fundido entre palabras"),
+    ("Smooth word transitions", "Transiciones suaves entre palabras"),
+    ("Each word fades up from dim, without a blink; off at high speeds", "Cada palabra aparece desde atenuada, sin parpadeo; se desactiva a altas velocidades"),
+    ("Focus mode (dim everything but the word)", "Modo enfoque (oscurecer todo salvo la palabra)"),
+    ("Dimming", "Oscurecimiento"),
+    ("Count words since the last pause", "Contar palabras desde la última pausa"),
+    ("words without a break", "palabras sin pausa"),
+    ("Reduce motion", "Reducir movimiento"),
+    ("Text Size", "Tamaño del texto"),
+    ("Alignment", "Alineación"),
+    ("Center", "Centro"),
+    ("Left", "Izquierda"),
+    ("Wrap column", "Columna de ajuste"),
+    ("Width that multi-word chunks wrap within", "Ancho dentro del que se ajustan los grupos de palabras"),
+    ("Vertical Position", "Posición vertical"),
+    ("Text", "Texto"),
+    ("Background", "Fondo"),
+    ("Dark", "Oscuro"),
+    ("Light", "Claro"),
+    ("Reading ruler", "Regla de lectura"),
+    ("Focus guides above and below", "Guías de enfoque arriba y abajo"),
+    ("Width", "Ancho"),
+    ("Font Family", "Fuente"),
+    ("Could not load font", "No se pudo cargar la fuente"),
+    ("Could not read font", "No se pudo leer la fuente"),
+    ("Uploaded font", "Fuente subida"),
+    ("uploaded", "subida"),
+    ("Font added", "Fuente añadida"),
+    ("using", "usando"),
+    ("Weight", "Grosor"),
+    ("Finished", "Terminado"),
+    ("Announce progress to screen readers", "Anunciar el progreso a los lectores de pantalla"),
+    ("of", "de"),
+    ("percent", "por ciento"),
+    ("Paused", "En pausa"),
+    ("Getting started", "Primeros pasos"),
+    ("Welcome! Words are shown one at a time in the center of the screen.",
+     "¡Bienvenido! Las palabras aparecen de una en una en el centro de la pantalla."),
+    ("Upload a PDF with the button above the reader, or try the sample text.",
+     "Sube un PDF con el botón sobre el lector o prueba el texto de ejemplo."),
+    ("Use the Speed slider, or keys 1–5, to choose how many words per minute you read.",
+     "Usa el control de velocidad, o las teclas 1–5, para elegir cuántas palabras lees por minuto."),
+    ("The red letter marks the focus point. Keep your eyes on it and let the words come to you.",
+     "La letra roja marca el punto de enfoque. Mantén la vista en ella y deja que las palabras lleguen."),
+    ("Load sample", "Cargar ejemplo"),
+    ("Back", "Atrás"),
+    ("Search", "Buscar"),
+    ("Previous match", "Coincidencia anterior"),
+    ("Next match", "Siguiente coincidencia"),
+    ("No matches", "Sin coincidencias"),
+    ("Next", "Siguiente"),
+    ("Skip", "Omitir"),
+    ("Done", "Listo"),
+];
+
+/// Translation of `english` into `lang`, or `english` itself if there is none.
+pub fn tr(lang: Lang, english: &'static str) -> &'static str {
+    match lang {
+        Lang::En => english,
+        Lang::Es => STRINGS
+            .iter()
+            .find(|(en, _)| *en == english)
+            .map_or(english, |(_, es)| es),
+    }
+}
@@ -0,0 +1,117 @@
+// Auto-pause for a reader who has walked away. Any key, click, pointer movement, scroll,
+// touch or controller button counts as being there; once playback has run for
+// `idle_pause_minutes` without any of them it pauses, and the spot it stopped at is saved
+// for the document. The next input brings up a prompt to resume from there, and reopening
+// the document later (even after a reload) offers the same.
+
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+
+use crate::doc_prefs::DocumentPrefs;
+use crate::i18n::tr;
+use crate::toast::Toasts;
+use crate::{current_position, jump_to, storage, ReaderDisplay, RsvpState, WordFade};
+
+const POSITION_KEY_PREFIX: &str = "rsvp_idle_position_";
+
+#[derive(Resource, Default)]
+pub struct IdlePause {
+    // In `Time::elapsed_secs_f64` terms
+    last_input: f64,
+    // (page, word) an auto-pause stopped at, until the reader resumes or declines
+    paused_at: Option<(usize, usize)>,
+    prompt: Option<u64>,
+    // Document whose saved position has been looked up, so each open is checked once
+    checked_key: Option<String>,
+}
+
+fn position_key(key: &str) -> String {
+    format!("{}{}", POSITION_KEY_PREFIX, key)
+}
+
+fn load_position(key: &str) -> Option<(usize, usize)> {
+    // Format: "page|word"
+    let saved = storage::load(&position_key(key))?;
+    let (page, word) = saved.split_once('|')?;
+    Some((page.parse().ok()?, word.parse().ok()?))
+}
+
+pub fn idle_pause_system(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut cursor: EventReader<CursorMoved>,
+    mut wheel: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    prefs: Res<DocumentPrefs>,
+    mut rsvp: ResMut<RsvpState>,
+    mut display: ResMut<ReaderDisplay>,
+    mut fade: ResMut<WordFade>,
+    mut toasts: ResMut<Toasts>,
+    mut idle: ResMut<IdlePause>,
+) {
+    let now = time.elapsed_secs_f64();
+    // Drain both readers rather than stopping at the first, so old events don't pile up
+    let moved = cursor.read().count() > 0;
+    let scrolled = wheel.read().count() > 0;
+    let input = moved
+        || scrolled
+        || keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || touches.iter_just_pressed().next().is_some()
+        || gamepads.iter().any(|g| g.digital().get_just_pressed().next().is_some());
+    if input {
+        idle.last_input = now;
+    }
+    let lang = rsvp.lang;
+    let key = prefs.key().map(str::to_string);
+
+    if idle.checked_key != key {
+        idle.checked_key.clone_from(&key);
+        idle.paused_at = key.as_deref().and_then(load_position);
+        idle.prompt = None;
+        if idle.paused_at.is_some() && !rsvp.pages.is_empty() {
+            idle.prompt = Some(toasts.prompt(tr(lang, "Paused — resume?"), tr(lang, "Resume")));
+        }
+    }
+
+    if let Some((page, word)) = idle.paused_at {
+        let resumed = match idle.prompt {
+            Some(id) if toasts.take_accepted(id) => {
+                jump_to(&mut rsvp, &mut display, &mut fade, page, word);
+                rsvp.is_playing = true;
+                true
+            }
+            Some(id) => !toasts.is_pending(id),
+            // Playback restarted without the prompt, from wherever the reader is now
+            None if rsvp.is_playing => true,
+            None => {
+                if input {
+                    idle.prompt = Some(toasts.prompt(tr(lang, "Paused — resume?"), tr(lang, "Resume")));
+                }
+                false
+            }
+        };
+        if resumed {
+            idle.paused_at = None;
+            idle.prompt = None;
+            if let Some(key) = &key {
+                storage::save(&position_key(key), "");
+            }
+        }
+        return;
+    }
+
+    let timeout_secs = rsvp.idle_pause_minutes as f64 * 60.0;
+    if rsvp.idle_pause_minutes == 0 || !rsvp.is_playing || now - idle.last_input < timeout_secs {
+        return;
+    }
+    rsvp.is_playing = false;
+    let (page, word) = current_position(&rsvp);
+    idle.paused_at = Some((page, word));
+    if let Some(key) = &key {
+        storage::save(&position_key(key), &format!("{}|{}", page, word));
+    }
+    info!("No input for {} min, paused at page {} word {}", rsvp.idle_pause_minutes, page + 1, word);
+}
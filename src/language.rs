@@ -0,0 +1,306 @@
+// Document language. Detected on upload from a sample of the extracted words: the dominant
+// script first, then, for Latin-script text, the language whose common words turn up
+// most often. The result picks the defaults that depend on it: the base text direction,
+// whether CJK runs are cut into short character chunks, and a bundled font that has the
+// script's glyphs. A manual choice overrides detection for every document until reset.
+
+use crate::{storage, WordStyle};
+
+const OVERRIDE_KEY: &str = "rsvp_document_language";
+
+// Words looked at when detecting; the start of a document is enough to tell
+const SAMPLE_WORDS: usize = 1000;
+// Fewer script letters than this and the sample says nothing reliable
+const MIN_LETTERS: usize = 20;
+// Common-word hits needed before a Latin-script language is named
+const MIN_COMMON_WORDS: usize = 3;
+// Characters per chunk in CJK text, which has no spaces to split on, unless the reader picks
+// another size
+pub const DEFAULT_CJK_CHUNK_CHARS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Han,
+    Kana,
+    Hangul,
+    Devanagari,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Script> {
+        let script = match c as u32 {
+            0x0041..=0x024F if c.is_alphabetic() => Script::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x052F => Script::Cyrillic,
+            0x0590..=0x05FF => Script::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+            0x0900..=0x097F => Script::Devanagari,
+            0x3040..=0x30FF | 0x31F0..=0x31FF => Script::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF | 0x3130..=0x318F => Script::Hangul,
+            _ => return None,
+        };
+        Some(script)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocLanguage {
+    English,
+    Spanish,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Dutch,
+    Russian,
+    Greek,
+    Arabic,
+    Hebrew,
+    Hindi,
+    Chinese,
+    Japanese,
+    Korean,
+}
+
+impl DocLanguage {
+    pub const ALL: [DocLanguage; 15] = [
+        DocLanguage::English,
+        DocLanguage::Spanish,
+        DocLanguage::French,
+        DocLanguage::German,
+        DocLanguage::Italian,
+        DocLanguage::Portuguese,
+        DocLanguage::Dutch,
+        DocLanguage::Russian,
+        DocLanguage::Greek,
+        DocLanguage::Arabic,
+        DocLanguage::Hebrew,
+        DocLanguage::Hindi,
+        DocLanguage::Chinese,
+        DocLanguage::Japanese,
+        DocLanguage::Korean,
+    ];
+
+    /// English name, also the storage value and the translation key.
+    pub fn name(self) -> &'static str {
+        match self {
+            DocLanguage::English => "English",
+            DocLanguage::Spanish => "Spanish",
+            DocLanguage::French => "French",
+            DocLanguage::German => "German",
+            DocLanguage::Italian => "Italian",
+            DocLanguage::Portuguese => "Portuguese",
+            DocLanguage::Dutch => "Dutch",
+            DocLanguage::Russian => "Russian",
+            DocLanguage::Greek => "Greek",
+            DocLanguage::Arabic => "Arabic",
+            DocLanguage::Hebrew => "Hebrew",
+            DocLanguage::Hindi => "Hindi",
+            DocLanguage::Chinese => "Chinese",
+            DocLanguage::Japanese => "Japanese",
+            DocLanguage::Korean => "Korean",
+        }
+    }
+
+    fn script(self) -> Script {
+        match self {
+            DocLanguage::English
+            | DocLanguage::Spanish
+            | DocLanguage::French
+            | DocLanguage::German
+            | DocLanguage::Italian
+            | DocLanguage::Portuguese
+            | DocLanguage::Dutch => Script::Latin,
+            DocLanguage::Russian => Script::Cyrillic,
+            DocLanguage::Greek => Script::Greek,
+            DocLanguage::Arabic => Script::Arabic,
+            DocLanguage::Hebrew => Script::Hebrew,
+            DocLanguage::Hindi => Script::Devanagari,
+            DocLanguage::Chinese => Script::Han,
+            DocLanguage::Japanese => Script::Kana,
+            DocLanguage::Korean => Script::Hangul,
+        }
+    }
+
+    // Frequent short words, for telling languages of the same script apart
+    fn common_words(self) -> &'static [&'static str] {
+        match self {
+            DocLanguage::English => &["the", "and", "of", "to", "is", "that", "it", "was", "with", "for"],
+            DocLanguage::Spanish => &["el", "los", "las", "que", "y", "en", "por", "una", "del", "es"],
+            DocLanguage::French => &["le", "les", "et", "des", "est", "une", "dans", "pour", "pas", "qui"],
+            DocLanguage::German => &["der", "die", "und", "das", "ist", "nicht", "ein", "eine", "zu", "mit"],
+            DocLanguage::Italian => &["il", "di", "che", "e", "non", "per", "gli", "del", "sono", "della"],
+            DocLanguage::Portuguese => &["o", "os", "que", "e", "do", "da", "não", "uma", "em", "com"],
+            DocLanguage::Dutch => &["de", "het", "een", "en", "van", "niet", "dat", "op", "zijn", "met"],
+            _ => &[],
+        }
+    }
+
+    pub fn is_rtl(self) -> bool {
+        matches!(self.script(), Script::Arabic | Script::Hebrew)
+    }
+
+    /// Whether runs of text are cut into character chunks rather than split on spaces.
+    pub fn uses_char_chunks(self) -> bool {
+        matches!(self.script(), Script::Han | Script::Kana)
+    }
+
+    /// `current` if it has the script's glyphs, else the first bundled font that does.
+    /// `None` when no bundled font covers the script.
+    pub fn covering_font(self, current: &str) -> Option<&'static str> {
+        let fonts: &[&'static str] = match self.script() {
+            Script::Latin | Script::Cyrillic | Script::Greek => crate::AVAILABLE_FONTS,
+            Script::Hebrew => &["Arimo-Regular.ttf", "Tinos-Regular.ttf", "DejaVuSans.ttf"],
+            Script::Arabic => &["DejaVuSans.ttf"],
+            Script::Devanagari | Script::Han | Script::Kana | Script::Hangul => &[],
+        };
+        fonts.iter().find(|f| **f == current).or(fonts.first()).copied()
+    }
+
+    fn from_name(name: &str) -> Option<DocLanguage> {
+        DocLanguage::ALL.into_iter().find(|l| l.name() == name)
+    }
+}
+
+/// The saved manual choice, if the user made one.
+pub fn load_override() -> Option<DocLanguage> {
+    storage::load(OVERRIDE_KEY).and_then(|name| DocLanguage::from_name(&name))
+}
+
+pub fn save_override(language: Option<DocLanguage>) {
+    storage::save(OVERRIDE_KEY, language.map_or("", DocLanguage::name));
+}
+
+/// Best guess at the language of `pages` from their first words, or `None` when the sample
+/// is too small or matches nothing known.
+pub fn detect(pages: &[Vec<String>]) -> Option<DocLanguage> {
+    let sample: Vec<&String> = pages.iter().flatten().take(SAMPLE_WORDS).collect();
+
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for c in sample.iter().flat_map(|w| w.chars()) {
+        let Some(script) = Script::of(c) else { continue };
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let count = |script| counts.iter().find(|(s, _)| *s == script).map_or(0, |(_, n)| *n);
+    let (script, _) = counts.iter().copied().max_by_key(|(_, n)| *n)?;
+    if counts.iter().map(|(_, n)| n).sum::<usize>() < MIN_LETTERS {
+        return None;
+    }
+
+    let language = match script {
+        // Japanese mixes kanji with kana; any real share of kana means it isn't Chinese
+        Script::Han | Script::Kana if count(Script::Kana) * 10 >= count(Script::Han) => DocLanguage::Japanese,
+        Script::Han | Script::Kana => DocLanguage::Chinese,
+        Script::Hangul => DocLanguage::Korean,
+        Script::Greek => DocLanguage::Greek,
+        Script::Cyrillic => DocLanguage::Russian,
+        Script::Arabic => DocLanguage::Arabic,
+        Script::Hebrew => DocLanguage::Hebrew,
+        Script::Devanagari => DocLanguage::Hindi,
+        Script::Latin => {
+            let lowered: Vec<String> = sample
+                .iter()
+                .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+                .collect();
+            let (language, hits) = DocLanguage::ALL
+                .into_iter()
+                .filter(|l| l.script() == Script::Latin)
+                .map(|l| (l, lowered.iter().filter(|w| l.common_words().contains(&w.as_str())).count()))
+                .max_by_key(|(_, hits)| *hits)?;
+            if hits < MIN_COMMON_WORDS {
+                return None;
+            }
+            language
+        }
+    };
+    Some(language)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(Script::of(c), Some(Script::Han | Script::Kana))
+}
+
+// Punctuation that belongs with the chunk after it rather than the one before
+fn is_opening(c: char) -> bool {
+    matches!(c, '「' | '『' | '（' | '《' | '〈' | '【' | '〔' | '“' | '‘' | '(' | '[')
+}
+
+/// Cuts a token into chunks of up to `chunk_chars` CJK characters. Punctuation stays
+/// with its neighbour and runs of other letters or digits become chunks of their own.
+fn split_token(token: &str, chunk_chars: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+    let mut cjk_chars = 0;
+    let mut other_chars = 0;
+    for c in token.chars() {
+        let starts_new = if is_opening(c) {
+            cjk_chars > 0 || other_chars > 0
+        } else if is_cjk(c) {
+            cjk_chars >= chunk_chars.max(1) || other_chars > 0
+        } else if c.is_alphanumeric() {
+            cjk_chars > 0
+        } else {
+            false
+        };
+        if starts_new {
+            pieces.push(std::mem::take(&mut piece));
+            cjk_chars = 0;
+            other_chars = 0;
+        }
+        piece.push(c);
+        if is_cjk(c) {
+            cjk_chars += 1;
+        } else if c.is_alphanumeric() {
+            other_chars += 1;
+        }
+    }
+    if !piece.is_empty() {
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Splits the CJK tokens of one page into character chunks. `styles` may be shorter than
+/// `words`; each chunk inherits its token's style, with the paragraph and line ends kept
+/// on the last chunk only.
+fn split_cjk_page(words: Vec<String>, styles: Vec<WordStyle>, chunk_chars: usize) -> (Vec<String>, Vec<WordStyle>) {
+    if !words.iter().any(|w| w.chars().any(is_cjk)) {
+        return (words, styles);
+    }
+    let mut out_words = Vec::with_capacity(words.len());
+    let mut out_styles = Vec::with_capacity(styles.len());
+    for (i, word) in words.into_iter().enumerate() {
+        let pieces = if word.chars().any(is_cjk) { split_token(&word, chunk_chars) } else { vec![word] };
+        if let Some(style) = styles.get(i) {
+            let inner = WordStyle { paragraph_end: false, line_end: false, ..*style };
+            out_styles.extend(std::iter::repeat_n(inner, pieces.len() - 1));
+            out_styles.push(*style);
+        }
+        out_words.extend(pieces);
+    }
+    (out_words, out_styles)
+}
+
+/// `split_cjk_page` over a whole document; `styles` may have fewer pages than `pages`.
+pub fn split_cjk_pages(
+    pages: Vec<Vec<String>>,
+    styles: Vec<Vec<WordStyle>>,
+    chunk_chars: usize,
+) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let page_count = styles.len();
+    let mut styles = styles.into_iter();
+    let (pages, mut split_styles): (Vec<_>, Vec<_>) =
+        pages.into_iter().map(|words| split_cjk_page(words, styles.next().unwrap_or_default(), chunk_chars)).unzip();
+    // Don't pad `styles` out to the page count with empty entries
+    split_styles.truncate(page_count);
+    (pages, split_styles)
+}
@@ -0,0 +1,181 @@
+// Deferred PDF loading. Instead of tokenizing every page on upload, the document starts as
+// one empty page per PDF page and words are filled in around the read position as it moves.
+// Two levels of deferral:
+// - extract on visit: the parsed `Document` stays here and a page's text is pulled out of it
+//   when needed;
+// - tokenize on visit: every page's text is extracted up front, but only split into words
+//   when needed. Raw text costs about a fifth of its tokenized `Vec<String>` form (1000
+//   pages of 400 words: ~2.5 MB vs ~14 MB of allocations), and tokenizing a page takes tens
+//   of microseconds, so reading never waits on extraction.
+
+use bevy::prelude::*;
+use lopdf::Document;
+
+use crate::page_furniture::strip_page_numbers;
+use crate::styled_text::clean_token;
+use crate::RsvpState;
+
+// Pages past the current one prepared ahead of time, at most one per frame
+const LOOKAHEAD_PAGES: usize = 2;
+
+/// How much of a PDF is turned into words when it's uploaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfLoadMode {
+    /// Extract and tokenize every page on upload; blank pages are dropped.
+    Eager,
+    /// Extract all text on upload, tokenize pages as they're reached.
+    TokenizeOnVisit,
+    /// Extract and tokenize pages as they're reached.
+    ExtractOnVisit,
+}
+
+impl PdfLoadMode {
+    pub const ALL: [PdfLoadMode; 3] = [PdfLoadMode::Eager, PdfLoadMode::TokenizeOnVisit, PdfLoadMode::ExtractOnVisit];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PdfLoadMode::Eager => "All pages on upload",
+            PdfLoadMode::TokenizeOnVisit => "Split pages into words as needed",
+            PdfLoadMode::ExtractOnVisit => "Extract PDF pages as needed",
+        }
+    }
+}
+
+/// Where pages that haven't been tokenized yet come from.
+pub enum DeferredPages {
+    Pdf(Box<Document>, Vec<u32>),
+    Text(Vec<String>),
+}
+
+#[derive(Resource, Default)]
+pub struct LazyPdf {
+    source: Option<DeferredPages>,
+    extracted: Vec<bool>,
+}
+
+impl LazyPdf {
+    pub fn open(&mut self, source: DeferredPages) {
+        let pages = match &source {
+            DeferredPages::Pdf(_, page_numbers) => page_numbers.len(),
+            DeferredPages::Text(texts) => texts.len(),
+        };
+        self.extracted = vec![false; pages];
+        self.source = Some(source);
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    fn extract(&mut self, rsvp: &mut RsvpState, page: usize) {
+        let words = match &mut self.source {
+            Some(DeferredPages::Pdf(doc, page_numbers)) => extract_page_words(doc, page_numbers[page], rsvp.clean_extracted_text),
+            // Each page's text is only needed once
+            Some(DeferredPages::Text(texts)) => tokenize(&std::mem::take(&mut texts[page])),
+            None => return,
+        };
+        rsvp.replace_page(page, words);
+        self.extracted[page] = true;
+    }
+}
+
+// Left halves that almost always start a real compound, so "well-" + "known" keeps its
+// hyphen. Ones that also begin plain words ("re-member", "so-cial") stay out: those merge
+const COMPOUND_PREFIXES: &[&str] = &["all", "anti", "cross", "half", "ill", "multi", "non", "self", "semi", "well"];
+// After "pre-" these mean the hyphen is suspended ("pre- and post-war"), not a line break
+const CONJUNCTIONS: &[&str] = &["and", "or", "nor", "to", "und", "oder", "et", "ou", "y", "o", "e"];
+
+/// The words of one PDF page's text, with words hyphenated at a line break made whole again.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let words = text.split_whitespace().map(clean_token).filter(|w| !w.is_empty()).map(String::from);
+    rejoin_hyphenated(words, text)
+}
+
+/// Merges "inter-" "esting" into "interesting". Only a hyphen after a letter and before a
+/// lowercase word is a candidate; whether it stays ("well-known") is decided by how the
+/// page writes the word elsewhere, and failing that by `COMPOUND_PREFIXES`.
+fn rejoin_hyphenated(words: impl Iterator<Item = String>, text: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for word in words {
+        let broken = out.last().and_then(|prev| {
+            let head = prev.strip_suffix('-')?;
+            let starts_lowercase = word.chars().next().is_some_and(char::is_lowercase);
+            let bare = word.trim_end_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+            (head.chars().last().is_some_and(char::is_alphabetic) && starts_lowercase && !CONJUNCTIONS.contains(&bare.as_str()))
+                .then(|| head.to_string())
+        });
+        let Some(head) = broken else {
+            out.push(word);
+            continue;
+        };
+
+        let tail = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+        let hyphenated = format!("{}-{}", head, tail);
+        let joined = format!("{}{}", head, tail);
+        let keep_hyphen = if text.contains(&hyphenated) {
+            true
+        } else if text.contains(&joined) {
+            false
+        } else {
+            let first = head.rsplit('-').next().unwrap_or(&head).to_lowercase();
+            COMPOUND_PREFIXES.contains(&first.as_str())
+        };
+        if let Some(prev) = out.last_mut() {
+            if !keep_hyphen {
+                prev.pop();
+            }
+            prev.push_str(&word);
+        }
+    }
+    out
+}
+
+/// Text of PDF page `page_num`; an unreadable page comes back empty.
+pub fn extract_page_text(doc: &Document, page_num: u32) -> String {
+    doc.extract_text(&[page_num]).unwrap_or_default()
+}
+
+/// Words on PDF page `page_num`; an unreadable page comes back empty. `clean` drops the
+/// page number, the part of the page furniture one page alone gives away.
+pub fn extract_page_words(doc: &Document, page_num: u32, clean: bool) -> Vec<String> {
+    let text = extract_page_text(doc, page_num);
+    tokenize(&if clean { strip_page_numbers(&text) } else { text })
+}
+
+pub fn lazy_pdf_system(mut lazy: ResMut<LazyPdf>, mut rsvp: ResMut<RsvpState>) {
+    if lazy.source.is_none() {
+        return;
+    }
+    let current = rsvp.current_page_index;
+
+    // The page being read can't wait
+    if lazy.extracted.get(current) == Some(&false) {
+        lazy.extract(&mut rsvp, current);
+    }
+
+    let ahead = (current + 1..=current + LOOKAHEAD_PAGES).find(|&p| lazy.extracted.get(p) == Some(&false));
+    if let Some(page) = ahead {
+        lazy.extract(&mut rsvp, page);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_break_hyphens_are_merged() {
+        assert_eq!(tokenize("so inter-\nesting"), ["so", "interesting"]);
+        assert_eq!(tokenize("so-\ncial re-\nmember pre-\nsent co-\nordinate"), ["social", "remember", "present", "coordinate"]);
+    }
+
+    #[test]
+    fn real_compounds_keep_their_hyphen() {
+        assert_eq!(tokenize("a well-\nknown self-\naware non-\nlinear task"), ["a", "well-known", "self-aware", "non-linear", "task"]);
+        // The page's own spelling wins over the prefix list
+        assert_eq!(tokenize("re-\nentry and then re-entry"), ["re-entry", "and", "then", "re-entry"]);
+        // Suspended hyphens and capitalized words are left alone
+        assert_eq!(tokenize("pre- and post-war"), ["pre-", "and", "post-war"]);
+        assert_eq!(tokenize("Franco-\nPrussian"), ["Franco-", "Prussian"]);
+    }
+}
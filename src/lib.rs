@@ -1,282 +1,4771 @@
+// Bevy systems take their data as parameters, so long signatures are expected
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 use wasm_bindgen::prelude::*;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::time::Duration;
 use std::io::Cursor;
 use lopdf::Document;
-use bevy::asset::AssetMetaCheck;
+use bevy::asset::{AssetMetaCheck, LoadState};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
 use bevy::log::LogPlugin;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use unicode_bidi::Direction;
+
+mod adaptive;
+mod bidi;
+mod bookmarks;
+mod calibration;
+mod comprehension;
+mod definitions;
+mod doc_prefs;
+mod download;
+mod focus;
+mod docx;
+mod epub;
+mod font_preview;
+mod frequency;
+mod gamepad;
+mod goal;
+mod gutenberg;
+mod html;
+mod idle_pause;
+mod i18n;
+mod language;
+mod live_region;
+mod lazy_pdf;
+mod markdown;
+mod math;
+mod onboarding;
+mod page_furniture;
+mod pdf_extraction;
+mod parse_error;
+mod styled_text;
+mod search;
+mod stats;
+mod storage;
+mod streak;
+mod subtitles;
+mod syllables;
+mod text_stream;
+mod toast;
+mod toc;
+mod training;
+#[cfg(not(target_arch = "wasm32"))]
+mod window_size;
+use adaptive::{adaptive_controls, adaptive_speed_system, AdaptiveSpeed};
+use bidi::{direction_mark, page_direction};
+use bookmarks::{bookmark_import_system, Bookmark};
+use calibration::{calibration_system, Calibration, DEFAULT_WPM_KEY};
+use comprehension::{comprehension_controls, comprehension_system, ComprehensionCheck};
+use definitions::{definitions_system, Definitions};
+use doc_prefs::{document_key, document_prefs_system, DocumentPrefs};
+use docx::{is_docx, parse_docx};
+use epub::{is_epub, parse_epub};
+use focus::focus_mode_system;
+use font_preview::{register_font_previews_system, FontPreviews};
+use frequency::frequency_multiplier;
+use gamepad::gamepad_input_system;
+use gutenberg::{gutenberg_fetch_system, trim_boilerplate, GutenbergFetch};
+use goal::{goal_ring, reading_goal_system, ReadingGoal};
+use html::{looks_like_html, parse_html};
+use idle_pause::{idle_pause_system, IdlePause};
+use i18n::{tr, Lang};
+use language::{split_cjk_pages, DocLanguage, DEFAULT_CJK_CHUNK_CHARS};
+use live_region::live_region_system;
+use lazy_pdf::{extract_page_text, lazy_pdf_system, tokenize, DeferredPages, LazyPdf, PdfLoadMode};
+use markdown::parse_markdown;
+use math::{clean_math_pages, is_math_symbol};
+use styled_text::StyledTextBuilder;
+use onboarding::{onboarding_system, Onboarding};
+use page_furniture::strip_furniture;
+use parse_error::{decode_text, looks_binary, ParseError};
+use pdf_extraction::{should_extract_in_frames, PdfExtraction, PdfJob};
+use stats::{stats_controls, ReadingStats};
+use streak::{focus_streak_system, FocusStreak};
+use subtitles::{export_subtitles, SubtitleFormat};
+use syllables::estimate_syllables;
+use toast::{toast_system, Toasts};
+use toc::TocEntry;
+use training::{training_controls, training_plan_system, TrainingPlan};
+use text_stream::{push_plain_text, should_stream, text_stream_system, TextStream};
 
 // Ensure these files exist in your "assets/fonts/" folder!
 const AVAILABLE_FONTS: &[&str] = &[
     "Arimo-Regular.ttf",
     "EBGaramond-Regular.ttf",
     "Roboto-Regular.ttf",
-    "Tinos-Regular.ttf"
+    "Tinos-Regular.ttf",
+    "DejaVuSans.ttf",
+];
+
+// Families bundled in several static weights; the weight slider picks the nearest file
+const WEIGHTED_FONTS: &[(&str, &[(u16, &str)])] = &[
+    ("DejaVuSans.ttf", &[
+        (200, "DejaVuSans-ExtraLight.ttf"),
+        (400, "DejaVuSans.ttf"),
+        (700, "DejaVuSans-Bold.ttf"),
+    ]),
 ];
 
+/// Weights available for a font family, if it ships more than one.
+fn font_weights(name: &str) -> Option<&'static [(u16, &'static str)]> {
+    WEIGHTED_FONTS.iter().find(|(family, _)| *family == name).map(|(_, weights)| *weights)
+}
+
+/// Asset path of a bold face for `name`, when the family ships one.
+fn bold_font_path(name: &str) -> Option<String> {
+    font_weights(name)?.iter().any(|(w, _)| *w >= 700).then(|| font_path(name, 700))
+}
+
+/// Asset path for `name` at the weight closest to `weight`.
+fn font_path(name: &str, weight: u16) -> String {
+    let file = font_weights(name)
+        .and_then(|weights| weights.iter().min_by_key(|(w, _)| w.abs_diff(weight)))
+        .map_or(name, |(_, file)| *file);
+    format!("fonts/{}", file)
+}
+
+const MIN_WPM: f32 = 30.0;
+const MAX_WPM: f32 = 900.0;
+const MIN_FONT_SIZE: f32 = 20.0;
+const MAX_FONT_SIZE: f32 = 200.0;
+const MAX_WORDS_PER_FRAME: usize = 7;
+
+// Shared by the Digit1..Digit5 shortcuts and the preset buttons
+const WPM_PRESETS: [f32; 5] = [150.0, 250.0, 350.0, 500.0, 700.0];
+// Speed change per press (or key repeat) of Up / Down
+const KEY_WPM_STEP: f32 = 10.0;
+// Playback counts down from this, one digit per COUNTDOWN_STEP_SECS
+const COUNTDOWN_FROM: u8 = 3;
+const COUNTDOWN_STEP_SECS: f32 = 0.7;
+
 // --- GLOBAL MAILBOX ---
-static UPLOADED_FILE_QUEUE: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+// Latest upload wins: a file that arrives before the previous one was picked up replaces it,
+// and one picked up while a long PDF is still being extracted (`PdfExtraction`) cancels that.
+static UPLOADED_FILE_QUEUE: Mutex<Option<Upload>> = Mutex::new(None);
+
+struct Upload {
+    // File name as picked in the browser; its extension selects the parser
+    name: String,
+    bytes: Vec<u8>,
+}
+
+fn queue_upload(name: &str, data: &[u8]) {
+    let mut lock = UPLOADED_FILE_QUEUE.lock().unwrap();
+    if lock.replace(Upload { name: name.to_string(), bytes: data.to_vec() }).is_some() {
+        info!("Replacing an upload that had not been processed yet");
+    }
+}
 
+/// Queues a PDF for reading; bytes without a PDF header are read as plain text.
 #[wasm_bindgen]
 pub fn pass_file_to_bevy(data: &[u8]) {
-    let mut lock = UPLOADED_FILE_QUEUE.lock().unwrap();
-    *lock = Some(data.to_vec());
+    queue_upload("", data);
+}
+
+/// Queues a file whose format is sniffed (DOCX, EPUB, HTML) or chosen from `name` (`.md`), otherwise PDF.
+#[wasm_bindgen]
+pub fn pass_named_file_to_bevy(name: &str, data: &[u8]) {
+    queue_upload(name, data);
+}
+
+/// Queues text (e.g. clipboard contents) to read as a plain-text document. Blank text is
+/// ignored, leaving the open document as it is.
+#[wasm_bindgen]
+pub fn pass_text_to_bevy(text: &str) {
+    if !text.trim().is_empty() {
+        queue_upload("clipboard.txt", text.as_bytes());
+    }
+}
+
+static PENDING_FONT: Mutex<Option<Upload>> = Mutex::new(None);
+
+/// Adds a TTF or OTF font to the font selector under `name` and switches the reader to it.
+/// Uploaded fonts last until the page is reloaded.
+#[wasm_bindgen]
+pub fn pass_font_to_bevy(name: &str, data: &[u8]) {
+    *PENDING_FONT.lock().unwrap() = Some(Upload { name: name.to_string(), bytes: data.to_vec() });
+}
+
+const DEFAULT_IDLE_MESSAGE: &str = "Upload a PDF to begin.";
+
+static PENDING_IDLE_MESSAGE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Replaces the text shown while no document is loaded (e.g. a localized prompt).
+#[wasm_bindgen]
+pub fn set_idle_message(text: String) {
+    *PENDING_IDLE_MESSAGE.lock().unwrap() = Some(text);
+}
+
+static PENDING_WORD_DURATIONS: Mutex<Option<Vec<Option<f32>>>> = Mutex::new(None);
+
+/// Hand-tuned display times in milliseconds, one entry per word of the open document in
+/// reading order; `null` entries keep the computed timing and an empty array clears them all.
+#[wasm_bindgen]
+pub fn set_word_durations(durations: JsValue) -> Result<(), JsValue> {
+    let durations: Vec<Option<f32>> = serde_wasm_bindgen::from_value(durations)?;
+    *PENDING_WORD_DURATIONS.lock().unwrap() = Some(durations);
+    Ok(())
+}
+
+static PENDING_CUES: Mutex<Option<Vec<usize>>> = Mutex::new(None);
+
+/// Word indices (document-wide, in reading order) that fire the `set_on_cue` callback when
+/// shown, e.g. chapter starts. Replaces any earlier list; an empty array clears them.
+#[wasm_bindgen]
+pub fn set_cues(indices: JsValue) -> Result<(), JsValue> {
+    let indices: Vec<usize> = serde_wasm_bindgen::from_value(indices)?;
+    *PENDING_CUES.lock().unwrap() = Some(indices);
+    Ok(())
+}
+
+static PENDING_SEEK: Mutex<Option<usize>> = Mutex::new(None);
+
+/// Jumps to a document-wide word index and shows it at once, the same way the position
+/// slider does (the position left goes on the Back history). Out-of-range indices are ignored.
+#[wasm_bindgen]
+pub fn seek(word_index: u32) {
+    *PENDING_SEEK.lock().unwrap() = Some(word_index as usize);
+}
+
+// Playback commands from controls outside the canvas, applied in order by `js_command_system`
+enum JsCommand {
+    SetPlaying(bool),
+    SetWpm(f32),
+    SeekTo { page: usize, word: usize },
+}
+
+static PENDING_COMMANDS: Mutex<Vec<JsCommand>> = Mutex::new(Vec::new());
+
+fn queue_command(command: JsCommand) {
+    PENDING_COMMANDS.lock().unwrap().push(command);
+}
+
+/// Plays or pauses, like the Play button; playing a finished document starts it over.
+/// Ignored (like the other playback commands) while no document is open.
+#[wasm_bindgen]
+pub fn set_playing(playing: bool) {
+    queue_command(JsCommand::SetPlaying(playing));
+}
+
+/// Sets the reading speed, clamped to the range of the speed slider.
+#[wasm_bindgen]
+pub fn set_wpm(wpm: f32) {
+    queue_command(JsCommand::SetWpm(wpm));
+}
+
+/// Jumps to `word` of `page` (both counted from 0 and clamped to the document) and shows it
+/// at once; the position left goes on the Back history.
+#[wasm_bindgen(js_name = seek_to)]
+pub fn seek_to_position(page: u32, word: u32) {
+    queue_command(JsCommand::SeekTo { page: page as usize, word: word as usize });
+}
+
+// --- CLIPBOARD PASTE ---
+// Ctrl+V anywhere on the page loads the clipboard text as a new document, except while an
+// egui text field has focus (egui handles that paste itself).
+
+static EGUI_WANTS_KEYBOARD: AtomicBool = AtomicBool::new(false);
+
+#[cfg(target_arch = "wasm32")]
+fn install_paste_listener() {
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else { return };
+    let handler = Closure::<dyn FnMut(web_sys::ClipboardEvent)>::new(|event: web_sys::ClipboardEvent| {
+        if EGUI_WANTS_KEYBOARD.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(text) = event.clipboard_data().and_then(|data| data.get_data("text/plain").ok()) else { return };
+        if !text.trim().is_empty() {
+            queue_upload("clipboard.txt", text.as_bytes());
+        }
+    });
+    let _ = document.add_event_listener_with_callback("paste", handler.as_ref().unchecked_ref());
+    // The listener lives as long as the page
+    handler.forget();
+}
+
+// --- JS CALLBACKS ---
+// Bevy runs on the browser main thread, so callbacks registered from JS live in thread-locals.
+
+thread_local! {
+    static ON_FINISHED: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+    static ON_WORD: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+    static ON_CUE: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+    static ON_ERROR: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+    static ON_STATE_CHANGE: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+}
+
+/// Registers a function called with no arguments when the last word of the document has been read.
+#[wasm_bindgen]
+pub fn set_on_finished(callback: js_sys::Function) {
+    ON_FINISHED.with(|cb| *cb.borrow_mut() = Some(callback));
+}
+
+fn notify_finished() {
+    ON_FINISHED.with(|cb| {
+        if let Some(f) = cb.borrow().as_ref() {
+            let _ = f.call0(&JsValue::NULL);
+        }
+    });
+}
+
+/// Registers a function called as `(wordIndex, text)` whenever a new chunk appears, whether
+/// from playback, a manual step or a seek. `wordIndex` is the document-wide index of the
+/// chunk's first word; at most one call is made per frame.
+#[wasm_bindgen]
+pub fn set_on_word(callback: js_sys::Function) {
+    ON_WORD.with(|cb| *cb.borrow_mut() = Some(callback));
+}
+
+fn notify_word(index: usize, text: &str) {
+    ON_WORD.with(|cb| {
+        if let Some(f) = cb.borrow().as_ref() {
+            let _ = f.call2(&JsValue::NULL, &JsValue::from(index as u32), &JsValue::from_str(text));
+        }
+    });
+}
+
+/// Registers a function called as `(wordIndex, text)` when a word from `set_cues` comes on
+/// screen, once per cue each time it's reached (a seek landing on it counts).
+#[wasm_bindgen]
+pub fn set_on_cue(callback: js_sys::Function) {
+    ON_CUE.with(|cb| *cb.borrow_mut() = Some(callback));
+}
+
+fn notify_cue(index: usize, text: &str) {
+    ON_CUE.with(|cb| {
+        if let Some(f) = cb.borrow().as_ref() {
+            let _ = f.call2(&JsValue::NULL, &JsValue::from(index as u32), &JsValue::from_str(text));
+        }
+    });
+}
+
+/// Registers a function called with `{ code, message }` when an upload or a book fetch fails.
+/// `code` is one of `NOT_A_PDF`, `ENCRYPTED`, `NO_TEXT`, `IMAGE_ONLY` (a PDF without a text
+/// layer, usually a scan), `NETWORK_ERROR` or `UNSUPPORTED`; `message` is the
+/// English text the built-in UI shows.
+#[wasm_bindgen]
+pub fn set_on_error(callback: js_sys::Function) {
+    ON_ERROR.with(|cb| *cb.borrow_mut() = Some(callback));
+}
+
+fn notify_error(error: &ParseError) {
+    ON_ERROR.with(|cb| {
+        if let Some(f) = cb.borrow().as_ref() {
+            if let Ok(report) = serde_wasm_bindgen::to_value(&error.report()) {
+                let _ = f.call1(&JsValue::NULL, &report);
+            }
+        }
+    });
+}
+
+// --- STATE SNAPSHOT ---
+
+/// Everything an external UI can observe, refreshed every frame by `state_snapshot_system`.
+#[derive(Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct StateSnapshot {
+    page: usize,
+    word: usize,
+    total_pages: usize,
+    total_words: usize,
+    words_on_page: usize,
+    wpm: f32,
+    is_playing: bool,
+    finished: bool,
+    font: String,
+    // Fraction of the whole document read, 0.0..=1.0
+    progress: f32,
+}
+
+static STATE_SNAPSHOT: Mutex<Option<StateSnapshot>> = Mutex::new(None);
+
+/// Returns the reader's current state as a plain object, or `null` before the first frame.
+#[wasm_bindgen]
+pub fn get_state() -> JsValue {
+    let snapshot = STATE_SNAPSHOT.lock().unwrap().clone();
+    snapshot
+        .and_then(|s| serde_wasm_bindgen::to_value(&s).ok())
+        .unwrap_or(JsValue::NULL)
+}
+
+/// Registers a function called with the same object as `get_state` whenever any of it
+/// changes: a new word, a seek, play or pause, a speed change. Frames where nothing an
+/// external UI shows has changed make no call.
+#[wasm_bindgen]
+pub fn set_on_state_change(callback: js_sys::Function) {
+    ON_STATE_CHANGE.with(|cb| *cb.borrow_mut() = Some(callback));
+}
+
+fn notify_state_change(snapshot: &StateSnapshot) {
+    ON_STATE_CHANGE.with(|cb| {
+        if let Some(f) = cb.borrow().as_ref() {
+            if let Ok(state) = serde_wasm_bindgen::to_value(snapshot) {
+                let _ = f.call1(&JsValue::NULL, &state);
+            }
+        }
+    });
+}
+
+// --- STARTUP OPTIONS ---
+
+/// Settings an embedder can pass to `start_with_options`. Every field is optional;
+/// anything missing keeps the regular default.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct StartOptions {
+    wpm: Option<f32>,
+    font: Option<String>,
+    idle_message: Option<String>,
+    theme: Option<StartTheme>,
+    // Start with every control panel closed (the Panels menu stays available)
+    minimal_ui: bool,
+}
+
+/// The reader's color presets, by name (`"dark"` or `"light"`).
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum StartTheme {
+    Dark,
+    Light,
+}
+
+impl StartOptions {
+    fn apply(self, world: &mut World) {
+        let mut rsvp = world.resource_mut::<RsvpState>();
+        if let Some(wpm) = self.wpm {
+            rsvp.wpm = wpm.clamp(MIN_WPM, MAX_WPM);
+        }
+        if let Some(message) = self.idle_message {
+            rsvp.idle_message = message;
+            rsvp.show_idle_message();
+        }
+        match self.font {
+            Some(font) if AVAILABLE_FONTS.contains(&font.as_str()) => rsvp.current_font_name = font,
+            Some(font) => warn!("Unknown start font {:?}, using the default", font),
+            None => {}
+        }
+        // For this session only, like the speed; colors picked in the UI are what get saved
+        if let Some(theme) = self.theme {
+            let (text, background) = match theme {
+                StartTheme::Dark => DARK_THEME,
+                StartTheme::Light => LIGHT_THEME,
+            };
+            rsvp.text_color = text;
+            rsvp.background_color = background;
+        }
+
+        if self.minimal_ui {
+            world.resource_mut::<PanelLayout>().open = [false; 4];
+        }
+    }
 }
 
 // --- RESOURCES ---
 
+/// Structural/emphasis flags a parser can attach to a word. Formats without markup leave
+/// every word at the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct WordStyle {
+    heading: bool,
+    bold: bool,
+    italic: bool,
+    // Last word of a paragraph, heading or list item
+    paragraph_end: bool,
+    // Last word before a line break in the source (plain text only, for line mode)
+    line_end: bool,
+}
+
+/// Horizontal placement of the reader text and its context lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReaderAlign {
+    Center,
+    /// Flush against a left margin, so chunks start at a fixed point.
+    Left,
+}
+
+impl ReaderAlign {
+    const ALL: [ReaderAlign; 2] = [ReaderAlign::Center, ReaderAlign::Left];
+
+    fn label(self) -> &'static str {
+        match self {
+            ReaderAlign::Center => "Center",
+            ReaderAlign::Left => "Left",
+        }
+    }
+
+    fn justify_text(self) -> JustifyText {
+        match self {
+            ReaderAlign::Center => JustifyText::Center,
+            ReaderAlign::Left => JustifyText::Left,
+        }
+    }
+
+    fn justify_content(self) -> JustifyContent {
+        match self {
+            ReaderAlign::Center => JustifyContent::Center,
+            ReaderAlign::Left => JustifyContent::FlexStart,
+        }
+    }
+
+    fn padding_left(self) -> Val {
+        match self {
+            ReaderAlign::Center => Val::ZERO,
+            ReaderAlign::Left => Val::Percent(LEFT_ALIGN_MARGIN_PERCENT),
+        }
+    }
+
+    /// Left and right padding that leave a column `width` percent wide for wrapped text.
+    fn column_padding(self, width: f32) -> (Val, Val) {
+        match self {
+            ReaderAlign::Center => {
+                let side = Val::Percent(((100.0 - width) / 2.0).max(0.0));
+                (side, side)
+            }
+            ReaderAlign::Left => {
+                (self.padding_left(), Val::Percent((100.0 - LEFT_ALIGN_MARGIN_PERCENT - width).max(0.0)))
+            }
+        }
+    }
+}
+
+const LEFT_ALIGN_MARGIN_PERCENT: f32 = 15.0;
+
+/// What moves the reader on to the next chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AdvanceMode {
+    /// The dwell timer, as computed from WPM and the pacing rules.
+    Timed,
+    /// Only a key press (Space or Right); the timer is idle.
+    Manual,
+}
+
+/// How words are grouped into the chunks shown one at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkMode {
+    /// `words_per_frame` words, adjusted by the smart-chunking and tiny-word rules.
+    Words,
+    /// One source line per chunk (verse).
+    Lines,
+    /// One sentence per chunk.
+    Sentences,
+}
+
+impl ChunkMode {
+    const ALL: [ChunkMode; 3] = [ChunkMode::Words, ChunkMode::Lines, ChunkMode::Sentences];
+
+    fn label(self) -> &'static str {
+        match self {
+            ChunkMode::Words => "Words",
+            ChunkMode::Lines => "Line mode (verse)",
+            ChunkMode::Sentences => "Sentences",
+        }
+    }
+}
+
+/// What timed playback does when it runs off the end of a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EndOfPageBehavior {
+    /// Turn the page, with the page pause if one is set.
+    AutoAdvance,
+    /// Turn the page and stop until Play (or Space / Right) is pressed.
+    PauseEachPage,
+    /// No break at all: the next page's first word follows straight on.
+    Continuous,
+}
+
+/// Where playback wraps back to the start instead of moving on or stopping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopMode {
+    None,
+    /// Repeat the current page.
+    Page,
+    /// Start the whole document over at the end.
+    Document,
+    /// Go back to marker A after showing the word at marker B.
+    Section,
+}
+
+impl LoopMode {
+    const ALL: [LoopMode; 4] = [LoopMode::None, LoopMode::Page, LoopMode::Document, LoopMode::Section];
+
+    fn label(self) -> &'static str {
+        match self {
+            LoopMode::None => "Don't repeat",
+            LoopMode::Page => "Repeat page",
+            LoopMode::Document => "Repeat document",
+            LoopMode::Section => "Repeat from A to B",
+        }
+    }
+}
+
+impl EndOfPageBehavior {
+    const ALL: [EndOfPageBehavior; 3] =
+        [EndOfPageBehavior::AutoAdvance, EndOfPageBehavior::PauseEachPage, EndOfPageBehavior::Continuous];
+
+    fn label(self) -> &'static str {
+        match self {
+            EndOfPageBehavior::AutoAdvance => "Go on to the next page",
+            EndOfPageBehavior::PauseEachPage => "Pause at each page",
+            EndOfPageBehavior::Continuous => "Continuous",
+        }
+    }
+}
+
+/// How a word's base dwell scales with the word itself, before punctuation and other rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimingModel {
+    /// Every word gets 60 / WPM.
+    Flat,
+    /// Words past 8 letters get 5% more per extra letter.
+    Length,
+    /// Scales with estimated syllable count, averaging out near the base rate.
+    Syllables,
+}
+
+impl TimingModel {
+    const ALL: [TimingModel; 3] = [TimingModel::Flat, TimingModel::Length, TimingModel::Syllables];
+
+    fn label(self) -> &'static str {
+        match self {
+            TimingModel::Flat => "Flat",
+            TimingModel::Length => "Word length",
+            TimingModel::Syllables => "Syllables",
+        }
+    }
+
+    fn multiplier(self, word: &str) -> f32 {
+        match self {
+            TimingModel::Flat => 1.0,
+            TimingModel::Length => {
+                let len = word.chars().filter(|c| c.is_alphanumeric()).count();
+                1.0 + len.saturating_sub(8) as f32 * 0.05
+            }
+            // English averages ~1.5 syllables per word, which lands close to 1.0
+            TimingModel::Syllables => 0.5 + 0.35 * estimate_syllables(word).max(1) as f32,
+        }
+    }
+}
+
 #[derive(Resource)]
 struct RsvpState {
+    lang: Lang,
+
+    // Read out as the document until a real one is uploaded
+    idle_message: String,
+    document_loaded: bool,
+
+    // Import cleanup: keep only the book between Project Gutenberg's START/END markers
+    trim_gutenberg_boilerplate: bool,
+    // How much of a PDF is tokenized up front (see `lazy_pdf`)
+    pdf_load_mode: PdfLoadMode,
+    // Drop page numbers and running headers from PDF text (see `page_furniture`)
+    clean_extracted_text: bool,
+    // Language of the open document as detected on upload, and the user's saved choice
+    // that replaces it (see `language`)
+    detected_language: Option<DocLanguage>,
+    language_override: Option<DocLanguage>,
+    // CJK characters per chunk, since there are no spaces to split words on
+    cjk_chunk_chars: usize,
+    // Page size for formats without pages of their own (plain text, pasted input)
+    words_per_page: usize,
+    // Whether the open document's pages came from `words_per_page` and can be recut
+    paginated_by_words: bool,
+    // The upload on screen and the import settings it was parsed with; `None` once anything
+    // else replaces it
+    loaded_upload: Option<UploadId>,
+
     // Outer Vec = Pages, Inner Vec = Words in that page
     pages: Vec<Vec<String>>,
+    // Parallel to `pages`; a page with no styled words may have an empty Vec
+    styles: Vec<Vec<WordStyle>>,
+    // Original PDF page number of each entry in `pages` (blank pages are skipped on load)
+    source_pages: Vec<u32>,
+    bookmarks: Vec<Bookmark>,
+    // From the PDF outline; empty for documents without one (the contents list shows pages then)
+    toc: Vec<TocEntry>,
+    // Positions left by discontinuous jumps (sliders, bookmarks, minimap), newest last
+    nav_history: Vec<(usize, usize)>,
+    // Text in the search box, where it's found (kept current by `refresh_totals`), and the
+    // match last jumped to
+    search_query: String,
+    search_matches: Vec<(usize, usize)>,
+    search_cursor: Option<usize>,
+    // Corner readout of the source page and word, for citing while reading
+    page_overlay: bool,
+    // Per-word dwell overrides in ms by flat index (see `set_word_durations`); empty = none
+    word_durations: Vec<Option<f32>>,
+    // Flat indices reported through `set_on_cue`, sorted and without repeats
+    cues: Vec<usize>,
+    // Cached on every `set_pages`: flat index of each page's first word, and the sum
+    page_offsets: Vec<usize>,
+    total_words: usize,
     
     current_page_index: usize,
     current_word_index: usize,
     
+    // `wpm` is the rate the user asked for; timing runs on `effective_wpm`, which eases
+    // toward it when `smooth_wpm` is on
     wpm: f32,
+    effective_wpm: f32,
+    smooth_wpm: bool,
+    // Speeds the user settled on before the current one, newest last, for "Undo speed".
+    // `settled_wpm` only moves once `wpm` has held still for WPM_SETTLE_SECS
+    wpm_history: Vec<f32>,
+    settled_wpm: f32,
+    wpm_unsettled_secs: f32,
+    // Offer a speed based on document length when a new document opens; the pending offer
+    // is the toast id and the suggested WPM
+    suggest_wpm_on_open: bool,
+    wpm_suggestion: Option<(u64, f32)>,
+    // Speed ramp: playback raises `wpm` by `ramp_rate_wpm_per_min` per minute of reading,
+    // from `ramp_start_wpm` when switched on, and switches itself off at the target
+    ramp_enabled: bool,
+    ramp_start_wpm: f32,
+    ramp_target_wpm: f32,
+    ramp_rate_wpm_per_min: f32,
+    // Give the low/mid WPM range more slider travel
+    log_wpm_slider: bool,
+    chunk_mode: ChunkMode,
     words_per_frame: usize, 
+    // Break chunks early at sentence ends
+    smart_chunking: bool,
+    // Show words of at most `merge_max_len` letters together with the word after them
+    merge_tiny_words: bool,
+    merge_max_len: usize,
     
+    advance_mode: AdvanceMode,
     is_playing: bool,
+    // Reached the end of the document; cleared by any seek
+    finished: bool,
+    // Running count of words shown by playback, for goals and stats
+    words_advanced: u64,
+    stats: ReadingStats,
+    // Set by `seek_to`, so the tick later in the same frame leaves the new chunk alone
+    seeked_this_frame: bool,
+    // "3, 2, 1" before playback starts or resumes; the digit still to show, while counting
+    show_countdown: bool,
+    countdown_remaining: Option<u8>,
+    // Words to back up by whenever playback starts again, to pick the sentence back up; 0 = off
+    rewind_on_resume: usize,
+    // Timed playback steps backwards through the document, for proofreading; stops at the start
+    reverse: bool,
+    loop_mode: LoopMode,
+    // Times the loop has wrapped since it was turned on or the document opened. Words
+    // shown again by a loop only reach `words_advanced` with `count_looped_words`.
+    loop_passes: u32,
+    count_looped_words: bool,
+    // (page, word) markers of the repeated section, kept in order (A never after B)
+    loop_a: Option<(usize, usize)>,
+    loop_b: Option<(usize, usize)>,
     timer: Timer,
+
+    timing_model: TimingModel,
+
+    // Pause multipliers by trailing punctuation class
+    sentence_pause_multiplier: f32,
+    clause_pause_multiplier: f32,
+    dash_pause_multiplier: f32,
+
+    // Extra dwell for words a parser marked as headings
+    heading_pause_multiplier: f32,
+
+    // Shorter dwell for filler: words of at most `short_word_len` letters or common stop words
+    short_word_speedup: bool,
+    short_word_len: usize,
+    short_word_factor: f32,
+
+    // Dwell scaled by how common the word is in English (see `frequency`); 0..=1 strength
+    frequency_timing: bool,
+    frequency_intensity: f32,
+
+    // Extra beat for capitalized / all-caps words
+    proper_noun_slowdown: bool,
+    proper_noun_multiplier: f32,
+
+    // Extra time for numeric tokens, growing with digit count
+    numeric_slowdown: bool,
+    numeric_multiplier: f32,
+
+    // Reassembles formulas split by PDF extraction and dwells longer on math symbols
+    math_cleanup: bool,
+
+    // Fixed extra pause for words with more letters than the threshold (0 ms = off)
+    long_word_len_threshold: usize,
+    long_word_extra_ms: f32,
+
+    // Dwell clamps, applied after every timing multiplier
+    min_word_ms: f32,
+    max_word_ms: f32,
+    // (page, start, end) of the chunk currently on screen
+    displayed_chunk: Option<(usize, usize, usize)>,
+    // What the timer's current duration was computed from; see `DwellInputs`
+    dwell_inputs: Option<DwellInputs>,
     
+    // Blank "breath" between sentences
+    sentence_gap: bool,
+    sentence_gap_ms: f32,
+    in_sentence_gap: bool,
+
+    // Brief hold (optionally showing the page number) when reading flows onto a new page
+    page_transition_pause_ms: f32,
+    page_transition_flash: bool,
+    end_of_page: EndOfPageBehavior,
+    // Stopped by `PauseEachPage`; Space / Right resume as well as Play
+    waiting_at_page: bool,
+    in_page_transition: bool,
+
+    // Optimal Recognition Point: color one pivot letter per word
+    orp_highlight: bool,
+    orp_center_word_only: bool,
+    // Shift the reader line so the (middle) pivot letter sits at the window's center
+    orp_fixed_pivot: bool,
+    // Where the pivot falls along a word's letters (0 = first letter, 0.5 = middle)
+    orp_ratio: f32,
+
+    // Tint long/hard words toward a warm color
+    difficulty_coloring: bool,
+    difficulty_intensity: f32,
+
+    // Three-line mode: previous words above, upcoming words below
+    peripheral_context: bool,
+    // Unmultiplied sRGBA of the line above (words already read) and of the line below (words to come)
+    context_read_color: [u8; 4],
+    context_upcoming_color: [u8; 4],
+    context_before: usize,
+    context_after: usize,
+    // Gap between the focus line and the context lines, as a multiple of the default
+    context_line_spacing: f32,
+    // Reading column for the context lines, as a percentage of window width
+    context_max_width: f32,
+    // Context words allowed from the neighbouring page at a page break (0 = stop at the edge)
+    cross_page_context: usize,
+    // Forward-only priming: just the upcoming line, `look_ahead_words` long
+    look_ahead: bool,
+    look_ahead_words: usize,
+
+    // Crossfade between words
+    fade_transitions: bool,
+    fade_ms: f32,
+    // Fade-in only, with no dip between words; the crossfade takes over while it's on
+    smooth_transitions: bool,
+    reduce_motion: bool,
+    // Spotlight: everything but the reader text is veiled at `focus_dim` opacity
+    focus_mode: bool,
+    focus_dim: f32,
+    // Words played since the last pause or jump, in small print under the reader
+    show_streak: bool,
+    // Page, percentage and (when paused) the sentence, in an aria-live region for screen readers
+    announce_progress: bool,
+    // Playback pauses after this long without any input; 0 turns auto-pause off
+    idle_pause_minutes: u32,
+    // Subtitle export: file format, and one cue per sentence instead of per chunk
+    subtitle_format: SubtitleFormat,
+    subtitle_sentences: bool,
+    // Text typed or pasted into the controls, loaded as a document with "Load text"
+    paste_text: String,
+
     font_size: f32,
+    align: ReaderAlign,
+    // Multi-word chunks wrap inside a column this many percent of the window wide
+    reading_column_width: f32,
+    // Where the focus word's line sits, as a percentage of window height (50 = centered)
+    vertical_position: f32,
+    // Tracking aid: a thin line under the focus word, `ruler_width` percent of the window wide
+    ruler: bool,
+    ruler_width: f32,
+    ruler_color: [u8; 3],
+    // Short tick marks above and below the center of the line, marking the pivot column
+    show_guides: bool,
+    // Reader text (where no highlight applies) and the window behind it
+    text_color: [u8; 3],
+    background_color: [u8; 3],
+    // CSS-style weight; only families in WEIGHTED_FONTS react to it
+    font_weight: u16,
     current_font_handle: Handle<Font>,
+    // Bold face of the current family, if it has one (see `bold_font_path`)
+    bold_font_handle: Option<Handle<Font>>,
+    // Draw words the source marks bold or italic differently
+    show_emphasis: bool,
     current_font_name: String,
+    // Fonts added with `pass_font_to_bevy`, selectable alongside AVAILABLE_FONTS
+    custom_fonts: Vec<(String, Handle<Font>)>,
 }
 
 impl Default for RsvpState {
     fn default() -> Self {
-        // Default demo text (Page 1)
-        let page1 = vec!["Upload".into(), "a".into(), "PDF".into(), "to".into(), "begin.".into()];
-        
-        Self {
-            pages: vec![page1],
+        // Set by the speed calibration
+        let default_wpm = storage::load(DEFAULT_WPM_KEY)
+            .and_then(|v| v.parse::<f32>().ok())
+            .map_or(300.0, |wpm| wpm.clamp(MIN_WPM, MAX_WPM));
+        let mut state = Self {
+            lang: Lang::detect(),
+            idle_message: DEFAULT_IDLE_MESSAGE.to_string(),
+            document_loaded: false,
+            trim_gutenberg_boilerplate: true,
+            pdf_load_mode: PdfLoadMode::Eager,
+            clean_extracted_text: true,
+            detected_language: None,
+            loaded_upload: None,
+            language_override: language::load_override(),
+            cjk_chunk_chars: storage::load(CJK_CHUNK_KEY).and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_CJK_CHUNK_CHARS),
+            words_per_page: storage::load(WORDS_PER_PAGE_KEY)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_WORDS_PER_PAGE),
+            paginated_by_words: false,
+            pages: Vec::new(),
+            styles: Vec::new(),
+            source_pages: Vec::new(),
+            bookmarks: Vec::new(),
+            toc: Vec::new(),
+            nav_history: Vec::new(),
+            search_query: String::new(),
+            search_matches: Vec::new(),
+            search_cursor: None,
+            page_overlay: false,
+            word_durations: Vec::new(),
+            cues: Vec::new(),
+            page_offsets: Vec::new(),
+            total_words: 0,
             current_page_index: 0,
             current_word_index: 0,
-            wpm: 300.0,
+            wpm: default_wpm,
+            effective_wpm: default_wpm,
+            wpm_history: Vec::new(),
+            settled_wpm: default_wpm,
+            wpm_unsettled_secs: 0.0,
+            smooth_wpm: true,
+            ramp_enabled: false,
+            ramp_start_wpm: 200.0,
+            ramp_target_wpm: 400.0,
+            ramp_rate_wpm_per_min: 20.0,
+            suggest_wpm_on_open: false,
+            wpm_suggestion: None,
+            log_wpm_slider: false,
+            chunk_mode: ChunkMode::Words,
             words_per_frame: 1, 
+            smart_chunking: false,
+            merge_tiny_words: false,
+            merge_max_len: 2,
+            advance_mode: AdvanceMode::Timed,
             is_playing: false,
+            finished: false,
+            words_advanced: 0,
+            stats: ReadingStats::default(),
+            seeked_this_frame: false,
+            show_countdown: true,
+            countdown_remaining: None,
+            rewind_on_resume: 3,
+            reverse: false,
+            loop_mode: LoopMode::None,
+            loop_passes: 0,
+            count_looped_words: false,
+            loop_a: None,
+            loop_b: None,
             timer: Timer::from_seconds(60.0 / 300.0, TimerMode::Repeating),
+            timing_model: TimingModel::Flat,
+            sentence_pause_multiplier: 2.0,
+            clause_pause_multiplier: 1.5,
+            dash_pause_multiplier: 1.3,
+            heading_pause_multiplier: 1.5,
+            short_word_speedup: false,
+            short_word_len: 2,
+            short_word_factor: 0.7,
+            frequency_timing: false,
+            frequency_intensity: 0.5,
+            proper_noun_slowdown: false,
+            proper_noun_multiplier: 1.3,
+            numeric_slowdown: false,
+            math_cleanup: false,
+            numeric_multiplier: 1.5,
+            long_word_len_threshold: 12,
+            long_word_extra_ms: 0.0,
+            min_word_ms: 40.0,
+            max_word_ms: 2500.0,
+            displayed_chunk: None,
+            dwell_inputs: None,
+            sentence_gap: false,
+            sentence_gap_ms: 150.0,
+            in_sentence_gap: false,
+            page_transition_pause_ms: 0.0,
+            page_transition_flash: true,
+            end_of_page: EndOfPageBehavior::AutoAdvance,
+            waiting_at_page: false,
+            in_page_transition: false,
+            orp_highlight: false,
+            orp_center_word_only: false,
+            orp_fixed_pivot: true,
+            orp_ratio: storage::load(ORP_RATIO_KEY)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_ORP_RATIO),
+            difficulty_coloring: false,
+            difficulty_intensity: 0.7,
+            peripheral_context: false,
+            context_read_color: [255, 255, 255, (CONTEXT_ALPHA * 255.0) as u8],
+            context_upcoming_color: [255, 255, 255, (CONTEXT_ALPHA * 255.0) as u8],
+            context_before: 5,
+            context_after: 5,
+            context_line_spacing: 1.0,
+            context_max_width: 80.0,
+            cross_page_context: 5,
+            look_ahead: false,
+            look_ahead_words: 3,
+            fade_transitions: false,
+            fade_ms: 80.0,
+            smooth_transitions: false,
+            reduce_motion: false,
+            focus_mode: false,
+            focus_dim: 0.85,
+            show_streak: false,
+            announce_progress: true,
+            idle_pause_minutes: storage::load(IDLE_PAUSE_KEY).and_then(|v| v.parse().ok()).unwrap_or(0),
+            subtitle_format: SubtitleFormat::Srt,
+            subtitle_sentences: false,
+            paste_text: String::new(),
             font_size: 100.0,
+            align: ReaderAlign::Center,
+            reading_column_width: storage::load(READING_COLUMN_KEY)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(70.0),
+            vertical_position: storage::load(VERTICAL_POSITION_KEY)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(50.0),
+            ruler: false,
+            ruler_width: 40.0,
+            ruler_color: [255, 255, 255],
+            show_guides: false,
+            text_color: load_rgb(TEXT_COLOR_KEY).unwrap_or(DARK_THEME.0),
+            background_color: load_rgb(BACKGROUND_COLOR_KEY).unwrap_or(DARK_THEME.1),
+            font_weight: 400,
             // Initialize with default, but we MUST overwrite this in setup before use
             current_font_handle: Handle::default(),
+            bold_font_handle: None,
+            show_emphasis: true,
             current_font_name: "Default".to_string(),
-        }
+            custom_fonts: Vec::new(),
+        };
+        state.show_idle_message();
+        state
     }
 }
 
-#[derive(Component)]
-struct ReaderText;
+impl RsvpState {
+    /// Loads `idle_message` as the document, unless the user has already opened a real one.
+    fn show_idle_message(&mut self) {
+        if self.document_loaded {
+            return;
+        }
+        let words: Vec<String> = self.idle_message.split_whitespace().map(String::from).collect();
+        self.set_pages(if words.is_empty() { Vec::new() } else { vec![words] });
+        self.current_page_index = 0;
+        self.current_word_index = 0;
+        self.displayed_chunk = None;
+    }
 
-// --- SYSTEMS ---
+    /// Replaces the document with unstyled words, refreshing the cached word totals.
+    fn set_pages(&mut self, pages: Vec<Vec<String>>) {
+        self.set_document(pages, Vec::new());
+    }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut rsvp: ResMut<RsvpState>) {
-    commands.spawn(Camera2d::default());
-    
-    // 1. SAFETY: Load the font immediately into a local variable
-    let font_name = AVAILABLE_FONTS.first().unwrap_or(&"Arimo-Regular.ttf"); 
-    let font_handle = asset_server.load(format!("fonts/{}", font_name));
-    
-    // 2. Update Resource with this valid handle
-    rsvp.current_font_name = font_name.to_string();
-    rsvp.current_font_handle = font_handle.clone();
+    /// Replaces the document along with per-word styles (parallel to `pages`, may be shorter).
+    fn set_document(&mut self, pages: Vec<Vec<String>>, styles: Vec<Vec<WordStyle>>) {
+        self.styles = styles;
+        self.source_pages.clear();
+        self.toc.clear();
+        self.word_durations.clear();
+        self.cues.clear();
+        self.nav_history.clear();
+        self.paginated_by_words = false;
+        self.detected_language = None;
+        self.loaded_upload = None;
+        self.loop_passes = 0;
+        self.pages = pages;
+        self.refresh_totals();
+    }
 
-    // 3. Spawn Text with the VALID handle (never use Handle::default() for TextFont)
-    commands.spawn((
-        Text::new("Ready"),
-        TextFont {
-            font: font_handle, // Use the local strong handle
-            font_size: rsvp.font_size,
-            ..default()
-        },
-        TextColor(Color::WHITE),
-        // CRITICAL FIX: Use NoWrap. WordBoundary causes panics if font metrics aren't ready during extraction.
-        TextLayout::new(JustifyText::Center, LineBreak::NoWrap),
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            position_type: PositionType::Absolute,
-            justify_content: JustifyContent::Center, 
-            align_items: AlignItems::Center,         
-            ..default()
-        },
-        ReaderText,
-    ));
-}
+    /// Adds pages to the end of the document (e.g. as a large file streams in).
+    fn append_pages(&mut self, pages: Vec<Vec<String>>, styles: Vec<Vec<WordStyle>>) {
+        if pages.is_empty() {
+            return;
+        }
+        let (pages, styles) = self.prepare_pages(pages, styles);
+        // Keep `styles` aligned with `pages` before extending both
+        self.styles.resize(self.pages.len(), Vec::new());
+        self.styles.extend(styles);
+        self.pages.extend(pages);
+        self.refresh_totals();
+    }
 
-fn ui_controls_system(
-    mut contexts: EguiContexts, 
-    mut rsvp: ResMut<RsvpState>, 
-    asset_server: Res<AssetServer>, 
-    mut text_query: Query<&mut TextFont, With<ReaderText>>
-) {
-    egui::Window::new("Reader Settings")
-        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0]) 
-        .default_width(200.0)
-        .show(contexts.ctx_mut(), |ui| {
-            
-            ui.heading("Controls");
-            
-            if ui.button(if rsvp.is_playing { "Pause" } else { "Play" }).clicked() { 
-                rsvp.is_playing = !rsvp.is_playing; 
-            }
-            
-            ui.separator();
-            
-            ui.horizontal(|ui| {
-                ui.label("Page:");
-                let mut page_display = rsvp.current_page_index + 1;
-                let total_pages = rsvp.pages.len().max(1);
-                
-                if ui.add(egui::Slider::new(&mut page_display, 1..=total_pages)).changed() {
-                    rsvp.current_page_index = page_display - 1;
-                    rsvp.current_word_index = 0;
-                }
-                ui.label(format!("/ {}", total_pages));
-            });
-            
-            let current_len = rsvp.pages.get(rsvp.current_page_index).map(|p| p.len()).unwrap_or(1);
-            let progress = (rsvp.current_word_index as f32 / current_len as f32).min(1.0);
-            ui.add(egui::ProgressBar::new(progress).text("Page Progress"));
-
-            ui.separator();
-
-            ui.label(format!("Speed: {:.0} WPM", rsvp.wpm));
-            ui.add(egui::Slider::new(&mut rsvp.wpm, 30.0..=900.0));
-
-            ui.separator();
-            
-            ui.label(format!("Words Per Frame: {}", rsvp.words_per_frame));
-            ui.add(egui::Slider::new(&mut rsvp.words_per_frame, 1..=7));
-            
-            ui.separator();
-
-            ui.label("Text Size");
-            if ui.add(egui::Slider::new(&mut rsvp.font_size, 20.0..=200.0)).changed() {
-                for mut font in text_query.iter_mut() { font.font_size = rsvp.font_size; }
-            }
-
-            ui.separator();
-
-            ui.label("Font Family");
-            
-            // Decoupled Font Selection Logic (Prevents UI lock/crash)
-            let mut selected_font = rsvp.current_font_name.clone();
-            let mut font_changed = false;
-
-            egui::ComboBox::from_id_salt("font_sel")
-                .selected_text(&selected_font)
-                .show_ui(ui, |ui| {
-                    for font_name in AVAILABLE_FONTS {
-                        if ui.selectable_value(&mut selected_font, font_name.to_string(), *font_name).clicked() {
-                            font_changed = true;
-                        }
-                    }
-                });
+    /// Recuts the whole document into pages of `words_per_page`, moving bookmarks along.
+    fn repaginate(&mut self) {
+        let bookmark_flats: Vec<usize> = self.bookmarks.iter().map(|b| self.flat_index(b.page, b.word)).collect();
+        let history_flats: Vec<usize> = self.nav_history.iter().map(|&(page, word)| self.flat_index(page, word)).collect();
 
-            if font_changed {
-                rsvp.current_font_name = selected_font;
-                let new_handle = asset_server.load(format!("fonts/{}", rsvp.current_font_name));
-                rsvp.current_font_handle = new_handle.clone();
-                
-                for mut font in text_query.iter_mut() { 
-                    font.font = new_handle.clone(); 
-                }
-            }
-        });
-}
+        self.styles.resize(self.pages.len(), Vec::new());
+        let mut styles = Vec::with_capacity(self.total_words);
+        for (page, page_styles) in self.pages.iter().zip(std::mem::take(&mut self.styles)) {
+            styles.extend(page_styles.into_iter().chain(std::iter::repeat(WordStyle::default())).take(page.len()));
+        }
+        let words: Vec<String> = std::mem::take(&mut self.pages).into_iter().flatten().collect();
 
-fn file_listener_system(mut rsvp: ResMut<RsvpState>) {
-    let mut lock = UPLOADED_FILE_QUEUE.lock().unwrap();
-    
-    if let Some(data) = lock.take() {
-        info!("Processing PDF...");
-        let cursor = Cursor::new(data);
-        
-        match Document::load_from(cursor) {
-            Ok(doc) => {
-                let mut new_pages = Vec::new();
-
-                let mut page_numbers: Vec<u32> = doc.get_pages().keys().cloned().collect();
-                page_numbers.sort();
-
-                for page_num in page_numbers {
-                    if let Ok(text) = doc.extract_text(&[page_num]) {
-                        let words: Vec<String> = text
-                            .split_whitespace()
-                            .map(|s| s.to_string())
-                            .collect();
-                        
-                        if !words.is_empty() {
-                            new_pages.push(words);
-                        }
-                    }
-                }
+        let size = self.words_per_page.max(1);
+        self.pages = words.chunks(size).map(<[String]>::to_vec).collect();
+        self.styles = styles.chunks(size).map(<[WordStyle]>::to_vec).collect();
+        self.refresh_totals();
 
-                if !new_pages.is_empty() {
-                    rsvp.pages = new_pages;
-                    rsvp.current_page_index = 0;
-                    rsvp.current_word_index = 0;
-                    rsvp.is_playing = true;
-                    info!("PDF Parsed. Pages: {}", rsvp.pages.len());
-                } else {
-                    error!("PDF contained no text.");
-                }
+        for (i, flat) in bookmark_flats.into_iter().enumerate() {
+            let (page, word) = self.position_from_flat(flat);
+            self.bookmarks[i].page = page;
+            self.bookmarks[i].word = word;
+        }
+        self.nav_history = history_flats.into_iter().map(|flat| self.position_from_flat(flat)).collect();
+    }
+
+    /// Swaps in the words of one page (e.g. once it has been extracted).
+    fn replace_page(&mut self, page: usize, words: Vec<String>) {
+        let (mut words, _) = self.prepare_pages(vec![words], Vec::new());
+        if let Some(slot) = self.pages.get_mut(page) {
+            *slot = words.pop().unwrap_or_default();
+            self.refresh_totals();
+        }
+    }
+
+    /// The manual language choice, or else the detected one.
+    fn document_language(&self) -> Option<DocLanguage> {
+        self.language_override.or(self.detected_language)
+    }
+
+    /// `prepare_pages` with the open document's language and settings.
+    fn prepare_pages(&self, pages: Vec<Vec<String>>, styles: Vec<Vec<WordStyle>>) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+        prepare_pages(pages, styles, self.document_language(), self.math_cleanup, self.cjk_chunk_chars)
+    }
+
+    /// Base direction text on `page` is laid out in: the document language's, or for an
+    /// unknown language, that of the page's first strongly directional word.
+    fn base_direction(&self, page: usize) -> Direction {
+        match self.document_language() {
+            Some(language) if language.is_rtl() => Direction::Rtl,
+            Some(_) => Direction::Ltr,
+            None => page_direction(self.pages.get(page).map_or(&[][..], |p| p.as_slice())),
+        }
+    }
+
+    fn refresh_totals(&mut self) {
+        self.page_offsets = self.pages
+            .iter()
+            .scan(0, |offset, page| {
+                let start = *offset;
+                *offset += page.len();
+                Some(start)
+            })
+            .collect();
+        self.total_words = self.pages.iter().map(|p| p.len()).sum();
+        self.refresh_search();
+    }
+
+    /// Re-runs the search over the current pages, forgetting the match last jumped to.
+    fn refresh_search(&mut self) {
+        self.search_matches = search::find_matches(&self.pages, &self.search_query);
+        self.search_cursor = None;
+    }
+
+    /// The PDF's own page number for `page`, if the document came with one.
+    fn source_page(&self, page: usize) -> Option<u32> {
+        self.source_pages.get(page).copied()
+    }
+
+    fn custom_font(&self, name: &str) -> Option<&Handle<Font>> {
+        self.custom_fonts.iter().find(|(n, _)| n == name).map(|(_, handle)| handle)
+    }
+
+    /// Whether `name` is a bundled or uploaded font.
+    fn has_font(&self, name: &str) -> bool {
+        AVAILABLE_FONTS.contains(&name) || self.custom_font(name).is_some()
+    }
+
+    fn text_color(&self) -> Color {
+        let [r, g, b] = self.text_color;
+        Color::srgb_u8(r, g, b)
+    }
+
+    fn word_style(&self, page: usize, word: usize) -> WordStyle {
+        self.styles.get(page).and_then(|p| p.get(word)).copied().unwrap_or_default()
+    }
+
+    /// Returns to the previous settled speed, if there is one. A change that hasn't settled
+    /// yet is undone first.
+    fn undo_wpm(&mut self) -> Option<f32> {
+        let wpm = if self.wpm != self.settled_wpm { self.settled_wpm } else { self.wpm_history.pop()? };
+        self.wpm = wpm;
+        self.settled_wpm = wpm;
+        self.wpm_unsettled_secs = 0.0;
+        Some(wpm)
+    }
+
+    /// Records the current position so `go_back` can return to it after a jump.
+    fn remember_position(&mut self) {
+        let position = current_position(self);
+        if self.nav_history.last() != Some(&position) {
+            if self.nav_history.len() == NAV_HISTORY_LEN {
+                self.nav_history.remove(0);
+            }
+            self.nav_history.push(position);
+        }
+    }
+
+    /// Position of (`page`, `word`) in the document read as one flat word stream.
+    fn flat_index(&self, page: usize, word: usize) -> usize {
+        self.page_offsets.get(page).map_or(self.total_words, |offset| offset + word)
+    }
+
+    /// Flat indices of the section markers, once both are set.
+    fn loop_section(&self) -> Option<(usize, usize)> {
+        let (a, b) = (self.loop_a?, self.loop_b?);
+        Some((self.flat_index(a.0, a.1), self.flat_index(b.0, b.1)))
+    }
+
+    /// Sets marker A or B at the current position, swapping the two if A would land after B.
+    fn set_loop_marker(&mut self, is_a: bool) {
+        let position = Some(current_position(self));
+        if is_a {
+            self.loop_a = position;
+        } else {
+            self.loop_b = position;
+        }
+        if self.loop_section().is_some_and(|(a, b)| a > b) {
+            std::mem::swap(&mut self.loop_a, &mut self.loop_b);
+        }
+    }
+
+    /// Whether the read position has just run off the end of a page onto a following one.
+    fn at_page_break(&self) -> bool {
+        let flat = self.flat_index(self.current_page_index, self.current_word_index);
+        self.page_offsets.get(self.current_page_index + 1) == Some(&flat)
+    }
+
+    /// Inverse of `flat_index`; indices past the end clamp to the last word.
+    fn position_from_flat(&self, flat: usize) -> (usize, usize) {
+        let flat = flat.min(self.total_words.saturating_sub(1));
+        // Last page starting at or before `flat`; empty pages share an offset and get skipped
+        let page = self.page_offsets.partition_point(|&offset| offset <= flat).saturating_sub(1);
+        (page, flat - self.page_offsets.get(page).copied().unwrap_or(0))
+    }
+}
+
+#[derive(Default, PartialEq, Eq)]
+enum FadePhase {
+    #[default]
+    Idle,
+    Out,
+    In,
+}
+
+/// Crossfade between words: the old word fades out, the text swaps, the new word fades in.
+/// The smooth transition skips the fade out and brings the new word up from `from`.
+#[derive(Resource, Default)]
+struct WordFade {
+    phase: FadePhase,
+    elapsed: f32,
+    // Both phases together; each takes half
+    duration: f32,
+    // Alpha the fade in starts at
+    from: f32,
+    pending: Option<Vec<Segment>>,
+}
+
+impl WordFade {
+    fn start(&mut self, segments: Vec<Segment>, duration: f32) {
+        self.phase = FadePhase::Out;
+        self.elapsed = 0.0;
+        self.duration = duration;
+        self.from = 0.0;
+        self.pending = Some(segments);
+    }
+
+    /// Shows `segments` at once, at `from` alpha, and brings them up to full over `secs`.
+    fn start_in(&mut self, display: &mut ReaderDisplay, segments: Vec<Segment>, secs: f32, from: f32) {
+        display.set(segments);
+        self.phase = FadePhase::In;
+        self.elapsed = 0.0;
+        self.duration = secs * 2.0;
+        self.from = from;
+        self.pending = None;
+    }
+
+    fn cancel(&mut self) {
+        self.phase = FadePhase::Idle;
+        self.pending = None;
+    }
+}
+
+// --- DISPLAY ---
+
+const TEXT_COLOR: Color = Color::WHITE;
+// (text, background) presets; Dark is the default look, Bevy's own clear color behind white
+const DARK_THEME: ([u8; 3], [u8; 3]) = ([255, 255, 255], [43, 44, 47]);
+const LIGHT_THEME: ([u8; 3], [u8; 3]) = ([34, 34, 34], [245, 242, 235]);
+const PIVOT_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+const DIFFICULT_COLOR: Color = Color::srgb(1.0, 0.65, 0.2);
+const HEADING_COLOR: Color = Color::srgb(0.55, 0.8, 1.0);
+// No italic faces are bundled, so italics (and bold, in families without a bold face) are tinted
+const EMPHASIS_COLOR: Color = Color::srgb(1.0, 0.9, 0.6);
+
+/// Spans pre-spawned under the `ReaderText` node; extra segments are folded into the last one.
+const READER_SPAN_COUNT: usize = 32;
+
+/// One colored run of the reader display. `None` color means the reader text color; `bold`
+/// runs use the family's bold face.
+#[derive(Clone, Debug, PartialEq)]
+struct Segment {
+    text: String,
+    color: Option<Color>,
+    bold: bool,
+}
+
+impl Segment {
+    fn plain(text: impl Into<String>) -> Self {
+        Self { text: text.into(), color: None, bold: false }
+    }
+
+    fn colored(text: impl Into<String>, color: Color) -> Self {
+        Self { text: text.into(), color: Some(color), bold: false }
+    }
+}
+
+/// What the reader area shows. Systems write here; `reader_render_system` syncs it to the spans.
+#[derive(Resource)]
+struct ReaderDisplay {
+    segments: Vec<Segment>,
+    alpha: f32,
+}
+
+impl Default for ReaderDisplay {
+    fn default() -> Self {
+        Self { segments: vec![Segment::plain("Ready")], alpha: 1.0 }
+    }
+}
+
+impl ReaderDisplay {
+    fn set(&mut self, segments: Vec<Segment>) {
+        self.segments = segments;
+    }
+
+    fn clear(&mut self) {
+        self.segments.clear();
+    }
+}
+
+/// Short-lived message drawn over the reader (e.g. "350 WPM" after a shortcut).
+#[derive(Resource, Default)]
+struct OverlayMessage {
+    text: String,
+    remaining: f32,
+}
+
+impl OverlayMessage {
+    fn show(&mut self, text: impl Into<String>, secs: f32) {
+        self.text = text.into();
+        self.remaining = secs;
+    }
+}
+
+#[derive(Component)]
+struct ReaderText;
+
+#[derive(Component)]
+struct ReaderSpan(usize);
+
+/// Horizontal guide line drawn under the focus word when `RsvpState::ruler` is on.
+#[derive(Component)]
+struct ReadingRuler;
+
+const RULER_THICKNESS_PX: f32 = 2.0;
+const RULER_ALPHA: f32 = 0.5;
+
+/// Tick mark above or below the focus line's center when `RsvpState::show_guides` is on.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum FocusGuide {
+    Above,
+    Below,
+}
+
+// In font sizes: how far the ticks sit from the line's center, and how long they are
+const GUIDE_GAP: f32 = 0.7;
+const GUIDE_LENGTH: f32 = 0.35;
+
+/// Dimmed line of surrounding words above (already read) or below (upcoming) the focus word.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum ContextLine {
+    Above,
+    Below,
+}
+
+const CONTEXT_FONT_SCALE: f32 = 0.4;
+const CONTEXT_ALPHA: f32 = 0.45;
+
+/// Anything carrying the reader's font: the root node and all its spans.
+type ReaderFontFilter = Or<(With<ReaderText>, With<ReaderSpan>)>;
+
+const DEFAULT_ORP_RATIO: f32 = 0.3;
+
+/// Char index of the pivot letter within `word`, skipping leading punctuation. `ratio` is
+/// how far along the letters it sits; words of five letters or fewer pivot no later than
+/// their second letter whatever the ratio.
+fn orp_index(word: &str, ratio: f32) -> usize {
+    let chars: Vec<char> = word.chars().collect();
+    let lead = chars.iter().take_while(|c| !c.is_alphanumeric()).count();
+    let core = chars[lead..].iter().take_while(|c| c.is_alphanumeric() || **c == '\'' || **c == '-').count();
+
+    let at_ratio = ((core as f32) * ratio.clamp(0.0, 0.5)).round() as usize;
+    let offset = match core {
+        0 | 1 => 0,
+        2..=5 => at_ratio.min(1),
+        _ => at_ratio,
+    };
+    (lead + offset).min(chars.len().saturating_sub(1))
+}
+
+/// Splits a word into (left, pivot, right) around its ORP.
+fn split_at_pivot(word: &str, ratio: f32) -> (String, String, String) {
+    let pivot = orp_index(word, ratio);
+    let mut left = String::new();
+    let mut mid = String::new();
+    let mut right = String::new();
+    for (i, c) in word.chars().enumerate() {
+        match i.cmp(&pivot) {
+            std::cmp::Ordering::Less => left.push(c),
+            std::cmp::Ordering::Equal => mid.push(c),
+            std::cmp::Ordering::Greater => right.push(c),
+        }
+    }
+    (left, mid, right)
+}
+
+/// 0.0 (easy) to 1.0 (hard), from letter count: 4 letters or fewer is easy, 12+ is hard.
+fn difficulty_score(word: &str) -> f32 {
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    (letters.saturating_sub(4) as f32 / 8.0).clamp(0.0, 1.0)
+}
+
+/// Tint for a word's non-pivot letters when difficulty coloring is on.
+fn difficulty_tint(rsvp: &RsvpState, word: &str) -> Option<Color> {
+    if !rsvp.difficulty_coloring {
+        return None;
+    }
+    let t = difficulty_score(word) * rsvp.difficulty_intensity;
+    if t <= 0.0 {
+        return None;
+    }
+    let from = rsvp.text_color().to_srgba();
+    let to = DIFFICULT_COLOR.to_srgba();
+    Some(Color::srgb(
+        from.red + (to.red - from.red) * t,
+        from.green + (to.green - from.green) * t,
+        from.blue + (to.blue - from.blue) * t,
+    ))
+}
+
+/// Appends a segment, merging it into the previous one when the colors match.
+fn push_segment(segments: &mut Vec<Segment>, segment: Segment) {
+    if segment.text.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some(last) if last.color == segment.color && last.bold == segment.bold => last.text.push_str(&segment.text),
+        _ => segments.push(segment),
+    }
+}
+
+/// Builds the display segments for `pages[page][start..end]`, pivot-highlighting each word
+/// (or just the middle one). Headings are drawn in their own color.
+fn chunk_segments(rsvp: &RsvpState, page: usize, start: usize, end: usize) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let words = rsvp.pages.get(page).map_or(&[][..], |p| &p[start.min(p.len())..end.min(p.len())]);
+    let center = words.len() / 2;
+
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            push_segment(&mut segments, Segment::plain(" "));
+        }
+        let style = rsvp.word_style(page, start + i);
+        let bold = rsvp.show_emphasis && style.bold && !style.heading && rsvp.bold_font_handle.is_some();
+        let tint = if style.heading {
+            Some(HEADING_COLOR)
+        } else if rsvp.show_emphasis && (style.italic || (style.bold && !bold)) {
+            Some(EMPHASIS_COLOR)
+        } else {
+            difficulty_tint(rsvp, word)
+        };
+        let body = |text: String| Segment { text, color: tint, bold };
+
+        let pivoted = rsvp.orp_highlight && (!rsvp.orp_center_word_only || i == center);
+        if pivoted {
+            let (left, mid, right) = split_at_pivot(word, rsvp.orp_ratio);
+            push_segment(&mut segments, body(left));
+            push_segment(&mut segments, Segment { bold, ..Segment::colored(mid, PIVOT_COLOR) });
+            push_segment(&mut segments, body(right));
+        } else {
+            push_segment(&mut segments, body(word.clone()));
+        }
+    }
+
+    // Lay the chunk out in its page's direction, not that of whichever word comes first
+    let base = rsvp.base_direction(page);
+    if let Some(mark) = direction_mark(&words.join(" "), &base) {
+        segments.insert(0, Segment::plain(mark.to_string()));
+    }
+    segments
+}
+
+// --- SYSTEMS ---
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut rsvp: ResMut<RsvpState>,
+    mut previews: ResMut<FontPreviews>,
+) {
+    commands.spawn(Camera2d);
+
+    // Load every bundled font up front so the selector can preview them
+    for name in AVAILABLE_FONTS {
+        previews.track(*name, asset_server.load(format!("fonts/{}", name)));
+    }
+    
+    // 1. SAFETY: Load the font immediately into a local variable
+    // (a start option may already have picked one; otherwise use the first bundled font)
+    let font_name = AVAILABLE_FONTS
+        .iter()
+        .find(|name| **name == rsvp.current_font_name)
+        .or(AVAILABLE_FONTS.first())
+        .unwrap_or(&"Arimo-Regular.ttf");
+    let font_handle = asset_server.load(font_path(font_name, rsvp.font_weight));
+    
+    // 2. Update Resource with this valid handle
+    rsvp.current_font_name = font_name.to_string();
+    rsvp.current_font_handle = font_handle.clone();
+    rsvp.bold_font_handle = bold_font_path(font_name).map(|path| asset_server.load(path));
+
+    // 3. Spawn Text with the VALID handle (never use Handle::default() for TextFont)
+    let text_font = TextFont {
+        font: font_handle, // Use the local strong handle
+        font_size: rsvp.font_size,
+        ..default()
+    };
+
+    // The root carries no text itself; ReaderDisplay segments are written into its spans
+    commands.spawn((
+        Text::new(""),
+        text_font.clone(),
+        TextColor(TEXT_COLOR),
+        // CRITICAL FIX: Use NoWrap. WordBoundary causes panics if font metrics aren't ready during extraction.
+        TextLayout::new(JustifyText::Center, LineBreak::NoWrap),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center, 
+            align_items: AlignItems::Center,         
+            ..default()
+        },
+        ReaderText,
+    )).with_children(|parent| {
+        for i in 0..READER_SPAN_COUNT {
+            parent.spawn((TextSpan::default(), text_font.clone(), TextColor(TEXT_COLOR), ReaderSpan(i)));
+        }
+    });
+
+    // Laid out by `reader_layout_system`; hidden until the ruler is switched on
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            height: Val::Px(RULER_THICKNESS_PX),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(TEXT_COLOR.with_alpha(RULER_ALPHA)),
+        ReadingRuler,
+    ));
+    for guide in [FocusGuide::Above, FocusGuide::Below] {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(50.0),
+                width: Val::Px(RULER_THICKNESS_PX),
+                margin: UiRect::left(Val::Px(-RULER_THICKNESS_PX / 2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(TEXT_COLOR.with_alpha(RULER_ALPHA)),
+            guide,
+        ));
+    }
+
+    // Peripheral context lines; hidden (empty) until the mode is switched on
+    for line in [ContextLine::Above, ContextLine::Below] {
+        commands.spawn((
+            Text::new(""),
+            TextFont { font_size: rsvp.font_size * CONTEXT_FONT_SCALE, ..text_font.clone() },
+            TextColor(TEXT_COLOR.with_alpha(CONTEXT_ALPHA)),
+            TextLayout::new(JustifyText::Center, LineBreak::NoWrap),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
             },
-            Err(e) => error!("Failed to load PDF: {:?}", e),
+            line,
+        ));
+    }
+}
+
+/// Everything the control panels read or write, bundled so each panel is one function.
+#[derive(SystemParam)]
+struct ControlsParams<'w, 's> {
+    rsvp: ResMut<'w, RsvpState>,
+    display: ResMut<'w, ReaderDisplay>,
+    fade: ResMut<'w, WordFade>,
+    goal: ResMut<'w, ReadingGoal>,
+    adaptive: ResMut<'w, AdaptiveSpeed>,
+    calibration: ResMut<'w, Calibration>,
+    definitions: ResMut<'w, Definitions>,
+    training: ResMut<'w, TrainingPlan>,
+    comprehension: ResMut<'w, ComprehensionCheck>,
+    layout: ResMut<'w, PanelLayout>,
+    doc_prefs: ResMut<'w, DocumentPrefs>,
+    gutenberg: ResMut<'w, GutenbergFetch>,
+    toasts: ResMut<'w, Toasts>,
+    previews: Res<'w, FontPreviews>,
+    asset_server: Res<'w, AssetServer>,
+    text_query: Query<'w, 's, &'static mut TextFont, ReaderFontFilter>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Playback,
+    Navigation,
+    Pacing,
+    Typography,
+}
+
+impl Panel {
+    const ALL: [Panel; 4] = [Panel::Playback, Panel::Navigation, Panel::Pacing, Panel::Typography];
+
+    fn title(self) -> &'static str {
+        match self {
+            Panel::Playback => "Playback",
+            Panel::Navigation => "Navigation",
+            Panel::Pacing => "Pacing",
+            Panel::Typography => "Typography",
+        }
+    }
+}
+
+/// Which control panels are open and whether they float or sit docked in a side panel.
+/// Persisted so the layout survives reloads.
+#[derive(Resource)]
+struct PanelLayout {
+    docked: bool,
+    open: [bool; 4],
+    minimap: bool,
+    status_bar: bool,
+    // Distraction-free reading (F): every panel and overlay hidden but a button to bring them
+    // back. Not persisted, so a reload never opens on a bare screen.
+    hidden: bool,
+}
+
+const LAYOUT_STORAGE_KEY: &str = "rsvp_panel_layout";
+const VERTICAL_POSITION_KEY: &str = "rsvp_vertical_position";
+const READING_COLUMN_KEY: &str = "rsvp_reading_column_width";
+const ORP_RATIO_KEY: &str = "rsvp_orp_ratio";
+const WORDS_PER_PAGE_KEY: &str = "rsvp_words_per_page";
+const IDLE_PAUSE_KEY: &str = "rsvp_idle_pause_minutes";
+const CJK_CHUNK_KEY: &str = "rsvp_cjk_chunk_chars";
+const TEXT_COLOR_KEY: &str = "rsvp_text_color";
+const BACKGROUND_COLOR_KEY: &str = "rsvp_background_color";
+const DEFAULT_WORDS_PER_PAGE: usize = 300;
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        let mut layout = Self { docked: true, open: [true; 4], minimap: true, status_bar: true, hidden: false };
+        // Format: "docked|playback|navigation|pacing|typography|minimap|status" as 0/1 flags
+        // (layouts saved before the minimap or status bar existed lack the final flags)
+        if let Some(saved) = storage::load(LAYOUT_STORAGE_KEY) {
+            let flags: Vec<bool> = saved.split('|').map(|f| f == "1").collect();
+            if flags.len() >= 5 {
+                layout.docked = flags[0];
+                layout.open.copy_from_slice(&flags[1..5]);
+                layout.minimap = flags.get(5).copied().unwrap_or(true);
+                layout.status_bar = flags.get(6).copied().unwrap_or(true);
+            }
+        }
+        layout
+    }
+}
+
+impl PanelLayout {
+    fn is_open(&self, panel: Panel) -> bool {
+        self.open[panel as usize]
+    }
+
+    fn save(&self) {
+        let flag = |b: bool| if b { "1" } else { "0" };
+        let flags: Vec<&str> = std::iter::once(self.docked)
+            .chain(self.open)
+            .chain([self.minimap, self.status_bar])
+            .map(flag)
+            .collect();
+        storage::save(LAYOUT_STORAGE_KEY, &flags.join("|"));
+    }
+}
+
+// Colors are stored as "r,g,b"
+fn load_rgb(key: &str) -> Option<[u8; 3]> {
+    let saved = storage::load(key)?;
+    let mut parts = saved.split(',').map(|c| c.trim().parse::<u8>());
+    let rgb = [parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?];
+    parts.next().is_none().then_some(rgb)
+}
+
+fn save_rgb(key: &str, [r, g, b]: [u8; 3]) {
+    storage::save(key, &format!("{},{},{}", r, g, b));
+}
+
+fn ui_controls_system(mut contexts: EguiContexts, mut p: ControlsParams) {
+    let ctx = contexts.ctx_mut();
+    let lang = p.rsvp.lang;
+
+    if p.layout.hidden {
+        egui::Area::new(egui::Id::new("panel_menu"))
+            .anchor(egui::Align2::LEFT_TOP, [10.0, 10.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_opacity(0.4);
+                if ui.small_button(tr(lang, "Show controls (F)")).clicked() {
+                    p.layout.hidden = false;
+                }
+            });
+        return;
+    }
+
+    // Panel picker: always reachable, even with every panel closed or focus mode's veil up
+    egui::Area::new(egui::Id::new("panel_menu"))
+        .anchor(egui::Align2::LEFT_TOP, [10.0, 10.0])
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            ui.menu_button(tr(lang, "Panels"), |ui| {
+                if ui.button(tr(lang, "Hide controls (F)")).clicked() {
+                    p.layout.hidden = true;
+                    ui.close_menu();
+                }
+                ui.separator();
+                let mut changed = ui.checkbox(&mut p.layout.docked, tr(lang, "Dock in side panel")).changed();
+                ui.separator();
+                for panel in Panel::ALL {
+                    changed |= ui.checkbox(&mut p.layout.open[panel as usize], tr(lang, panel.title())).changed();
+                }
+                changed |= ui.checkbox(&mut p.layout.minimap, tr(lang, "Minimap")).changed();
+                changed |= ui.checkbox(&mut p.layout.status_bar, tr(lang, "Status bar")).changed();
+                if changed {
+                    p.layout.save();
+                }
+
+                ui.separator();
+                if ui.checkbox(&mut p.doc_prefs.use_global, tr(lang, "Use global settings for all documents")).changed() {
+                    p.doc_prefs.save_use_global();
+                }
+
+                ui.separator();
+                ui.label(tr(lang, "Language"));
+                for option in Lang::ALL {
+                    ui.radio_value(&mut p.rsvp.lang, option, option.native_name());
+                }
+            });
+        });
+
+    // Before the side panels, so the bar runs the full width of the window
+    if p.layout.status_bar {
+        status_bar(ctx, &p.rsvp);
+    }
+    if p.layout.minimap {
+        minimap(ctx, &mut p);
+    }
+    if p.rsvp.page_overlay {
+        page_readout(ctx, &p.rsvp);
+    }
+
+    if p.layout.docked {
+        egui::SidePanel::right("reader_controls")
+            .default_width(230.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for panel in Panel::ALL {
+                        if p.layout.is_open(panel) {
+                            egui::CollapsingHeader::new(tr(lang, panel.title()))
+                                .default_open(true)
+                                .show(ui, |ui| panel_contents(ui, panel, &mut p));
+                        }
+                    }
+                });
+            });
+    } else {
+        for (i, panel) in Panel::ALL.into_iter().enumerate() {
+            let mut open = p.layout.is_open(panel);
+            if !open {
+                continue;
+            }
+            egui::Window::new(tr(lang, panel.title()))
+                .id(egui::Id::new(panel.title()))
+                .open(&mut open)
+                .default_pos([ctx.screen_rect().right() - 250.0, 10.0 + 40.0 * i as f32])
+                .default_width(220.0)
+                .show(ctx, |ui| panel_contents(ui, panel, &mut p));
+            if !open {
+                p.layout.open[panel as usize] = false;
+                p.layout.save();
+            }
+        }
+    }
+}
+
+/// Search field with a match count and previous/next buttons. Enter in the field goes to the
+/// next match. Next and previous count from the current position rather than the last match,
+/// so after reading on from one, Next finds the one ahead.
+fn search_box(ui: &mut egui::Ui, rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) {
+    let lang = rsvp.lang;
+    let mut step = None;
+    ui.horizontal(|ui| {
+        let field = ui.add(
+            egui::TextEdit::singleline(&mut rsvp.search_query)
+                .hint_text(tr(lang, "Search"))
+                .desired_width(110.0),
+        );
+        if field.changed() {
+            rsvp.refresh_search();
+        }
+        if field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            step = Some(1);
+            field.request_focus();
+        }
+        let found = !rsvp.search_matches.is_empty();
+        if ui.add_enabled(found, egui::Button::new("◀")).on_hover_text(tr(lang, "Previous match")).clicked() {
+            step = Some(-1);
+        }
+        if ui.add_enabled(found, egui::Button::new("▶")).on_hover_text(tr(lang, "Next match")).clicked() {
+            step = Some(1);
+        }
+        match (rsvp.search_cursor, rsvp.search_matches.len()) {
+            _ if rsvp.search_query.trim().is_empty() => {}
+            (_, 0) => {
+                ui.label(tr(lang, "No matches"));
+            }
+            (Some(i), n) => {
+                ui.label(format!("{} / {}", i + 1, n));
+            }
+            (None, n) => {
+                ui.label(n.to_string());
+            }
+        }
+    });
+
+    let Some(step) = step.filter(|_| !rsvp.search_matches.is_empty()) else { return };
+    let (page, word) = current_position(rsvp);
+    let here = rsvp.flat_index(page, word);
+    let flats: Vec<usize> = rsvp.search_matches.iter().map(|&(page, word)| rsvp.flat_index(page, word)).collect();
+    // Wrapping around at either end of the document
+    let i = if step > 0 {
+        flats.iter().position(|&flat| flat > here).unwrap_or(0)
+    } else {
+        flats.iter().rposition(|&flat| flat < here).unwrap_or(flats.len() - 1)
+    };
+    let (page, word) = rsvp.search_matches[i];
+    jump_to(rsvp, display, fade, page, word);
+    rsvp.search_cursor = Some(i);
+}
+
+/// Editable list of the document's bookmarks; changes are saved when the document has a key.
+fn bookmark_list(
+    ui: &mut egui::Ui,
+    rsvp: &mut RsvpState,
+    display: &mut ReaderDisplay,
+    fade: &mut WordFade,
+    toasts: &mut Toasts,
+    doc_key: Option<&str>,
+) {
+    let lang = rsvp.lang;
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Bookmarks"));
+        if ui.button(tr(lang, "Add")).clicked() {
+            let (page, word) = current_position(rsvp);
+            let label = rsvp.pages.get(page).and_then(|p| p.get(word)).cloned().unwrap_or_default();
+            toasts.success(format!("{}: {}", tr(lang, "Bookmark added"), label));
+            rsvp.bookmarks.push(Bookmark { page, word, label });
+            changed = true;
+        }
+        // Files are tagged with the document key, so both need an uploaded document
+        if let Some(key) = doc_key {
+            if ui.button(tr(lang, "Export")).clicked() {
+                bookmarks::export(key, &rsvp.bookmarks);
+                toasts.info(tr(lang, "Bookmarks exported"));
+            }
+            if ui.button(tr(lang, "Import")).clicked() {
+                bookmarks::pick_import_file();
+            }
+        }
+    });
+
+    let mut remove = None;
+    let mut jump = None;
+    for (i, bookmark) in rsvp.bookmarks.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.button(format!("{}:{}", bookmark.page + 1, bookmark.word + 1)).clicked() {
+                jump = Some((bookmark.page, bookmark.word));
+            }
+            changed |= ui.add(egui::TextEdit::singleline(&mut bookmark.label).desired_width(110.0)).lost_focus();
+            if ui.small_button("x").on_hover_text(tr(lang, "Delete")).clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = remove {
+        rsvp.bookmarks.remove(i);
+        changed = true;
+    }
+    if let Some((page, word)) = jump {
+        jump_to(rsvp, display, fade, page, word);
+    }
+    if let (true, Some(key)) = (changed, doc_key) {
+        bookmarks::save(key, &rsvp.bookmarks);
+    }
+}
+
+const CONTENTS_ROW_HEIGHT: f32 = 18.0;
+
+/// The document's table of contents, or one entry per page when it has none, with the
+/// section being read highlighted. Clicking an entry jumps to it.
+fn contents_list(ui: &mut egui::Ui, rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) {
+    let lang = rsvp.lang;
+    if rsvp.pages.is_empty() {
+        return;
+    }
+    let (page, word) = current_position(rsvp);
+    let here = rsvp.flat_index(page, word);
+    let rows = if rsvp.toc.is_empty() { rsvp.pages.len() } else { rsvp.toc.len() };
+    let current = if rsvp.toc.is_empty() {
+        page
+    } else {
+        rsvp.toc.iter().rposition(|e| rsvp.flat_index(e.page, e.word) <= here).unwrap_or(0)
+    };
+
+    let mut jump = None;
+    egui::CollapsingHeader::new(tr(lang, "Contents")).show(ui, |ui| {
+        // Only the visible rows are laid out, so a thousand-page fallback stays cheap
+        egui::ScrollArea::vertical().max_height(200.0).show_rows(ui, CONTENTS_ROW_HEIGHT, rows, |ui, range| {
+            for i in range {
+                let (label, target, indent) = match rsvp.toc.get(i) {
+                    Some(entry) => (entry.title.clone(), (entry.page, entry.word), entry.level),
+                    None => (format!("{} {}", tr(lang, "Page"), i + 1), (i, 0), 0),
+                };
+                ui.horizontal(|ui| {
+                    ui.add_space(12.0 * indent as f32);
+                    if ui.selectable_label(i == current, label).clicked() {
+                        jump = Some(target);
+                    }
+                });
+            }
+        });
+    });
+    if let Some((page, word)) = jump {
+        jump_to_page(rsvp, display, fade, page, word);
+    }
+}
+
+const MINIMAP_WIDTH: f32 = 14.0;
+
+/// Thin strip down the left edge standing for the whole document: ticks at page starts,
+/// a marker at the current word, and click/drag to seek.
+fn minimap(ctx: &egui::Context, p: &mut ControlsParams) {
+    let rsvp = &mut *p.rsvp;
+    if rsvp.total_words == 0 {
+        return;
+    }
+
+    egui::SidePanel::left("minimap")
+        .exact_width(MINIMAP_WIDTH)
+        .resizable(false)
+        .show_separator_line(false)
+        .frame(egui::Frame::none().fill(egui::Color32::from_black_alpha(120)))
+        .show(ctx, |ui| {
+            let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+            let painter = ui.painter_at(rect);
+            let total = rsvp.total_words as f32;
+            let y_at = |flat: usize| rect.top() + rect.height() * flat as f32 / total;
+
+            let tick = egui::Stroke::new(1.0, egui::Color32::from_gray(110));
+            for &offset in rsvp.page_offsets.iter().skip(1) {
+                let y = y_at(offset);
+                painter.line_segment([egui::pos2(rect.left() + 3.0, y), egui::pos2(rect.right() - 3.0, y)], tick);
+            }
+
+            let (page, word) = current_position(rsvp);
+            let marker = egui::Rect::from_center_size(
+                egui::pos2(rect.center().x, y_at(rsvp.flat_index(page, word))),
+                egui::vec2(rect.width(), 4.0),
+            );
+            painter.rect_filled(marker, 1.0, egui::Color32::from_rgb(255, 51, 51));
+
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let fraction = ((pointer.y - rect.top()) / rect.height()).clamp(0.0, 1.0);
+                let (page, word) = rsvp.position_from_flat((fraction * total) as usize);
+                // Only the press starts a jump; the rest of a drag just scrubs
+                if ui.input(|i| i.pointer.any_pressed()) {
+                    rsvp.remember_position();
+                }
+                seek_to(rsvp, &mut p.display, &mut p.fade, page, word);
+            }
+        });
+}
+
+/// One thin line along the bottom: play state, page and word counts, speed and time left.
+/// Everything comes from the cached totals and offsets, so it costs nothing to draw.
+fn status_bar(ctx: &egui::Context, rsvp: &RsvpState) {
+    let lang = rsvp.lang;
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word);
+    let words_left = rsvp.total_words.saturating_sub(flat);
+    let minutes_left = (words_left as f32 / rsvp.effective_wpm.max(MIN_WPM)).ceil() as u32;
+    let time_left = match minutes_left {
+        0 => tr(lang, "done").to_string(),
+        m if m < 60 => format!("{} {}", m, tr(lang, "min left")),
+        m => format!("{} h {:02} {}", m / 60, m % 60, tr(lang, "min left")),
+    };
+
+    egui::TopBottomPanel::bottom("status_bar")
+        .exact_height(18.0)
+        .show_separator_line(false)
+        .frame(egui::Frame::none().fill(egui::Color32::from_black_alpha(120)).inner_margin(egui::Margin::symmetric(8.0, 2.0)))
+        .show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                let text = |s: String| egui::RichText::new(s).small().color(egui::Color32::from_gray(170));
+                let state = if rsvp.is_playing { "▶" } else { "⏸" };
+                ui.label(text(state.to_string()));
+                if rsvp.total_words > 0 {
+                    ui.label(text(format!("{} {} / {}", tr(lang, "Page"), page + 1, rsvp.pages.len())));
+                    ui.label(text(format!("{} {} / {}", tr(lang, "Word"), flat + 1, rsvp.total_words)));
+                }
+                ui.label(text(format!("{:.0} WPM", rsvp.effective_wpm)));
+                if rsvp.total_words > 0 {
+                    ui.label(text(time_left));
+                }
+            });
+        });
+}
+
+/// Height taken from the bottom of the window by panels shown so far this frame (the
+/// status bar), for overlays anchored to the bottom edge to sit above.
+fn bottom_inset(ctx: &egui::Context) -> f32 {
+    (ctx.screen_rect().bottom() - ctx.available_rect().bottom()).max(0.0)
+}
+
+/// Always-visible "Page N · Word M" in the bottom-left corner, with N the PDF's own page
+/// number when the document kept one.
+fn page_readout(ctx: &egui::Context, rsvp: &RsvpState) {
+    if rsvp.total_words == 0 {
+        return;
+    }
+    let lang = rsvp.lang;
+    let (page, word) = current_position(rsvp);
+    let page_number = rsvp.source_page(page).map_or(page + 1, |n| n as usize);
+
+    egui::Area::new(egui::Id::new("page_readout"))
+        .anchor(egui::Align2::LEFT_BOTTOM, [MINIMAP_WIDTH + 10.0, -10.0 - bottom_inset(ctx)])
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!("{} {} · {} {}", tr(lang, "Page"), page_number, tr(lang, "Word"), word + 1))
+                    .small()
+                    .color(egui::Color32::from_gray(160)),
+            );
+        });
+}
+
+fn panel_contents(ui: &mut egui::Ui, panel: Panel, p: &mut ControlsParams) {
+    match panel {
+        Panel::Playback => playback_panel(ui, p),
+        Panel::Navigation => navigation_panel(ui, p),
+        Panel::Pacing => pacing_panel(ui, p),
+        Panel::Typography => typography_panel(ui, p),
+    }
+}
+
+fn playback_panel(ui: &mut egui::Ui, p: &mut ControlsParams) {
+    let rsvp = &mut *p.rsvp;
+    let lang = rsvp.lang;
+
+    if rsvp.finished {
+        ui.horizontal(|ui| {
+            ui.colored_label(egui::Color32::from_rgb(255, 51, 51), tr(lang, "Finished"));
+            if ui.button(tr(lang, "Read again")).clicked() {
+                jump_to(rsvp, &mut p.display, &mut p.fade, 0, 0);
+                rsvp.is_playing = true;
+            }
+        });
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button(tr(lang, "Restart")).on_hover_text("Home").clicked() {
+            jump_to(rsvp, &mut p.display, &mut p.fade, 0, 0);
+        }
+        if ui.button("« 5").clicked() {
+            step_words(rsvp, &mut p.display, &mut p.fade, -5);
+        }
+        if ui.button(tr(lang, if rsvp.is_playing { "Pause" } else { "Play" })).clicked() { 
+            rsvp.is_playing = !rsvp.is_playing; 
+        }
+        if ui.button("5 »").clicked() {
+            step_words(rsvp, &mut p.display, &mut p.fade, 5);
+        }
+        if ui.button(tr(lang, "End")).on_hover_text("End").clicked() {
+            jump_to_end(rsvp, &mut p.display, &mut p.fade);
+        }
+    });
+
+    ui.separator();
+
+    if p.adaptive.enabled {
+        ui.label(format!("{}: {:.0} WPM ({})", tr(lang, "Speed"), rsvp.wpm, tr(lang, "adaptive")));
+    } else {
+        ui.label(format!("{}: {:.0} WPM", tr(lang, "Speed"), rsvp.wpm));
+    }
+    // egui maps a logarithmic slider's travel exponentially but keeps the value in real WPM
+    let log_scale = rsvp.log_wpm_slider;
+    ui.horizontal(|ui| {
+        ui.add(egui::Slider::new(&mut rsvp.wpm, MIN_WPM..=MAX_WPM).logarithmic(log_scale));
+        let undo = ui.add_enabled(!rsvp.wpm_history.is_empty() || rsvp.wpm != rsvp.settled_wpm, egui::Button::new("↶"));
+        if undo.on_hover_text(tr(lang, "Undo speed (Z)")).clicked() {
+            rsvp.undo_wpm();
+        }
+    });
+    ui.horizontal(|ui| {
+        for (i, wpm) in WPM_PRESETS.into_iter().enumerate() {
+            let preset = ui.selectable_label(rsvp.wpm == wpm, format!("{:.0}", wpm));
+            if preset.on_hover_text(format!("{} ({})", tr(lang, "Set speed"), i + 1)).clicked() {
+                rsvp.wpm = wpm;
+            }
+        }
+    });
+    ui.checkbox(&mut rsvp.log_wpm_slider, tr(lang, "Logarithmic speed slider"));
+    ui.checkbox(&mut rsvp.smooth_wpm, tr(lang, "Ease into speed changes"));
+    ui.checkbox(&mut rsvp.suggest_wpm_on_open, tr(lang, "Suggest speed on open"));
+    adaptive_controls(ui, &mut p.adaptive, lang);
+    if ui.checkbox(&mut rsvp.ramp_enabled, tr(lang, "Ramp up speed while reading")).changed() && rsvp.ramp_enabled {
+        rsvp.wpm = rsvp.ramp_start_wpm.min(rsvp.ramp_target_wpm);
+    }
+    if rsvp.ramp_enabled {
+        // Moving the start restarts the ramp from there
+        if ui.add(egui::Slider::new(&mut rsvp.ramp_start_wpm, MIN_WPM..=MAX_WPM).text(tr(lang, "Start"))).changed() {
+            rsvp.wpm = rsvp.ramp_start_wpm.min(rsvp.ramp_target_wpm);
+        }
+        ui.add(egui::Slider::new(&mut rsvp.ramp_target_wpm, MIN_WPM..=MAX_WPM).text(tr(lang, "Target")));
+        ui.add(egui::Slider::new(&mut rsvp.ramp_rate_wpm_per_min, 1.0..=100.0).text(tr(lang, "WPM per minute")));
+    }
+    let calibrate = ui.add_enabled(!p.calibration.is_active(), egui::Button::new(tr(lang, "Calibrate speed…")));
+    if calibrate.on_hover_text(tr(lang, "Replaces the open document with a short test passage")).clicked() {
+        p.calibration.start();
+    }
+
+    let mut manual = rsvp.advance_mode == AdvanceMode::Manual;
+    if ui.checkbox(&mut manual, tr(lang, "Manual advance (Space / →)")).changed() {
+        rsvp.advance_mode = if manual { AdvanceMode::Manual } else { AdvanceMode::Timed };
+        // Whatever was pending on the timer restarts from zero when timing resumes
+        rsvp.timer.reset();
+        rsvp.in_sentence_gap = false;
+        rsvp.in_page_transition = false;
+    }
+    ui.checkbox(&mut rsvp.show_countdown, tr(lang, "Count down before playing"));
+    ui.checkbox(&mut rsvp.reverse, tr(lang, "Play backwards"))
+        .on_hover_text(tr(lang, "Steps back a word at a time, for proofreading"));
+    ui.add(egui::Slider::new(&mut rsvp.rewind_on_resume, 0..=20).text(tr(lang, "Words to repeat on resume")))
+        .on_hover_text(tr(lang, "0 turns it off"));
+    ui.checkbox(&mut p.definitions.enabled, tr(lang, "Define words while paused"));
+    let idle = egui::Slider::new(&mut rsvp.idle_pause_minutes, 0..=30).text(tr(lang, "Auto-pause when idle (min)"));
+    if ui.add(idle).on_hover_text(tr(lang, "0 turns it off")).changed() {
+        storage::save(IDLE_PAUSE_KEY, &rsvp.idle_pause_minutes.to_string());
+    }
+    comprehension_controls(ui, &mut p.comprehension, lang);
+    stats_controls(ui, &mut rsvp.stats, lang);
+
+    ui.separator();
+
+    let goal = &mut *p.goal;
+    ui.horizontal(|ui| {
+        goal_ring(ui, goal.fraction());
+        ui.vertical(|ui| {
+            ui.label(format!("{}: {} / {} {}", tr(lang, "Today"), goal.words_today, goal.daily_word_goal, tr(lang, "words")));
+            let slider = egui::Slider::new(&mut goal.daily_word_goal, 500..=50_000).text(tr(lang, "Goal"));
+            if ui.add(slider).changed() {
+                goal.mark_dirty();
+            }
+        });
+    });
+    training_controls(ui, &mut p.training, rsvp);
+}
+
+/// Share of the current page read, 0.0 to 1.0; a page with one word (or none) divides by 1.
+fn page_progress(rsvp: &RsvpState) -> f32 {
+    let page_len = rsvp.pages.get(rsvp.current_page_index).map_or(0, |p| p.len());
+    (rsvp.current_word_index as f32 / page_len.max(1) as f32).min(1.0)
+}
+
+/// Share of the document read, 0.0 to 1.0, and the words still to come. From the cached
+/// offsets and total, like the status bar, so it's cheap every frame.
+fn document_progress(rsvp: &RsvpState) -> (f32, usize) {
+    if rsvp.finished {
+        return (1.0, 0);
+    }
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word).min(rsvp.total_words);
+    (flat as f32 / rsvp.total_words.max(1) as f32, rsvp.total_words - flat)
+}
+
+fn navigation_panel(ui: &mut egui::Ui, p: &mut ControlsParams) {
+    let rsvp = &mut *p.rsvp;
+    let lang = rsvp.lang;
+
+    // A one-page document has nothing to slide between
+    let total_pages = rsvp.pages.len().max(1);
+    if total_pages > 1 {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", tr(lang, "Page")));
+            let mut page_display = rsvp.current_page_index + 1;
+
+            let slider = ui.add(egui::Slider::new(&mut page_display, 1..=total_pages));
+            if slider.drag_started() || (slider.changed() && !slider.dragged()) {
+                rsvp.remember_position();
+            }
+            if slider.changed() {
+                let page = page_display - 1;
+                if rsvp.pages[page].is_empty() {
+                    // Not extracted yet (lazy PDFs); the tick shows it once it has words
+                    rsvp.current_page_index = page;
+                    rsvp.current_word_index = 0;
+                } else {
+                    // Shows the page's first chunk right away, paused or not
+                    seek_to(rsvp, &mut p.display, &mut p.fade, page, 0);
+                }
+            }
+            ui.label(format!("/ {}", total_pages));
+        });
+    }
+    if let Some(source) = rsvp.source_page(rsvp.current_page_index) {
+        ui.label(format!("{} {}", tr(lang, "PDF page"), source));
+    }
+    ui.checkbox(&mut rsvp.page_overlay, tr(lang, "Show position in corner"));
+
+    let page_len = rsvp.pages.get(rsvp.current_page_index).map_or(0, |p| p.len());
+    if page_len > 1 {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", tr(lang, "Word")));
+            let (page, mut word) = current_position(rsvp);
+            let slider = ui.add(egui::Slider::new(&mut word, 0..=page_len - 1).show_value(false));
+            // Typed or scrubbed exactly, counting from 1 like the page number
+            let mut number = word.min(page_len - 1) + 1;
+            let field = ui.add(egui::DragValue::new(&mut number).range(1..=page_len));
+            ui.label(format!("/ {}", page_len));
+            if field.changed() {
+                word = number - 1;
+            }
+            let moved = slider.changed() || field.changed();
+            if slider.drag_started() || field.drag_started() || (moved && !slider.dragged() && !field.dragged()) {
+                rsvp.remember_position();
+            }
+            if moved {
+                seek_to(rsvp, &mut p.display, &mut p.fade, page, word);
+            }
+        });
+    }
+    
+    ui.label(format!("{}: {} · {}: {}", tr(lang, "Words on page"), page_len, tr(lang, "Total words"), rsvp.total_words));
+    // The whole document at the slider's speed, whatever has been read so far
+    let secs = (estimate_minutes(rsvp.total_words, rsvp.wpm) * 60.0).round() as u32;
+    ui.label(format!(
+        "{} {:.0} WPM: {}:{:02}:{:02}",
+        tr(lang, "Reading time at"),
+        rsvp.wpm,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    ));
+
+    let progress = page_progress(rsvp);
+    let progress_text = match rsvp.loop_passes {
+        0 => tr(lang, "Page Progress").to_string(),
+        passes => format!("{} · {} {}", tr(lang, "Page Progress"), tr(lang, "pass"), passes + 1),
+    };
+    ui.add(egui::ProgressBar::new(progress).text(progress_text));
+
+    let (document_progress, words_left) = document_progress(rsvp);
+    let secs_left = (words_left as f32 * 60.0 / rsvp.effective_wpm.max(MIN_WPM)).round() as u32;
+    ui.add(egui::ProgressBar::new(document_progress).text(format!(
+        "{} {:.0}% · {}:{:02} {}",
+        tr(lang, "Document"),
+        document_progress * 100.0,
+        secs_left / 60,
+        secs_left % 60,
+        tr(lang, "left")
+    )));
+
+    ui.separator();
+
+    let back = ui.add_enabled(!rsvp.nav_history.is_empty(), egui::Button::new(tr(lang, "Back")));
+    if back.on_hover_text("Backspace").clicked() {
+        go_back(rsvp, &mut p.display, &mut p.fade);
+    }
+
+    search_box(ui, rsvp, &mut p.display, &mut p.fade);
+    bookmark_list(ui, rsvp, &mut p.display, &mut p.fade, &mut p.toasts, p.doc_prefs.key());
+    contents_list(ui, rsvp, &mut p.display, &mut p.fade);
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("subtitle_format")
+            .selected_text(rsvp.subtitle_format.label())
+            .show_ui(ui, |ui| {
+                for format in SubtitleFormat::ALL {
+                    ui.selectable_value(&mut rsvp.subtitle_format, format, format.label());
+                }
+            });
+        ui.checkbox(&mut rsvp.subtitle_sentences, tr(lang, "By sentence"));
+        let export = ui.add_enabled(rsvp.document_loaded, egui::Button::new(tr(lang, "Export subtitles")));
+        if export.on_hover_text(tr(lang, "Times the document at the current speed and settings")).clicked() {
+            let format = rsvp.subtitle_format;
+            download::offer(format.file_name(), format.mime(), &export_subtitles(rsvp, format, rsvp.subtitle_sentences));
+            p.toasts.info(tr(lang, "Subtitles exported"));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Gutenberg ID"));
+        let fetch = &mut *p.gutenberg;
+        ui.add(egui::TextEdit::singleline(&mut fetch.id).desired_width(60.0));
+        if fetch.loading {
+            ui.spinner();
+        } else if ui.button(tr(lang, "Load")).clicked() {
+            if let Err(e) = fetch.start() {
+                p.toasts.error(tr(lang, e));
+            }
+        }
+    });
+    ui.checkbox(&mut rsvp.trim_gutenberg_boilerplate, tr(lang, "Trim Gutenberg license text"));
+    ui.add(
+        egui::TextEdit::multiline(&mut rsvp.paste_text)
+            .hint_text(tr(lang, "Paste or type text to read"))
+            .desired_rows(3)
+            .desired_width(f32::INFINITY),
+    );
+    let has_text = !rsvp.paste_text.trim().is_empty();
+    if ui.add_enabled(has_text, egui::Button::new(tr(lang, "Load text"))).clicked() {
+        pass_text_to_bevy(&std::mem::take(&mut rsvp.paste_text));
+    }
+    egui::ComboBox::from_id_salt("pdf_load_mode")
+        .selected_text(tr(lang, rsvp.pdf_load_mode.label()))
+        .show_ui(ui, |ui| {
+            for mode in PdfLoadMode::ALL {
+                ui.selectable_value(&mut rsvp.pdf_load_mode, mode, tr(lang, mode.label()));
+            }
+        });
+    ui.checkbox(&mut rsvp.clean_extracted_text, tr(lang, "Skip PDF page numbers and headers"))
+        .on_hover_text(tr(lang, "Applies to PDFs opened afterwards; turn off if numbers on their own line matter"));
+
+    let detected = rsvp.detected_language.map_or(tr(lang, "unknown"), |l| tr(lang, l.name()));
+    let auto = format!("{} ({})", tr(lang, "Auto"), detected);
+    let mut choice = rsvp.language_override;
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Document language"))
+            .on_hover_text(tr(lang, "Sets text direction and font; CJK character chunking applies to documents opened afterwards"));
+        egui::ComboBox::from_id_salt("document_language")
+            .selected_text(choice.map_or(auto.clone(), |l| tr(lang, l.name()).to_string()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut choice, None, auto);
+                for language in DocLanguage::ALL {
+                    ui.selectable_value(&mut choice, Some(language), tr(lang, language.name()));
+                }
+            });
+    });
+    if choice != rsvp.language_override {
+        rsvp.language_override = choice;
+        language::save_override(choice);
+        apply_language_font(rsvp, &p.asset_server, &mut p.text_query, &mut p.toasts);
+        rechunk_in_place(rsvp, &mut p.display, &mut p.fade);
+    }
+    if rsvp.document_language().is_some_and(DocLanguage::uses_char_chunks) {
+        let slider = egui::Slider::new(&mut rsvp.cjk_chunk_chars, 1..=6).text(tr(lang, "Characters per chunk"));
+        if ui.add(slider).on_hover_text(tr(lang, "Applies to documents opened afterwards")).changed() {
+            storage::save(CJK_CHUNK_KEY, &rsvp.cjk_chunk_chars.to_string());
+        }
+    }
+
+    egui::CollapsingHeader::new(tr(lang, "Advanced"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(tr(lang, "Words per page (text)"));
+            let slider = ui.add(egui::Slider::new(&mut rsvp.words_per_page, 50..=2000).logarithmic(true));
+            // Recut once the drag settles rather than on every intermediate value
+            if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                storage::save(WORDS_PER_PAGE_KEY, &rsvp.words_per_page.to_string());
+                repaginate_in_place(rsvp, &mut p.display, &mut p.fade);
+                if let Some(key) = p.doc_prefs.key() {
+                    bookmarks::save(key, &rsvp.bookmarks);
+                }
+            }
+        });
+}
+
+fn pacing_panel(ui: &mut egui::Ui, p: &mut ControlsParams) {
+    let rsvp = &mut *p.rsvp;
+    let lang = rsvp.lang;
+
+    let mut rechunk = false;
+    ui.horizontal(|ui| {
+        for mode in ChunkMode::ALL {
+            rechunk |= ui.radio_value(&mut rsvp.chunk_mode, mode, tr(lang, mode.label())).changed();
+        }
+    });
+    if rsvp.chunk_mode == ChunkMode::Words {
+        ui.label(format!("{}: {}", tr(lang, "Words Per Frame"), rsvp.words_per_frame));
+        rechunk |= ui.add(egui::Slider::new(&mut rsvp.words_per_frame, 1..=MAX_WORDS_PER_FRAME)).changed();
+        rechunk |= ui.checkbox(&mut rsvp.smart_chunking, tr(lang, "Keep sentences apart")).changed();
+        rechunk |= ui.checkbox(&mut rsvp.merge_tiny_words, tr(lang, "Join tiny words to the next")).changed();
+        if rsvp.merge_tiny_words {
+            rechunk |= ui.add(egui::Slider::new(&mut rsvp.merge_max_len, 1..=3).text(tr(lang, "Letters"))).changed();
+        }
+    }
+    if rechunk {
+        rechunk_in_place(rsvp, &mut p.display, &mut p.fade);
+    }
+
+    ui.separator();
+
+    ui.label(tr(lang, "Timing Model"));
+    egui::ComboBox::from_id_salt("timing_model")
+        .selected_text(tr(lang, rsvp.timing_model.label()))
+        .show_ui(ui, |ui| {
+            for model in TimingModel::ALL {
+                ui.selectable_value(&mut rsvp.timing_model, model, tr(lang, model.label()));
+            }
+        });
+
+    ui.label(tr(lang, "Pauses"));
+    ui.add(egui::Slider::new(&mut rsvp.sentence_pause_multiplier, 1.0..=4.0).text(". ? ! ; :"));
+    ui.add(egui::Slider::new(&mut rsvp.clause_pause_multiplier, 1.0..=4.0).text(","));
+    ui.add(egui::Slider::new(&mut rsvp.dash_pause_multiplier, 1.0..=4.0).text("—"));
+
+    ui.add(egui::Slider::new(&mut rsvp.heading_pause_multiplier, 1.0..=4.0).text(tr(lang, "Headings")));
+
+    ui.checkbox(&mut rsvp.short_word_speedup, tr(lang, "Speed through short words"));
+    if rsvp.short_word_speedup {
+        ui.add(egui::Slider::new(&mut rsvp.short_word_len, 1..=4).text(tr(lang, "Letters")));
+        ui.add(egui::Slider::new(&mut rsvp.short_word_factor, 0.3..=1.0).text("x"));
+    }
+
+    let english = rsvp.document_language().is_none_or(|l| l == DocLanguage::English);
+    ui.add_enabled(english, egui::Checkbox::new(&mut rsvp.frequency_timing, tr(lang, "Pace by word frequency")))
+        .on_hover_text(tr(lang, "Common words go by faster and rare ones slower (English only)"));
+    if rsvp.frequency_timing && english {
+        ui.add(egui::Slider::new(&mut rsvp.frequency_intensity, 0.0..=1.0).text(tr(lang, "Intensity")));
+    }
+
+    ui.checkbox(&mut rsvp.proper_noun_slowdown, tr(lang, "Slow down on names & acronyms"));
+    if rsvp.proper_noun_slowdown {
+        ui.add(egui::Slider::new(&mut rsvp.proper_noun_multiplier, 1.0..=2.5).text("x"));
+    }
+
+    ui.checkbox(&mut rsvp.numeric_slowdown, tr(lang, "Slow down on numbers"));
+    if rsvp.numeric_slowdown {
+        ui.add(egui::Slider::new(&mut rsvp.numeric_multiplier, 1.0..=3.0).text("x"));
+    }
+
+    let math = ui.checkbox(&mut rsvp.math_cleanup, tr(lang, "Clean up math & formulas"));
+    math.on_hover_text(tr(lang, "Rejoins split formulas like H₂O and x² and pauses on math symbols. Applies to documents opened afterwards"));
+
+    ui.label(tr(lang, "Extra pause on long words (ms)"));
+    ui.add(egui::Slider::new(&mut rsvp.long_word_extra_ms, 0.0..=1000.0));
+    if rsvp.long_word_extra_ms > 0.0 {
+        ui.add(egui::Slider::new(&mut rsvp.long_word_len_threshold, 6..=20).text(tr(lang, "Letters")));
+    }
+
+    ui.separator();
+
+    ui.label(tr(lang, "Min Word Time (ms)"));
+    ui.add(egui::Slider::new(&mut rsvp.min_word_ms, 0.0..=300.0));
+    // Every word gets at least the floor, so it sets a top speed of its own
+    let floor_wpm = 60_000.0 / rsvp.min_word_ms.max(1.0);
+    if rsvp.min_word_ms > 0.0 && rsvp.effective_wpm > floor_wpm {
+        ui.label(
+            egui::RichText::new(format!("{} {:.0} WPM", tr(lang, "Minimum word time caps the speed at"), floor_wpm))
+                .small()
+                .color(egui::Color32::from_gray(170)),
+        );
+    }
+    ui.label(tr(lang, "Max Word Time (ms)"));
+    ui.add(egui::Slider::new(&mut rsvp.max_word_ms, 300.0..=5000.0));
+    
+    ui.separator();
+
+    ui.checkbox(&mut rsvp.sentence_gap, tr(lang, "Blank gap between sentences"));
+    if rsvp.sentence_gap {
+        ui.add(egui::Slider::new(&mut rsvp.sentence_gap_ms, 50.0..=600.0).text("ms"));
+    }
+
+    ui.label(tr(lang, "At the end of a page"));
+    egui::ComboBox::from_id_salt("end_of_page")
+        .selected_text(tr(lang, rsvp.end_of_page.label()))
+        .show_ui(ui, |ui| {
+            for behavior in EndOfPageBehavior::ALL {
+                ui.selectable_value(&mut rsvp.end_of_page, behavior, tr(lang, behavior.label()));
+            }
+        });
+    if rsvp.end_of_page == EndOfPageBehavior::AutoAdvance {
+        ui.label(tr(lang, "Pause between pages (ms)"));
+        ui.add(egui::Slider::new(&mut rsvp.page_transition_pause_ms, 0.0..=2000.0));
+        if rsvp.page_transition_pause_ms > 0.0 {
+            ui.checkbox(&mut rsvp.page_transition_flash, tr(lang, "Show page number"));
+        }
+    }
+
+    let previous_loop = rsvp.loop_mode;
+    egui::ComboBox::from_id_salt("loop_mode")
+        .selected_text(tr(lang, rsvp.loop_mode.label()))
+        .show_ui(ui, |ui| {
+            for mode in LoopMode::ALL {
+                ui.selectable_value(&mut rsvp.loop_mode, mode, tr(lang, mode.label()));
+            }
+        });
+    if rsvp.loop_mode != previous_loop {
+        rsvp.loop_passes = 0;
+    }
+    if rsvp.loop_mode == LoopMode::Section {
+        let marker = |position: Option<(usize, usize)>| position.map_or("–".to_string(), |(page, word)| format!("{}:{}", page + 1, word + 1));
+        ui.horizontal(|ui| {
+            if ui.button(tr(lang, "Set A")).clicked() {
+                rsvp.set_loop_marker(true);
+            }
+            ui.label(marker(rsvp.loop_a));
+            if ui.button(tr(lang, "Set B")).clicked() {
+                rsvp.set_loop_marker(false);
+            }
+            ui.label(marker(rsvp.loop_b));
+            if ui.small_button("x").on_hover_text(tr(lang, "Clear")).clicked() {
+                rsvp.loop_a = None;
+                rsvp.loop_b = None;
+            }
+        });
+    }
+    if rsvp.loop_mode != LoopMode::None {
+        ui.checkbox(&mut rsvp.count_looped_words, tr(lang, "Count repeated words in goals and stats"));
+    }
+}
+
+fn typography_panel(ui: &mut egui::Ui, p: &mut ControlsParams) {
+    let rsvp = &mut *p.rsvp;
+    let lang = rsvp.lang;
+
+    ui.checkbox(&mut rsvp.orp_highlight, tr(lang, "Highlight pivot letter"));
+    if ui.checkbox(&mut rsvp.show_emphasis, tr(lang, "Show bold and italic from the source")).changed() {
+        rechunk_in_place(rsvp, &mut p.display, &mut p.fade);
+    }
+    if rsvp.orp_highlight {
+        ui.checkbox(&mut rsvp.orp_center_word_only, tr(lang, "Only pivot the middle word"));
+        if rsvp.align == ReaderAlign::Center {
+            ui.checkbox(&mut rsvp.orp_fixed_pivot, tr(lang, "Keep the pivot letter in one place"));
+        }
+        let slider = egui::Slider::new(&mut rsvp.orp_ratio, 0.0..=0.5).fixed_decimals(2).text(tr(lang, "Pivot position"));
+        if ui.add(slider).on_hover_text(tr(lang, "How far into each word the pivot letter sits")).changed() {
+            storage::save(ORP_RATIO_KEY, &rsvp.orp_ratio.to_string());
+            rechunk_in_place(rsvp, &mut p.display, &mut p.fade);
+        }
+    }
+
+    ui.checkbox(&mut rsvp.difficulty_coloring, tr(lang, "Tint difficult words"));
+    if rsvp.difficulty_coloring {
+        ui.add(egui::Slider::new(&mut rsvp.difficulty_intensity, 0.0..=1.0).text(tr(lang, "Intensity")));
+    }
+
+    ui.checkbox(&mut rsvp.peripheral_context, tr(lang, "Show surrounding words"));
+    if rsvp.peripheral_context {
+        ui.add(egui::Slider::new(&mut rsvp.context_before, 1..=10).text(tr(lang, "Before")));
+        ui.add(egui::Slider::new(&mut rsvp.context_after, 1..=10).text(tr(lang, "After")));
+        ui.add(egui::Slider::new(&mut rsvp.context_line_spacing, 0.5..=3.0).text(tr(lang, "Line spacing")));
+        ui.add(egui::Slider::new(&mut rsvp.context_max_width, 20.0..=100.0).suffix("%").text(tr(lang, "Column width")));
+        ui.add(egui::Slider::new(&mut rsvp.cross_page_context, 0..=10).text(tr(lang, "Words across page breaks")));
+        ui.horizontal(|ui| {
+            ui.label(tr(lang, "Read"));
+            ui.color_edit_button_srgba_unmultiplied(&mut rsvp.context_read_color);
+            ui.label(tr(lang, "Upcoming"));
+            ui.color_edit_button_srgba_unmultiplied(&mut rsvp.context_upcoming_color);
+        });
+    }
+
+    if !rsvp.peripheral_context {
+        ui.checkbox(&mut rsvp.look_ahead, tr(lang, "Preview upcoming words"));
+        if rsvp.look_ahead {
+            ui.horizontal(|ui| {
+                ui.add(egui::Slider::new(&mut rsvp.look_ahead_words, 1..=10).text(tr(lang, "Words")));
+                ui.color_edit_button_srgba_unmultiplied(&mut rsvp.context_upcoming_color);
+            });
+        }
+    }
+
+    ui.checkbox(&mut rsvp.fade_transitions, tr(lang, "Fade between words"));
+    if rsvp.fade_transitions {
+        ui.add(egui::Slider::new(&mut rsvp.fade_ms, 20.0..=300.0).text("ms"));
+    } else {
+        ui.checkbox(&mut rsvp.smooth_transitions, tr(lang, "Smooth word transitions"))
+            .on_hover_text(tr(lang, "Each word fades up from dim, without a blink; off at high speeds"));
+    }
+    ui.checkbox(&mut rsvp.reduce_motion, tr(lang, "Reduce motion"));
+    ui.checkbox(&mut rsvp.focus_mode, tr(lang, "Focus mode (dim everything but the word)"));
+    if rsvp.focus_mode {
+        ui.add(egui::Slider::new(&mut rsvp.focus_dim, 0.3..=1.0).text(tr(lang, "Dimming")));
+    }
+    ui.checkbox(&mut rsvp.show_streak, tr(lang, "Count words since the last pause"));
+    ui.checkbox(&mut rsvp.announce_progress, tr(lang, "Announce progress to screen readers"));
+
+    ui.separator();
+
+    ui.label(tr(lang, "Text Size"));
+    if ui.add(egui::Slider::new(&mut rsvp.font_size, MIN_FONT_SIZE..=MAX_FONT_SIZE)).changed() {
+        for mut font in p.text_query.iter_mut() { font.font_size = rsvp.font_size; }
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Alignment"));
+        for align in ReaderAlign::ALL {
+            ui.radio_value(&mut rsvp.align, align, tr(lang, align.label()));
+        }
+    });
+
+    let slider = egui::Slider::new(&mut rsvp.reading_column_width, 20.0..=100.0).suffix("%").text(tr(lang, "Wrap column"));
+    if ui.add(slider).on_hover_text(tr(lang, "Width that multi-word chunks wrap within")).changed() {
+        storage::save(READING_COLUMN_KEY, &rsvp.reading_column_width.to_string());
+    }
+
+    ui.label(tr(lang, "Vertical Position"));
+    if ui.add(egui::Slider::new(&mut rsvp.vertical_position, 0.0..=100.0).suffix("%")).changed() {
+        storage::save(VERTICAL_POSITION_KEY, &rsvp.vertical_position.to_string());
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Text"));
+        let text = ui.color_edit_button_srgb(&mut rsvp.text_color).changed();
+        ui.label(tr(lang, "Background"));
+        let background = ui.color_edit_button_srgb(&mut rsvp.background_color).changed();
+        let mut preset = None;
+        if ui.button(tr(lang, "Dark")).clicked() {
+            preset = Some(DARK_THEME);
+        }
+        if ui.button(tr(lang, "Light")).clicked() {
+            preset = Some(LIGHT_THEME);
+        }
+        if let Some((text, background)) = preset {
+            rsvp.text_color = text;
+            rsvp.background_color = background;
+        }
+        if text || background || preset.is_some() {
+            save_rgb(TEXT_COLOR_KEY, rsvp.text_color);
+            save_rgb(BACKGROUND_COLOR_KEY, rsvp.background_color);
+        }
+    });
+
+    ui.checkbox(&mut rsvp.ruler, tr(lang, "Reading ruler"));
+    if rsvp.align == ReaderAlign::Center {
+        ui.checkbox(&mut rsvp.show_guides, tr(lang, "Focus guides above and below"));
+    }
+    if rsvp.ruler {
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut rsvp.ruler_width, 5.0..=100.0).suffix("%").text(tr(lang, "Width")));
+            ui.color_edit_button_srgb(&mut rsvp.ruler_color);
+        });
+    }
+
+    ui.separator();
+
+    ui.label(tr(lang, "Font Family"));
+    
+    // Decoupled Font Selection Logic (Prevents UI lock/crash)
+    let mut selected_font = rsvp.current_font_name.clone();
+    let mut font_changed = false;
+
+    egui::ComboBox::from_id_salt("font_sel")
+        .selected_text(&selected_font)
+        .show_ui(ui, |ui| {
+            let uploaded = rsvp.custom_fonts.iter().map(|(name, _)| name.as_str());
+            for font_name in AVAILABLE_FONTS.iter().copied().chain(uploaded) {
+                if ui.selectable_value(&mut selected_font, font_name.to_string(), p.previews.label(font_name)).clicked() {
+                    font_changed = true;
+                }
+            }
+        });
+
+    if let Some(weights) = font_weights(&rsvp.current_font_name) {
+        let lightest = weights.first().map_or(400, |(w, _)| *w);
+        let boldest = weights.last().map_or(400, |(w, _)| *w);
+        let slider = egui::Slider::new(&mut rsvp.font_weight, lightest..=boldest).step_by(100.0).text(tr(lang, "Weight"));
+        if ui.add(slider).changed() {
+            font_changed = true;
+        }
+    }
+
+    if font_changed {
+        rsvp.current_font_name = selected_font;
+        load_reader_font(rsvp, &p.asset_server, &mut p.text_query);
+    }
+}
+
+/// Loads `current_font_name` at the current weight and points the reader text at it.
+fn load_reader_font(
+    rsvp: &mut RsvpState,
+    asset_server: &AssetServer,
+    text_query: &mut Query<&mut TextFont, ReaderFontFilter>,
+) {
+    let new_handle = match rsvp.custom_font(&rsvp.current_font_name) {
+        Some(handle) => handle.clone(),
+        None => asset_server.load(font_path(&rsvp.current_font_name, rsvp.font_weight)),
+    };
+    rsvp.current_font_handle = new_handle.clone();
+    rsvp.bold_font_handle = bold_font_path(&rsvp.current_font_name).map(|path| asset_server.load(path));
+
+    for mut font in text_query.iter_mut() {
+        font.font = new_handle.clone();
+    }
+}
+
+/// Switches to a bundled font with glyphs for the document language's script, if the
+/// current one lacks them.
+fn apply_language_font(
+    rsvp: &mut RsvpState,
+    asset_server: &AssetServer,
+    text_query: &mut Query<&mut TextFont, ReaderFontFilter>,
+    toasts: &mut Toasts,
+) {
+    let Some(language) = rsvp.document_language() else { return };
+    // Nothing is known about an uploaded font's coverage, and the reader picked it on purpose
+    if rsvp.custom_font(&rsvp.current_font_name).is_some() {
+        return;
+    }
+    let lang = rsvp.lang;
+    match language.covering_font(&rsvp.current_font_name) {
+        Some(font) if font != rsvp.current_font_name => {
+            rsvp.current_font_name = font.to_string();
+            load_reader_font(rsvp, asset_server, text_query);
+            toasts.info(format!("{}: {}", tr(lang, "Switched to a font for"), tr(lang, language.name())));
+        }
+        Some(_) => {}
+        None => toasts.error(format!("{}: {}", tr(lang, "No bundled font covers"), tr(lang, language.name()))),
+    }
+}
+
+/// Adds a font handed over by `pass_font_to_bevy` to the selector and reads in it. Bytes that
+/// aren't a font are refused, leaving the current font as it was.
+fn custom_font_system(
+    asset_server: Res<AssetServer>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut rsvp: ResMut<RsvpState>,
+    mut previews: ResMut<FontPreviews>,
+    mut toasts: ResMut<Toasts>,
+    mut text_query: Query<&mut TextFont, ReaderFontFilter>,
+) {
+    let Some(upload) = PENDING_FONT.lock().unwrap().take() else { return };
+    let lang = rsvp.lang;
+    let font = match Font::try_from_bytes(upload.bytes) {
+        Ok(font) => font,
+        Err(e) => {
+            error!("Could not read font {:?}: {}", upload.name, e);
+            toasts.error(format!("{} {}", tr(lang, "Could not read font"), upload.name));
+            return;
+        }
+    };
+
+    let mut name = if upload.name.trim().is_empty() { tr(lang, "Uploaded font").to_string() } else { upload.name };
+    // Bundled entries keep their name; re-uploading a custom font replaces it
+    if AVAILABLE_FONTS.contains(&name.as_str()) {
+        name = format!("{} ({})", name, tr(lang, "uploaded"));
+    }
+    let handle = fonts.add(font);
+    rsvp.custom_fonts.retain(|(n, _)| *n != name);
+    rsvp.custom_fonts.push((name.clone(), handle.clone()));
+    previews.track(name.clone(), handle);
+
+    rsvp.current_font_name = name.clone();
+    rsvp.font_weight = 400;
+    load_reader_font(&mut rsvp, &asset_server, &mut text_query);
+    toasts.info(format!("{}: {}", tr(lang, "Font added"), name));
+}
+
+/// Whether a font's data is there to lay text out with. Fonts added from uploaded bytes never
+/// go through the asset server and are ready as soon as they exist.
+fn font_ready(asset_server: &AssetServer, handle: &Handle<Font>) -> bool {
+    asset_server.get_load_state(handle).is_none() || asset_server.is_loaded_with_dependencies(handle)
+}
+
+/// Bundled face used when the chosen font fails to load.
+const FALLBACK_FONT: &str = "Arimo-Regular.ttf";
+
+/// Font loads finish (or fail) asynchronously; once the reader's font has failed, switch to
+/// `FALLBACK_FONT` instead of leaving the text blank.
+fn font_fallback_system(
+    asset_server: Res<AssetServer>,
+    mut rsvp: ResMut<RsvpState>,
+    mut toasts: ResMut<Toasts>,
+    mut text_query: Query<&mut TextFont, ReaderFontFilter>,
+    // The fallback itself failed to load under this handle; nothing more to try with it
+    mut gave_up: Local<Option<AssetId<Font>>>,
+) {
+    if *gave_up == Some(rsvp.current_font_handle.id()) {
+        return;
+    }
+    let Some(LoadState::Failed(error)) = asset_server.get_load_state(&rsvp.current_font_handle) else { return };
+    let lang = rsvp.lang;
+
+    if rsvp.current_font_name == FALLBACK_FONT && rsvp.font_weight == 400 {
+        *gave_up = Some(rsvp.current_font_handle.id());
+        toasts.error(format!("{} {}: {}", tr(lang, "Could not load font"), FALLBACK_FONT, error));
+        return;
+    }
+    toasts.error(format!("{} {}; {} {}", tr(lang, "Could not load font"), rsvp.current_font_name, tr(lang, "using"), FALLBACK_FONT));
+    rsvp.current_font_name = FALLBACK_FONT.to_string();
+    rsvp.font_weight = 400;
+    load_reader_font(&mut rsvp, &asset_server, &mut text_query);
+}
+
+/// Token cleanup every page goes through on its way in: math reassembly when enabled, then
+/// cutting CJK runs into chunks of `cjk_chunk_chars` characters when `language` calls for it.
+fn prepare_pages(
+    pages: Vec<Vec<String>>,
+    styles: Vec<Vec<WordStyle>>,
+    language: Option<DocLanguage>,
+    math_cleanup: bool,
+    cjk_chunk_chars: usize,
+) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let (pages, styles) = if math_cleanup { clean_math_pages(pages, styles) } else { (pages, styles) };
+    if !language.is_some_and(DocLanguage::uses_char_chunks) {
+        return (pages, styles);
+    }
+    split_cjk_pages(pages, styles, cjk_chunk_chars)
+}
+
+/// Picks up a reopened document at the position saved for it, clamped to the pages it has
+/// now. A document that was read to the end (or barely begun) starts over instead; returns
+/// whether the position moved.
+fn restore_position(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade, page: usize, word: usize) -> bool {
+    let page = page.min(rsvp.pages.len().saturating_sub(1));
+    let Some(words) = rsvp.pages.get(page) else { return false };
+    if (page == 0 && word == 0) || (page + 1 == rsvp.pages.len() && word + 1 >= words.len()) {
+        return false;
+    }
+    if words.is_empty() {
+        // A lazily loaded page not extracted yet; it's read next, since it's the current one
+        rsvp.current_page_index = page;
+        rsvp.current_word_index = word;
+    } else {
+        seek_to(rsvp, display, fade, page, word);
+    }
+    true
+}
+
+/// Replaces whatever is being read with `pages` and starts playing it from the top.
+fn open_document(
+    rsvp: &mut RsvpState,
+    fade: &mut WordFade,
+    display: &mut ReaderDisplay,
+    pages: Vec<Vec<String>>,
+    styles: Vec<Vec<WordStyle>>,
+) {
+    rsvp.set_document(pages, styles);
+    rsvp.bookmarks.clear();
+    rsvp.loop_a = None;
+    rsvp.loop_b = None;
+    rsvp.document_loaded = true;
+    rsvp.current_page_index = 0;
+    rsvp.current_word_index = 0;
+    rsvp.displayed_chunk = None;
+    rsvp.in_sentence_gap = false;
+    rsvp.in_page_transition = false;
+    rsvp.finished = false;
+    rsvp.is_playing = true;
+    // Drop anything still on screen or mid-fade from the previous document
+    fade.cancel();
+    display.clear();
+}
+
+/// Pages of words pulled from an upload, with whatever structure the format provides.
+#[derive(Default)]
+struct ParsedDocument {
+    pages: Vec<Vec<String>>,
+    styles: Vec<Vec<WordStyle>>,
+    source_pages: Vec<u32>,
+    outline: Vec<toc::OutlineEntry>,
+    // Lazy PDFs hand over the parsed document and its page numbers
+    deferred: Option<DeferredPages>,
+    // Long PDFs whose text is still to be extracted, over the next frames
+    extraction: Option<PdfJob>,
+    // Plain text cut into pages by word count, which re-paginating can redo
+    paginated_by_words: bool,
+    // Text decoded with enough invalid UTF-8 that some of it shows as replacement characters
+    not_utf8: bool,
+}
+
+fn is_markdown_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".md") || name.ends_with(".markdown")
+}
+
+fn parse_pdf(bytes: Vec<u8>, mode: PdfLoadMode, clean: bool) -> Result<ParsedDocument, ParseError> {
+    let mut doc = Document::load_from(Cursor::new(bytes)).map_err(|e| match e {
+        lopdf::Error::Header => ParseError::NotAPdf(format!("Not a PDF: {}", e)),
+        lopdf::Error::Decryption(_) => ParseError::Encrypted,
+        _ => ParseError::Unsupported(format!("Failed to load PDF: {}", e)),
+    })?;
+    // Many PDFs are encrypted only to restrict printing or copying and open with an empty password
+    if doc.is_encrypted() && doc.decrypt("").is_err() {
+        return Err(ParseError::Encrypted);
+    }
+    let mut parsed = ParsedDocument { outline: toc::pdf_outline(&doc), ..default() };
+
+    // lopdf numbers pages 1.. by walking the page tree's /Kids, so this is already reading
+    // order whatever the page objects' ids are
+    let page_numbers: Vec<u32> = doc.get_pages().into_keys().collect();
+    let has_pages = !page_numbers.is_empty();
+
+    match mode {
+        PdfLoadMode::Eager | PdfLoadMode::TokenizeOnVisit => {
+            if should_extract_in_frames(page_numbers.len()) {
+                // Picked up by `file_listener_system`, which extracts a batch of pages a frame
+                parsed.extraction = Some(PdfJob::new(doc, page_numbers, mode, clean));
+                return Ok(parsed);
+            }
+            let texts = page_numbers.iter().map(|&page_num| extract_page_text(&doc, page_num)).collect();
+            finish_pdf(parsed, texts, &page_numbers, mode, clean)
+        }
+        PdfLoadMode::ExtractOnVisit => {
+            // Stops at the first page with text, so only a scan is read all the way through
+            if has_pages && !page_numbers.iter().any(|&page_num| !extract_page_text(&doc, page_num).trim().is_empty()) {
+                return Err(ParseError::ImageOnly);
+            }
+            // Every page starts empty (blank ones included) and is filled in by `lazy_pdf_system`
+            parsed.pages = vec![Vec::new(); page_numbers.len()];
+            parsed.source_pages = page_numbers.clone();
+            parsed.deferred = Some(DeferredPages::Pdf(Box::new(doc), page_numbers));
+            Ok(parsed)
+        }
+    }
+}
+
+/// Turns the text of every page (extracted up front, in `page_numbers` order) into the
+/// document's pages, for the loading modes that extract it all on upload.
+fn finish_pdf(
+    mut parsed: ParsedDocument,
+    mut texts: Vec<String>,
+    page_numbers: &[u32],
+    mode: PdfLoadMode,
+    clean: bool,
+) -> Result<ParsedDocument, ParseError> {
+    // Running headers are told apart by comparing pages, so both modes clean them all at once
+    if clean {
+        strip_furniture(&mut texts);
+    }
+    match mode {
+        PdfLoadMode::TokenizeOnVisit => {
+            // Blank pages are known now, so they're dropped just like when loading eagerly
+            let mut kept = Vec::new();
+            for (text, &page_num) in texts.into_iter().zip(page_numbers) {
+                if !text.trim().is_empty() {
+                    kept.push(text);
+                    parsed.source_pages.push(page_num);
+                }
+            }
+            // Filled in by `lazy_pdf_system`
+            parsed.pages = vec![Vec::new(); kept.len()];
+            parsed.deferred = Some(DeferredPages::Text(kept));
+        }
+        _ => {
+            for (text, &page_num) in texts.iter().zip(page_numbers) {
+                let words = tokenize(text);
+                if !words.is_empty() {
+                    parsed.pages.push(words);
+                    parsed.source_pages.push(page_num);
+                }
+            }
+        }
+    }
+    // Pages, but no text on any of them: almost always scanned images
+    if parsed.pages.is_empty() {
+        return Err(if page_numbers.is_empty() { ParseError::NoText } else { ParseError::ImageOnly });
+    }
+    Ok(parsed)
+}
+
+fn is_text_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".txt") || name.ends_with(".text")
+}
+
+/// Plain text in pages of `words_per_page`; blank lines mark paragraph ends.
+fn parse_plain_text(text: &str, words_per_page: usize) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let mut b = StyledTextBuilder::with_page_limit(words_per_page);
+    push_plain_text(&mut b, text);
+    b.finish()
+}
+
+fn is_html_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".html") || name.ends_with(".htm")
+}
+
+/// Whether the bytes start like a PDF. Readers accept the header anywhere in the first
+/// kilobyte, after junk some generators put in front of it.
+fn looks_like_pdf(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(1024)].windows(5).any(|w| w == b"%PDF-")
+}
+
+/// Whether plain text `bytes` get pages by word count. Form feeds are the file's own page
+/// breaks, which recutting by word count would lose.
+fn paginates_by_words(bytes: &[u8]) -> bool {
+    !bytes.contains(&0x0C)
+}
+
+fn parse_text_upload(text: &str, rsvp: &RsvpState) -> ParsedDocument {
+    let text = if rsvp.trim_gutenberg_boilerplate { trim_boilerplate(text) } else { text };
+    let (pages, styles) = parse_plain_text(text, rsvp.words_per_page);
+    ParsedDocument { pages, styles, paginated_by_words: paginates_by_words(text.as_bytes()), ..default() }
+}
+
+/// Picks a parser from the content (zip archives, PDFs) or file name, falling back to
+/// sniffing for HTML and then reading anything else as plain text. Text formats that aren't
+/// valid UTF-8 are decoded lossily rather than refused.
+fn parse_upload(upload: Upload, rsvp: &RsvpState) -> Result<ParsedDocument, ParseError> {
+    let mut not_utf8 = false;
+    let (pages, styles) = if is_docx(&upload.bytes) {
+        parse_docx(&upload.bytes).map_err(ParseError::Unsupported)?
+    } else if is_epub(&upload.bytes) {
+        parse_epub(&upload.bytes).map_err(ParseError::Unsupported)?
+    } else if upload.bytes.starts_with(b"PK") {
+        return Err(ParseError::Unsupported("Unsupported or damaged archive (expected a DOCX or EPUB)".to_string()));
+    } else if is_text_name(&upload.name) {
+        let (text, not_utf8) = decode_text(&upload.bytes);
+        return Ok(ParsedDocument { not_utf8, ..parse_text_upload(&text, rsvp) });
+    } else if is_markdown_name(&upload.name) {
+        let (text, invalid) = decode_text(&upload.bytes);
+        not_utf8 = invalid;
+        parse_markdown(&text)
+    } else if is_html_name(&upload.name) || looks_like_html(&upload.bytes) {
+        let (text, invalid) = decode_text(&upload.bytes);
+        not_utf8 = invalid;
+        parse_html(&text)
+    } else if looks_like_pdf(&upload.bytes) {
+        return parse_pdf(upload.bytes, rsvp.pdf_load_mode, rsvp.clean_extracted_text);
+    } else {
+        // Unnamed uploads (`pass_file_to_bevy`) that aren't PDFs are most likely text and are
+        // read like a `.txt`; binary files of other kinds decode to mostly replacement
+        // characters and are refused
+        let (text, not_utf8) = decode_text(&upload.bytes);
+        if not_utf8 && looks_binary(&text) {
+            return Err(ParseError::NotAPdf("Neither a PDF nor readable text".to_string()));
+        }
+        return Ok(ParsedDocument { not_utf8, ..parse_text_upload(&text, rsvp) });
+    };
+    Ok(ParsedDocument { pages, styles, not_utf8, ..default() })
+}
+
+/// Minutes to read `total_words` straight through at `wpm`, before any per-word timing.
+fn estimate_minutes(total_words: usize, wpm: f32) -> f32 {
+    total_words as f32 / wpm.max(MIN_WPM)
+}
+
+/// Speed to offer for a document of `total_words`, starting from the user's `wpm`: short
+/// pieces can go a little faster, long books are better begun at a pace that can be kept up.
+fn suggested_wpm(total_words: usize, wpm: f32) -> f32 {
+    let factor = match total_words {
+        0..1_000 => 1.15,
+        1_000..5_000 => 1.05,
+        5_000..50_000 => 1.0,
+        _ => 0.9,
+    };
+    ((wpm * factor / 10.0).round() * 10.0).clamp(MIN_WPM, MAX_WPM)
+}
+
+fn wpm_suggestion_system(mut rsvp: ResMut<RsvpState>, mut toasts: ResMut<Toasts>) {
+    let Some((id, wpm)) = rsvp.wpm_suggestion else { return };
+    if toasts.take_accepted(id) {
+        rsvp.wpm = wpm;
+        rsvp.wpm_suggestion = None;
+    } else if !toasts.is_pending(id) {
+        rsvp.wpm_suggestion = None;
+    }
+}
+
+fn show_extraction_progress(extraction: &PdfExtraction, lang: Lang, display: &mut ReaderDisplay) {
+    let (done, total) = extraction.progress();
+    display.set(vec![Segment::plain(format!("{} {}/{} {}", tr(lang, "Parsing…"), done, total, tr(lang, "pages")))]);
+}
+
+/// An upload as opened: its `document_key` and every setting that changes what parsing it
+/// gives. The same bytes uploaded again under the same settings would open the same document.
+#[derive(Clone, PartialEq)]
+struct UploadId {
+    key: String,
+    pdf_load_mode: PdfLoadMode,
+    trim_gutenberg_boilerplate: bool,
+    clean_extracted_text: bool,
+    math_cleanup: bool,
+    cjk_chunk_chars: usize,
+    language_override: Option<DocLanguage>,
+}
+
+impl UploadId {
+    fn new(key: String, rsvp: &RsvpState) -> Self {
+        Self {
+            key,
+            pdf_load_mode: rsvp.pdf_load_mode,
+            trim_gutenberg_boilerplate: rsvp.trim_gutenberg_boilerplate,
+            clean_extracted_text: rsvp.clean_extracted_text,
+            math_cleanup: rsvp.math_cleanup,
+            cjk_chunk_chars: rsvp.cjk_chunk_chars,
+            language_override: rsvp.language_override,
+        }
+    }
+}
+
+fn file_listener_system(
+    mut rsvp: ResMut<RsvpState>,
+    mut fade: ResMut<WordFade>,
+    mut display: ResMut<ReaderDisplay>,
+    mut prefs: ResMut<DocumentPrefs>,
+    mut lazy: ResMut<LazyPdf>,
+    mut stream: ResMut<TextStream>,
+    mut extraction: ResMut<PdfExtraction>,
+    mut toasts: ResMut<Toasts>,
+    asset_server: Res<AssetServer>,
+    mut text_query: Query<&mut TextFont, ReaderFontFilter>,
+) {
+    let lang = rsvp.lang;
+    let upload = UPLOADED_FILE_QUEUE.lock().unwrap().take();
+    let (parsed, upload_id, name) = match upload {
+        Some(upload) => {
+            let key = document_key(&upload.bytes);
+            // UIs that re-fire their change event hand over the same file again; keep the place
+            // unless an import setting changed since, which is a reason to upload it again
+            let upload_id = UploadId::new(key, &rsvp);
+            if rsvp.loaded_upload.as_ref() == Some(&upload_id) {
+                toasts.info(tr(lang, "That document is already open"));
+                return;
+            }
+            // A newer upload replaces one still being extracted
+            extraction.cancel();
+            let name = if upload.name.is_empty() { "upload".to_string() } else { upload.name.clone() };
+            info!("Processing {}...", name);
+
+            // Huge plain-text files load a slice per frame instead of all at once
+            let mut parsed = if is_text_name(&upload.name) && should_stream(&upload.bytes) {
+                let paginated_by_words = paginates_by_words(&upload.bytes);
+                let (pages, styles) = stream.start(upload.bytes, rsvp.words_per_page);
+                Ok(ParsedDocument { pages, styles, paginated_by_words, ..default() })
+            } else {
+                stream.cancel();
+                parse_upload(upload, &rsvp)
+            };
+            // Long PDFs are extracted a batch of pages per frame from here on, and opened
+            // once the last one is in
+            if let Some(job) = parsed.as_mut().ok().and_then(|p| p.extraction.take()) {
+                extraction.start(job, parsed.unwrap_or_default(), upload_id, name);
+                rsvp.is_playing = false;
+                fade.cancel();
+                show_extraction_progress(&extraction, lang, &mut display);
+                return;
+            }
+            (parsed, upload_id, name)
+        }
+        None if extraction.is_active() => {
+            // Nothing plays until the new document is open, so the progress stays on screen
+            rsvp.is_playing = false;
+            match extraction.step() {
+                Some(extracted) => {
+                    if extracted.0.is_err() {
+                        // Don't leave the progress up; play carries on the previous document
+                        display.clear();
+                    }
+                    extracted
+                }
+                None => {
+                    show_extraction_progress(&extraction, lang, &mut display);
+                    return;
+                }
+            }
+        }
+        None => return,
+    };
+    let key = upload_id.key.clone();
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            toasts.error(format!("{}: {}", tr(lang, "Could not open file"), e));
+            notify_error(&e);
+            return;
+        }
+    };
+    if parsed.pages.is_empty() {
+        toasts.error(tr(lang, "Document contained no text"));
+        notify_error(&ParseError::NoText);
+        return;
+    }
+
+    let detected = language::detect(&parsed.pages);
+    let language = rsvp.language_override.or(detected);
+    let (pages, styles) = prepare_pages(parsed.pages, parsed.styles, language, rsvp.math_cleanup, rsvp.cjk_chunk_chars);
+    open_document(&mut rsvp, &mut fade, &mut display, pages, styles);
+    rsvp.detected_language = detected;
+    rsvp.loaded_upload = Some(upload_id);
+    // The saved per-document font below still wins over this one
+    apply_language_font(&mut rsvp, &asset_server, &mut text_query, &mut toasts);
+    rsvp.toc = toc::map_outline(parsed.outline, &parsed.source_pages);
+    rsvp.source_pages = parsed.source_pages;
+    rsvp.paginated_by_words = parsed.paginated_by_words;
+    rsvp.bookmarks = bookmarks::load(&key);
+    match parsed.deferred {
+        Some(deferred) => lazy.open(deferred),
+        None => lazy.close(),
+    }
+
+    let saved = prefs.open(key);
+    // Before the position is restored, so it's chunked the way it was last read
+    if let Some(words) = saved.as_ref().and_then(|s| s.words_per_frame) {
+        rsvp.words_per_frame = words.clamp(1, MAX_WORDS_PER_FRAME);
+    }
+    if let Some((page, word)) = prefs.saved_position() {
+        if restore_position(&mut rsvp, &mut display, &mut fade, page, word) {
+            toasts.info(format!("{} {}", tr(lang, "Continuing from page"), rsvp.current_page_index + 1));
+        }
+    }
+    if let Some(saved) = saved {
+        rsvp.wpm = saved.wpm.clamp(MIN_WPM, MAX_WPM);
+        if let Some(size) = saved.font_size {
+            rsvp.font_size = size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+            for mut font in text_query.iter_mut() {
+                font.font_size = rsvp.font_size;
+            }
+        }
+        if rsvp.has_font(&saved.font) && saved.font != rsvp.current_font_name {
+            rsvp.current_font_name = saved.font;
+            load_reader_font(&mut rsvp, &asset_server, &mut text_query);
+        }
+        toasts.info(tr(lang, "Using saved settings for this document"));
+    } else if rsvp.suggest_wpm_on_open {
+        let wpm = suggested_wpm(rsvp.total_words, rsvp.wpm);
+        rsvp.wpm_suggestion = (wpm != rsvp.wpm).then(|| {
+            let message = format!("{}: {:.0} WPM", tr(lang, "Suggested speed for this document"), wpm);
+            (toasts.prompt(message, tr(lang, "Use it")), wpm)
+        });
+    }
+    toasts.success(format!("{}: {} ({} {})", tr(lang, "Loaded"), name, rsvp.pages.len(), tr(lang, "pages")));
+    if parsed.not_utf8 {
+        toasts.warning(tr(lang, "Parts of this file aren't UTF-8 and show as �; save it as UTF-8 for clean text"));
+    }
+}
+
+// --- TIMING ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Punctuation {
+    None,
+    Sentence, // . ? !
+    Clause,   // , ; :
+    Dash,     // em/en dash
+}
+
+// Skipped when looking at the punctuation a word ends with
+const CLOSING_MARKS: [char; 8] = ['"', '\'', '”', '’', ')', ']', '}', '»'];
+
+/// Classifies the punctuation a word ends with, ignoring closing quotes and brackets.
+fn trailing_punctuation(word: &str) -> Punctuation {
+    let trimmed = word.trim_end_matches(CLOSING_MARKS);
+
+    if trimmed.ends_with("--") {
+        return Punctuation::Dash;
+    }
+    match trimmed.chars().last() {
+        Some('.' | '?' | '!' | '…') => Punctuation::Sentence,
+        Some(',' | ';' | ':') => Punctuation::Clause,
+        Some('—' | '–') => Punctuation::Dash,
+        _ => Punctuation::None,
+    }
+}
+
+/// Whether `word` ends a clause with `;` or `:`, which get the sentence pause even though they
+/// don't end the sentence.
+fn ends_in_semicolon_or_colon(word: &str) -> bool {
+    word.trim_end_matches(CLOSING_MARKS).ends_with([';', ':'])
+}
+
+/// A word starts a sentence when there is no previous word or the previous one ends one.
+fn is_sentence_initial(prev: Option<&str>) -> bool {
+    prev.is_none_or(|p| trailing_punctuation(p) == Punctuation::Sentence)
+}
+
+/// Capitalized mid-sentence words (likely proper nouns) and all-caps acronyms.
+fn is_proper_noun_like(word: &str, prev: Option<&str>) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.is_empty() {
+        return false;
+    }
+    if letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase()) {
+        return true;
+    }
+    letters[0].is_uppercase() && !is_sentence_initial(prev)
+}
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "in", "is", "it",
+    "of", "on", "or", "so", "the", "to", "was", "with",
+];
+
+/// Floor for sped-up short words so they still register on screen.
+const SHORT_WORD_MIN_MS: f32 = 60.0;
+
+/// Extra dwell on a math symbol standing alone, which packs a phrase into one glyph.
+const MATH_SYMBOL_MULTIPLIER: f32 = 1.8;
+
+/// Function words that carry little meaning: very short, or in `STOP_WORDS`.
+fn is_filler_word(word: &str, max_len: usize) -> bool {
+    let core: String = word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect();
+    !core.is_empty() && (core.chars().count() <= max_len || STOP_WORDS.contains(&core.as_str()))
+}
+
+/// Number of digits if the token is predominantly numeric ("1,234,567", "3.14%"), else 0.
+fn numeric_digit_count(word: &str) -> usize {
+    let digits = word.chars().filter(|c| c.is_ascii_digit()).count();
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    if digits > 0 && digits > letters { digits } else { 0 }
+}
+
+/// Display time in seconds for a single word, after all multipliers and clamps.
+/// `prev` is the word shown before it, used for sentence-position rules.
+fn word_duration_secs(rsvp: &RsvpState, word: &str, prev: Option<&str>, style: WordStyle) -> f32 {
+    let mut base_ms = 60_000.0 / rsvp.effective_wpm * rsvp.timing_model.multiplier(word);
+
+    // The list is English, so other languages would read as rare words throughout
+    if rsvp.frequency_timing && rsvp.document_language().is_none_or(|l| l == DocLanguage::English) {
+        base_ms *= frequency_multiplier(word, rsvp.frequency_intensity);
+    }
+
+    if style.heading {
+        base_ms *= rsvp.heading_pause_multiplier;
+    }
+
+    if rsvp.short_word_speedup && is_filler_word(word, rsvp.short_word_len) {
+        // Never push a word that was already quick below the visibility floor
+        base_ms = (base_ms * rsvp.short_word_factor).max(base_ms.min(SHORT_WORD_MIN_MS));
+    }
+
+    if rsvp.proper_noun_slowdown && is_proper_noun_like(word, prev) {
+        base_ms *= rsvp.proper_noun_multiplier;
+    }
+
+    let digits = numeric_digit_count(word);
+    if rsvp.numeric_slowdown && digits > 0 {
+        // Short numbers get the base strength, each digit past three adds 10%
+        let extra = digits.saturating_sub(3) as f32 * 0.1;
+        base_ms *= rsvp.numeric_multiplier * (1.0 + extra);
+    }
+
+    if rsvp.math_cleanup && is_math_symbol(word) {
+        base_ms *= MATH_SYMBOL_MULTIPLIER;
+    }
+
+    base_ms *= match trailing_punctuation(word) {
+        Punctuation::Sentence => rsvp.sentence_pause_multiplier,
+        Punctuation::Clause if ends_in_semicolon_or_colon(word) => rsvp.sentence_pause_multiplier,
+        Punctuation::Clause => rsvp.clause_pause_multiplier,
+        Punctuation::Dash => rsvp.dash_pause_multiplier,
+        // An unpunctuated paragraph end (headings, list items) still closes a thought
+        Punctuation::None if style.paragraph_end => rsvp.sentence_pause_multiplier,
+        Punctuation::None => 1.0,
+    };
+
+    // Added on top of the scaled time so the pause is the same at any speed
+    if word.chars().filter(|c| c.is_alphanumeric()).count() > rsvp.long_word_len_threshold {
+        base_ms += rsvp.long_word_extra_ms;
+    }
+
+    // Clamps go last so no multiplier can push a word out of the readable range
+    let min_ms = rsvp.min_word_ms;
+    let max_ms = rsvp.max_word_ms.max(min_ms);
+    base_ms.clamp(min_ms, max_ms) / 1000.0
+}
+
+/// Display time for `pages[page][start..end]`: the sum of its words' durations, with any
+/// per-word override from `word_durations` taking the place of the computed time.
+fn chunk_duration_secs(rsvp: &RsvpState, page: usize, start: usize, end: usize) -> f32 {
+    let Some(words) = rsvp.pages.get(page) else { return 0.0 };
+    let offset = rsvp.flat_index(page, 0);
+    (start..end.min(words.len()))
+        .map(|i| {
+            if let Some(ms) = rsvp.word_durations.get(offset + i).copied().flatten() {
+                return ms / 1000.0;
+            }
+            let prev = i.checked_sub(1).map(|p| words[p].as_str());
+            word_duration_secs(rsvp, &words[i], prev, rsvp.word_style(page, i))
+        })
+        .sum()
+}
+
+/// Fade length for a chunk of the given dwell, or 0 when fading is off.
+/// Capped at a quarter of the dwell so the fade never eats into the next word.
+fn fade_secs(rsvp: &RsvpState, dwell_secs: f32) -> f32 {
+    if !rsvp.fade_transitions || rsvp.reduce_motion {
+        return 0.0;
+    }
+    let secs = (rsvp.fade_ms / 1000.0).min(dwell_secs * 0.25);
+    // Below ~one frame the fade is invisible, so skip it
+    if secs < 0.016 { 0.0 } else { secs }
+}
+
+// Smooth transitions: each word comes up from this alpha over the first part of its dwell
+const SMOOTH_START_ALPHA: f32 = 0.3;
+const SMOOTH_SHARE: f32 = 0.2;
+const SMOOTH_MAX_SECS: f32 = 0.12;
+// Shorter than two frames reads as a dim flash rather than a fade
+const SMOOTH_MIN_SECS: f32 = 0.033;
+
+/// Fade-in length for a chunk of the given dwell, or 0 when smooth transitions are off or
+/// the dwell is too short for one (roughly above 400 WPM).
+fn smooth_fade_secs(rsvp: &RsvpState, dwell_secs: f32) -> f32 {
+    if !rsvp.smooth_transitions || rsvp.reduce_motion {
+        return 0.0;
+    }
+    let secs = (dwell_secs * SMOOTH_SHARE).min(SMOOTH_MAX_SECS);
+    if secs < SMOOTH_MIN_SECS { 0.0 } else { secs }
+}
+
+/// Whether `word` is short enough to ride along with the next one ("a dog", "to the").
+fn is_tiny_word(word: &str, max_len: usize) -> bool {
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    letters > 0 && letters <= max_len && trailing_punctuation(word) == Punctuation::None
+}
+
+/// Exclusive end of the chunk starting at `pages[page][start]`. Line mode takes the rest of the
+/// source line and sentence mode the rest of the sentence. Otherwise smart chunking stops
+/// after a sentence terminator, and tiny-word merging then pulls in the word after a
+/// trailing tiny word.
+fn chunk_end(rsvp: &RsvpState, page_index: usize, start: usize) -> usize {
+    let page = rsvp.pages.get(page_index).map_or(&[][..], Vec::as_slice);
+    match rsvp.chunk_mode {
+        ChunkMode::Lines => {
+            let limit = (start + MAX_LINE_WORDS).min(page.len());
+            return (start..limit)
+                .find(|&i| {
+                    let style = rsvp.word_style(page_index, i);
+                    style.line_end || style.paragraph_end
+                })
+                .map_or(limit, |i| i + 1);
+        }
+        ChunkMode::Sentences => {
+            // Headings and list items often have no terminator; their block end closes them
+            let limit = (start + MAX_SENTENCE_WORDS).min(page.len());
+            return (start..limit)
+                .find(|&i| {
+                    trailing_punctuation(&page[i]) == Punctuation::Sentence || rsvp.word_style(page_index, i).paragraph_end
+                })
+                .map_or(limit, |i| i + 1);
+        }
+        ChunkMode::Words => {}
+    }
+
+    let limit = (start + rsvp.words_per_frame.max(1)).min(page.len());
+    let mut end = if rsvp.smart_chunking {
+        (start..limit)
+            .find(|&i| trailing_punctuation(&page[i]) == Punctuation::Sentence)
+            .map_or(limit, |i| i + 1)
+    } else {
+        limit
+    };
+    if rsvp.merge_tiny_words {
+        // Chains like "of a" keep going, but never past what fits on the line
+        let max_end = (end + MAX_MERGED_WORDS).min(page.len());
+        while end < max_end && is_tiny_word(&page[end - 1], rsvp.merge_max_len) {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// Most words tiny-word merging may add to a chunk.
+const MAX_MERGED_WORDS: usize = 3;
+/// Line mode splits longer lines (prose without breaks) so they still fit on screen.
+const MAX_LINE_WORDS: usize = 12;
+/// Sentence mode splits run-ons (or text without punctuation) the same way.
+const MAX_SENTENCE_WORDS: usize = 40;
+
+const NAV_HISTORY_LEN: usize = 50;
+
+// Catch-up limit for `rsvp_tick_system` when one frame spans several dwells
+const MAX_ADVANCES_PER_FRAME: u32 = 8;
+
+const WPM_HISTORY_LEN: usize = 20;
+// A slider drag or a run of key presses counts as one change once it's been still this long
+const WPM_SETTLE_SECS: f32 = 1.0;
+
+/// Per-frame order of the reader's systems, so a frame always sees the newest input:
+/// uploads, UI, keys and seeks come first; then lazily loaded pages are filled in; then
+/// playback advances from the resulting position; then everything that shows or reports
+/// that position (fade, render, layout, context lines, stats, JS events) runs on the outcome.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+enum ReaderSet {
+    Input,
+    Load,
+    Tick,
+    Present,
+}
+
+/// `seek_to` for discontinuous jumps: the position being left goes on the history stack.
+fn jump_to(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade, page: usize, word: usize) {
+    rsvp.remember_position();
+    seek_to(rsvp, display, fade, page, word);
+}
+
+/// `jump_to` that also takes pages not extracted yet (lazy PDFs): as with the page slider,
+/// those are made current for the tick to show once they have words.
+fn jump_to_page(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade, page: usize, word: usize) {
+    let Some(last_page) = rsvp.pages.len().checked_sub(1) else { return };
+    let page = page.min(last_page);
+    if rsvp.pages[page].is_empty() {
+        rsvp.remember_position();
+        rsvp.current_page_index = page;
+        rsvp.current_word_index = 0;
+    } else {
+        jump_to(rsvp, display, fade, page, word);
+    }
+}
+
+/// `jump_to` the final word, so one more step finishes the document.
+fn jump_to_end(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) {
+    let Some(last_page) = rsvp.pages.len().checked_sub(1) else { return };
+    let last_word = rsvp.pages[last_page].len().saturating_sub(1);
+    jump_to(rsvp, display, fade, last_page, last_word);
+}
+
+/// Returns to the position before the latest jump; false when there is nothing to go back to.
+fn go_back(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) -> bool {
+    let Some((page, word)) = rsvp.nav_history.pop() else { return false };
+    seek_to(rsvp, display, fade, page, word);
+    true
+}
+
+/// Moves `delta` words from the current position, crossing pages and clamping at the document ends.
+fn step_words(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade, delta: isize) {
+    let total = rsvp.total_words;
+    if total == 0 {
+        return;
+    }
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word) as isize + delta;
+    let (page, word) = rsvp.position_from_flat(flat.clamp(0, total as isize - 1) as usize);
+    seek_to(rsvp, display, fade, page, word);
+}
+
+/// (page, word) of the first word on screen, or of the next word when nothing is shown yet.
+fn current_position(rsvp: &RsvpState) -> (usize, usize) {
+    match rsvp.displayed_chunk {
+        Some((page, start, _)) if page == rsvp.current_page_index => (page, start),
+        _ => (rsvp.current_page_index, rsvp.current_word_index),
+    }
+}
+
+/// Jumps to `word` on `page` (both clamped) and shows that chunk immediately,
+/// leaving the reader exactly as if playback had just advanced onto it. Every seek — the
+/// sliders, keys, minimap, bookmarks and the JS `seek` export — ends up here, so a fade in
+/// progress, a pending gap or page pause and the time already spent on the old word are
+/// all dropped in one place.
+fn seek_to(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade, page: usize, word: usize) {
+    if rsvp.pages.is_empty() {
+        return;
+    }
+    let page = page.min(rsvp.pages.len() - 1);
+    let word = word.min(rsvp.pages[page].len().saturating_sub(1));
+    let end = chunk_end(rsvp, page, word);
+
+    fade.cancel();
+    display.set(chunk_segments(rsvp, page, word, end));
+
+    rsvp.current_page_index = page;
+    rsvp.displayed_chunk = Some((page, word, end));
+    rsvp.current_word_index = end;
+    rsvp.in_sentence_gap = false;
+    rsvp.in_page_transition = false;
+    rsvp.finished = false;
+    rsvp.timer.reset();
+    rsvp.seeked_this_frame = true;
+}
+
+/// Re-shows the current chunk under new chunking settings, anchored on the flat index of
+/// its first word so the reader stays on the same word.
+fn rechunk_in_place(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) {
+    if rsvp.displayed_chunk.is_none() || rsvp.total_words == 0 {
+        return;
+    }
+    let (page, word) = current_position(rsvp);
+    let (page, word) = rsvp.position_from_flat(rsvp.flat_index(page, word));
+    seek_to(rsvp, display, fade, page, word);
+}
+
+/// Recuts a word-paginated document after `words_per_page` changes, keeping the reader
+/// on the same word.
+fn repaginate_in_place(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) {
+    if !rsvp.paginated_by_words || rsvp.total_words == 0 {
+        return;
+    }
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word);
+    rsvp.repaginate();
+    let (page, word) = rsvp.position_from_flat(flat);
+    if rsvp.displayed_chunk.is_some() {
+        seek_to(rsvp, display, fade, page, word);
+    } else {
+        rsvp.current_page_index = page;
+        rsvp.current_word_index = word;
+    }
+}
+
+/// Time constant of the WPM easing; the effective rate is ~95% of the way there after 300ms.
+const WPM_SMOOTHING_SECS: f32 = 0.1;
+
+/// Moves `effective_wpm` toward `wpm`, snapping when smoothing is off or nothing is playing.
+fn ease_effective_wpm(rsvp: &mut RsvpState, delta_secs: f32) {
+    if !rsvp.smooth_wpm || !rsvp.is_playing {
+        rsvp.effective_wpm = rsvp.wpm;
+        return;
+    }
+    let gap = rsvp.wpm - rsvp.effective_wpm;
+    if gap.abs() < 0.5 {
+        rsvp.effective_wpm = rsvp.wpm;
+    } else {
+        rsvp.effective_wpm += gap * (1.0 - (-delta_secs / WPM_SMOOTHING_SECS).exp());
+    }
+}
+
+/// Whether the chunk currently on screen ends with a sentence terminator.
+fn displayed_chunk_ends_sentence(rsvp: &RsvpState) -> bool {
+    rsvp.displayed_chunk
+        .and_then(|(page, _, end)| rsvp.pages.get(page)?.get(end.checked_sub(1)?))
+        .is_some_and(|w| trailing_punctuation(w) == Punctuation::Sentence)
+}
+
+/// Everything the on-screen chunk's dwell depends on. The timer duration is only
+/// recomputed when this changes, not every frame.
+#[derive(Clone, Copy, PartialEq)]
+struct DwellInputs {
+    chunk: Option<(usize, usize, usize)>,
+    in_sentence_gap: bool,
+    in_page_transition: bool,
+    effective_wpm: f32,
+    words_per_frame: usize,
+    timing_model: TimingModel,
+    short_word_speedup: bool,
+    short_word_len: usize,
+    frequency_timing: bool,
+    proper_noun_slowdown: bool,
+    numeric_slowdown: bool,
+    math_cleanup: bool,
+    long_word_len_threshold: usize,
+    // Pause multipliers, multiplier strengths, clamps and gap lengths, in field order
+    tuning: [f32; 13],
+}
+
+impl DwellInputs {
+    fn of(rsvp: &RsvpState) -> Self {
+        Self {
+            chunk: rsvp.displayed_chunk,
+            in_sentence_gap: rsvp.in_sentence_gap,
+            in_page_transition: rsvp.in_page_transition,
+            effective_wpm: rsvp.effective_wpm,
+            words_per_frame: rsvp.words_per_frame,
+            timing_model: rsvp.timing_model,
+            short_word_speedup: rsvp.short_word_speedup,
+            short_word_len: rsvp.short_word_len,
+            frequency_timing: rsvp.frequency_timing,
+            proper_noun_slowdown: rsvp.proper_noun_slowdown,
+            numeric_slowdown: rsvp.numeric_slowdown,
+            math_cleanup: rsvp.math_cleanup,
+            long_word_len_threshold: rsvp.long_word_len_threshold,
+            tuning: [
+                rsvp.sentence_pause_multiplier,
+                rsvp.clause_pause_multiplier,
+                rsvp.dash_pause_multiplier,
+                rsvp.heading_pause_multiplier,
+                rsvp.short_word_factor,
+                rsvp.frequency_intensity,
+                rsvp.proper_noun_multiplier,
+                rsvp.numeric_multiplier,
+                rsvp.long_word_extra_ms,
+                rsvp.min_word_ms,
+                rsvp.max_word_ms,
+                rsvp.sentence_gap_ms,
+                rsvp.page_transition_pause_ms,
+            ],
+        }
+    }
+
+    /// Whether both are the same chunk, gap or page pause, timed differently or not.
+    fn same_step(&self, other: &Self) -> bool {
+        (self.chunk, self.in_sentence_gap, self.in_page_transition) == (other.chunk, other.in_sentence_gap, other.in_page_transition)
+    }
+}
+
+fn rsvp_tick_system(
+    time: Res<Time>, 
+    mut rsvp: ResMut<RsvpState>, 
+    mut fade: ResMut<WordFade>,
+    mut display: ResMut<ReaderDisplay>,
+    mut was_playing: Local<bool>,
+    // Time on the current countdown digit
+    mut countdown_secs: Local<f32>,
+) {
+    let started = rsvp.is_playing && !*was_playing;
+    *was_playing = rsvp.is_playing;
+
+    // Input runs first (see `ReaderSet`); a seek it made gets its full dwell from the next
+    // frame rather than being ticked past on a long frame
+    if rsvp.seeked_this_frame {
+        rsvp.seeked_this_frame = false;
+        // The seek put its own word up, which the countdown mustn't cover
+        rsvp.countdown_remaining = None;
+        return;
+    }
+
+    if rsvp.effective_wpm != rsvp.wpm {
+        ease_effective_wpm(&mut rsvp, time.delta_secs());
+    }
+
+    if rsvp.is_playing && rsvp.waiting_at_page {
+        rsvp.waiting_at_page = false;
+    }
+
+    // Manual mode is driven from `keyboard_input_system` instead
+    let timed = rsvp.advance_mode == AdvanceMode::Timed;
+    let counting_down = started && timed && rsvp.show_countdown && !rsvp.pages.is_empty();
+    // Backwards, the words just before the stop are the ones still to come
+    if started && timed && rsvp.rewind_on_resume > 0 && !rsvp.reverse {
+        if rewind_for_resume(&mut rsvp, &mut display, &mut fade) && counting_down {
+            // Shown again once the countdown is over, rather than skipped past
+            let (page, word) = current_position(&rsvp);
+            rsvp.current_page_index = page;
+            rsvp.current_word_index = word;
+            rsvp.displayed_chunk = None;
+        }
+        if !counting_down {
+            return;
+        }
+    }
+    if counting_down {
+        rsvp.countdown_remaining = Some(COUNTDOWN_FROM);
+        *countdown_secs = 0.0;
+        fade.cancel();
+        display.set(vec![Segment::colored(COUNTDOWN_FROM.to_string(), PIVOT_COLOR)]);
+        return;
+    }
+    if let Some(digit) = rsvp.countdown_remaining {
+        if !rsvp.is_playing {
+            // Paused mid-countdown; the next play starts it over
+            rsvp.countdown_remaining = None;
+            display.clear();
+            return;
+        }
+        *countdown_secs += time.delta_secs();
+        if *countdown_secs < COUNTDOWN_STEP_SECS {
+            return;
+        }
+        *countdown_secs -= COUNTDOWN_STEP_SECS;
+        if digit > 1 {
+            rsvp.countdown_remaining = Some(digit - 1);
+            display.set(vec![Segment::colored((digit - 1).to_string(), PIVOT_COLOR)]);
+            return;
+        }
+        // The first word goes up straight after "1", with its full dwell
+        rsvp.countdown_remaining = None;
+        play_step(&mut rsvp, &mut fade, &mut display);
+        sync_dwell(&mut rsvp);
+        rsvp.timer.reset();
+        return;
+    }
+
+    if !rsvp.is_playing || rsvp.pages.is_empty() || !timed {
+        return;
+    }
+    if !rsvp.finished {
+        rsvp.stats.active_secs += time.delta_secs_f64();
+        if rsvp.ramp_enabled {
+            ramp_wpm(&mut rsvp, time.delta_secs());
+        }
+    }
+
+    sync_dwell(&mut rsvp);
+    rsvp.timer.tick(time.delta());
+    if !rsvp.timer.just_finished() {
+        return;
+    }
+
+    // A slow frame can cover several dwells at high WPM: everything past the first is
+    // spent on the chunks that follow, so the rate holds even when frames drop
+    let duration = rsvp.timer.duration();
+    let mut overflow = rsvp.timer.elapsed() + duration * (rsvp.timer.times_finished_this_tick() - 1);
+    play_step(&mut rsvp, &mut fade, &mut display);
+    for _ in 1..MAX_ADVANCES_PER_FRAME {
+        if !rsvp.is_playing {
+            return;
+        }
+        sync_dwell(&mut rsvp);
+        let Some(rest) = overflow.checked_sub(rsvp.timer.duration()) else { break };
+        overflow = rest;
+        play_step(&mut rsvp, &mut fade, &mut display);
+    }
+    // Past the cap (a stalled or backgrounded tab) the backlog is dropped, not replayed
+    let carried = overflow.min(rsvp.timer.duration());
+    rsvp.timer.set_elapsed(carried);
+}
+
+/// One timed step of playback: `advance`, or `step_back` in reverse.
+fn play_step(rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay) {
+    if rsvp.reverse {
+        step_back(rsvp, fade, display);
+    } else {
+        advance(rsvp, fade, display);
+    }
+}
+
+/// `advance` backwards: shows what comes before the chunk on screen, a chunk's worth of
+/// words back, crossing to the end of the previous page at a page start. At the start of
+/// the document playback stops there. Sentence gaps and page pauses aren't repeated.
+fn step_back(rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay) {
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word).min(rsvp.total_words);
+    if flat == 0 {
+        rsvp.is_playing = false;
+        return;
+    }
+    let shown = match rsvp.displayed_chunk {
+        Some((_, start, end)) => end.saturating_sub(start).max(1),
+        None => 1,
+    };
+    let (page, word) = rsvp.position_from_flat(flat.saturating_sub(shown));
+    seek_to(rsvp, display, fade, page, word);
+    // Playback, not a seek: the tick carries on timing this chunk
+    rsvp.seeked_this_frame = false;
+}
+
+/// Backs up `rewind_on_resume` words from the chunk on screen (across pages, stopping at the
+/// start) and shows it with a full dwell. False when already at the start.
+fn rewind_for_resume(rsvp: &mut RsvpState, display: &mut ReaderDisplay, fade: &mut WordFade) -> bool {
+    let (page, word) = current_position(rsvp);
+    let flat = rsvp.flat_index(page, word).min(rsvp.total_words);
+    if flat == 0 {
+        return false;
+    }
+    let (page, word) = rsvp.position_from_flat(flat.saturating_sub(rsvp.rewind_on_resume));
+    seek_to(rsvp, display, fade, page, word);
+    // Already mid-tick, so there's no later tick for the seek to hold off
+    rsvp.seeked_this_frame = false;
+    true
+}
+
+/// One frame of the speed ramp; reaching the target ends it.
+fn ramp_wpm(rsvp: &mut RsvpState, dt: f32) {
+    let target = rsvp.ramp_target_wpm.clamp(MIN_WPM, MAX_WPM);
+    rsvp.wpm = (rsvp.wpm + rsvp.ramp_rate_wpm_per_min * dt / 60.0).min(target);
+    if rsvp.wpm >= target {
+        rsvp.ramp_enabled = false;
+        info!("Speed ramp reached {:.0} WPM", target);
+    }
+}
+
+/// Sets the timer to the dwell of what's on screen, if anything it depends on changed;
+/// returns whether it did. The dwell belongs to the chunk already on screen, so it rests on
+/// that word.
+fn sync_dwell(rsvp: &mut RsvpState) -> bool {
+    let inputs = DwellInputs::of(rsvp);
+    if rsvp.dwell_inputs == Some(inputs) {
+        return false;
+    }
+    let seconds_per_chunk = match rsvp.displayed_chunk {
+        _ if rsvp.in_sentence_gap => rsvp.sentence_gap_ms / 1000.0,
+        _ if rsvp.in_page_transition => rsvp.page_transition_pause_ms / 1000.0,
+        Some((page, start, end)) if page < rsvp.pages.len() => chunk_duration_secs(rsvp, page, start, end),
+        Some(_) => 60.0 / rsvp.effective_wpm,
+        None => (60.0 / rsvp.effective_wpm) * (rsvp.words_per_frame as f32),
+    };
+    let duration = Duration::from_secs_f32(seconds_per_chunk.max(0.001));
+    // New timing for the step already on screen (a speed change, easing, a slider): it keeps
+    // the share of its dwell it has had, so the rest plays at the new pace instead of the
+    // word lingering on or cutting off
+    if rsvp.dwell_inputs.is_some_and(|old| old.same_step(&inputs)) {
+        let elapsed = rescaled_elapsed(rsvp.timer.elapsed(), rsvp.timer.duration(), duration);
+        rsvp.timer.set_duration(duration);
+        rsvp.timer.set_elapsed(elapsed);
+    } else {
+        rsvp.timer.set_duration(duration);
+    }
+    rsvp.dwell_inputs = Some(inputs);
+    true
+}
+
+/// `elapsed` of `old` as the same fraction of `new`.
+fn rescaled_elapsed(elapsed: Duration, old: Duration, new: Duration) -> Duration {
+    if old.is_zero() {
+        return elapsed.min(new);
+    }
+    new.mul_f64((elapsed.as_secs_f64() / old.as_secs_f64()).min(1.0))
+}
+
+/// Moves on by one step: the next chunk, a sentence gap or page pause (timed mode only),
+/// the next page, or the end of the document.
+fn advance(rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay) {
+    let timed = rsvp.advance_mode == AdvanceMode::Timed;
+
+    // The page pause already separated the old page's last sentence from the new one
+    let leaving_page_transition = std::mem::take(&mut rsvp.in_page_transition);
+
+    // Sentence ended: go blank for one gap interval before the next word
+    if timed && rsvp.sentence_gap && !rsvp.in_sentence_gap && !leaving_page_transition && displayed_chunk_ends_sentence(rsvp) {
+        rsvp.in_sentence_gap = true;
+        fade.cancel();
+        display.clear();
+        return;
+    }
+    rsvp.in_sentence_gap = false;
+
+    // Past marker B: back to A
+    if let (LoopMode::Section, Some((a, b))) = (rsvp.loop_mode, rsvp.loop_section()) {
+        if rsvp.flat_index(rsvp.current_page_index, rsvp.current_word_index) > b {
+            let (page, word) = rsvp.position_from_flat(a);
+            rsvp.current_page_index = page;
+            rsvp.current_word_index = word;
+            rsvp.loop_passes += 1;
+        }
+    }
+
+    let current_page = &rsvp.pages[rsvp.current_page_index];
+
+    if rsvp.current_word_index < current_page.len() {
+        let page = rsvp.current_page_index;
+        let end_index = chunk_end(rsvp, page, rsvp.current_word_index);
+        let segments = chunk_segments(rsvp, page, rsvp.current_word_index, end_index);
+        let dwell = chunk_duration_secs(rsvp, page, rsvp.current_word_index, end_index);
+        let fade_len = if timed { fade_secs(rsvp, dwell) } else { 0.0 };
+        let smooth_len = if timed { smooth_fade_secs(rsvp, dwell) } else { 0.0 };
+
+        if fade_len > 0.0 {
+            fade.start(segments, fade_len);
+        } else if smooth_len > 0.0 {
+            fade.start_in(display, segments, smooth_len, SMOOTH_START_ALPHA);
+        } else {
+            fade.cancel();
+            display.set(segments);
+        }
+
+        if rsvp.loop_passes == 0 || rsvp.count_looped_words {
+            rsvp.words_advanced += (end_index - rsvp.current_word_index) as u64;
+            rsvp.stats.words += (end_index - rsvp.current_word_index) as u64;
+        }
+        rsvp.displayed_chunk = Some((rsvp.current_page_index, rsvp.current_word_index, end_index));
+        rsvp.current_word_index = end_index;
+    } else if rsvp.loop_mode == LoopMode::Page && !current_page.is_empty() {
+        rsvp.current_word_index = 0;
+        rsvp.loop_passes += 1;
+        advance(rsvp, fade, display);
+    } else if rsvp.at_page_break() {
+        rsvp.current_page_index += 1;
+        rsvp.current_word_index = 0;
+
+        if !timed || rsvp.end_of_page == EndOfPageBehavior::Continuous {
+            // A key press should always show a word, not just turn the page
+            advance(rsvp, fade, display);
+        } else if rsvp.end_of_page == EndOfPageBehavior::PauseEachPage {
+            rsvp.is_playing = false;
+            rsvp.waiting_at_page = true;
+            fade.cancel();
+            let label = format!("{} {}", tr(rsvp.lang, "Page"), rsvp.current_page_index + 1);
+            display.set(vec![Segment::colored(label, PIVOT_COLOR)]);
+        } else if rsvp.page_transition_pause_ms > 0.0 {
+            rsvp.in_page_transition = true;
+            fade.cancel();
+            if rsvp.page_transition_flash {
+                let label = format!("{} {}", tr(rsvp.lang, "Page"), rsvp.current_page_index + 1);
+                display.set(vec![Segment::colored(label, PIVOT_COLOR)]);
+            } else {
+                display.clear();
+            }
+        }
+    } else if rsvp.loop_mode == LoopMode::Document && rsvp.total_words > 0 {
+        rsvp.current_page_index = 0;
+        rsvp.current_word_index = 0;
+        rsvp.loop_passes += 1;
+        advance(rsvp, fade, display);
+    } else {
+        rsvp.is_playing = false;
+        rsvp.finished = true;
+        fade.cancel();
+        // The last word stays up, even if a sentence gap or page pause had blanked it
+        if let Some((page, start, end)) = rsvp.displayed_chunk {
+            display.set(chunk_segments(rsvp, page, start, end));
+        }
+        notify_finished();
+    }
+}
+
+fn keyboard_input_system(
+    mut contexts: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut rsvp: ResMut<RsvpState>,
+    mut display: ResMut<ReaderDisplay>,
+    mut fade: ResMut<WordFade>,
+    mut overlay: ResMut<OverlayMessage>,
+    mut layout: ResMut<PanelLayout>,
+    // Playback was paused by us when a text field took focus, and should resume after
+    mut paused_for_focus: Local<bool>,
+) {
+    // `ButtonInput` only reports the first press; the events also carry the OS key repeats,
+    // so holding an arrow keeps stepping or changing speed
+    let pressed: Vec<KeyCode> = key_events.read().filter(|e| e.state == ButtonState::Pressed).map(|e| e.key_code).collect();
+    let presses = |key: KeyCode| pressed.iter().filter(|&&k| k == key).count();
+
+    // Typing into an egui field must not drive the reader (or trigger the paste listener)
+    let egui_focused = contexts.ctx_mut().wants_keyboard_input();
+    EGUI_WANTS_KEYBOARD.store(egui_focused, Ordering::Relaxed);
+
+    if *paused_for_focus && rsvp.is_playing {
+        // Something else (a Play click, a new document) already restarted playback
+        *paused_for_focus = false;
+    }
+    if egui_focused && rsvp.is_playing {
+        rsvp.is_playing = false;
+        *paused_for_focus = true;
+    } else if !egui_focused && std::mem::take(&mut *paused_for_focus) && !rsvp.finished {
+        rsvp.is_playing = true;
+    }
+
+    if egui_focused {
+        return;
+    }
+    if keys.just_pressed(KeyCode::KeyF) {
+        layout.hidden = !layout.hidden;
+        if layout.hidden {
+            overlay.show(tr(rsvp.lang, "Press F to show controls"), 1.5);
+        }
+    }
+    if rsvp.pages.is_empty() {
+        return;
+    }
+
+    const PRESET_KEYS: [KeyCode; 5] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4, KeyCode::Digit5];
+    for (key, wpm) in PRESET_KEYS.iter().zip(WPM_PRESETS) {
+        if keys.just_pressed(*key) {
+            rsvp.wpm = wpm;
+            overlay.show(format!("{:.0} WPM", wpm), 1.0);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::KeyZ) {
+        match rsvp.undo_wpm() {
+            Some(wpm) => overlay.show(format!("{:.0} WPM", wpm), 1.0),
+            None => overlay.show(tr(rsvp.lang, "No earlier speed"), 1.0),
+        }
+    }
+
+    let space = keys.just_pressed(KeyCode::Space);
+    let right = presses(KeyCode::ArrowRight);
+    if rsvp.advance_mode == AdvanceMode::Manual {
+        for _ in 0..right + space as usize {
+            if !rsvp.finished {
+                advance(&mut rsvp, &mut fade, &mut display);
+            }
+        }
+    } else if rsvp.waiting_at_page && (space || right > 0) {
+        rsvp.is_playing = true;
+    } else {
+        if space && rsvp.finished {
+            jump_to(&mut rsvp, &mut display, &mut fade, 0, 0);
+            rsvp.is_playing = true;
+        } else if space {
+            rsvp.is_playing = !rsvp.is_playing;
+        }
+        if right > 0 {
+            step_words(&mut rsvp, &mut display, &mut fade, right as isize);
+        }
+    }
+    let left = presses(KeyCode::ArrowLeft);
+    if left > 0 {
+        step_words(&mut rsvp, &mut display, &mut fade, -(left as isize));
+    }
+
+    let wpm_steps = presses(KeyCode::ArrowUp) as f32 - presses(KeyCode::ArrowDown) as f32;
+    if wpm_steps != 0.0 {
+        rsvp.wpm = (rsvp.wpm + wpm_steps * KEY_WPM_STEP).clamp(MIN_WPM, MAX_WPM);
+        overlay.show(format!("{:.0} WPM", rsvp.wpm), 1.0);
+    }
+
+    if keys.just_pressed(KeyCode::Comma) {
+        step_words(&mut rsvp, &mut display, &mut fade, -5);
+    }
+    if keys.just_pressed(KeyCode::Period) {
+        step_words(&mut rsvp, &mut display, &mut fade, 5);
+    }
+    if keys.just_pressed(KeyCode::Home) {
+        jump_to(&mut rsvp, &mut display, &mut fade, 0, 0);
+    }
+    if keys.just_pressed(KeyCode::Backspace) && !go_back(&mut rsvp, &mut display, &mut fade) {
+        overlay.show(tr(rsvp.lang, "No earlier position"), 1.0);
+    }
+    if keys.just_pressed(KeyCode::End) {
+        jump_to_end(&mut rsvp, &mut display, &mut fade);
+    }
+}
+
+fn overlay_message_system(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    mut overlay: ResMut<OverlayMessage>,
+) {
+    if overlay.remaining <= 0.0 {
+        return;
+    }
+    overlay.remaining -= time.delta_secs();
+
+    egui::Area::new(egui::Id::new("overlay_message"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.heading(&overlay.text);
+            });
+        });
+}
+
+/// Rough advance of an average glyph, in ems, for fitting words into a column.
+const AVERAGE_GLYPH_EM: f32 = 0.55;
+
+/// Joins as many of `words` as fit in `max_chars`, keeping those nearest the focus word
+/// (the tail when `nearest_last`, otherwise the head).
+fn fit_words(words: &[String], max_chars: usize, nearest_last: bool) -> String {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut len = 0;
+    let ordered: Box<dyn Iterator<Item = &String>> =
+        if nearest_last { Box::new(words.iter().rev()) } else { Box::new(words.iter()) };
+
+    for word in ordered {
+        let added = word.chars().count() + usize::from(!kept.is_empty());
+        if len + added > max_chars && !kept.is_empty() {
+            break;
+        }
+        len += added;
+        kept.push(word);
+    }
+    if nearest_last {
+        kept.reverse();
+    }
+    kept.join(" ")
+}
+
+/// Words at the flat indices in `flats`, nearest first, letting at most
+/// `cross_page_context` of them come from pages other than `page`.
+fn context_words(rsvp: &RsvpState, page: usize, flats: impl Iterator<Item = usize>) -> Vec<String> {
+    let mut crossed = 0;
+    let mut kept = Vec::new();
+    for flat in flats {
+        let (p, w) = rsvp.position_from_flat(flat);
+        if p != page {
+            crossed += 1;
+            if crossed > rsvp.cross_page_context {
+                break;
+            }
+        }
+        let Some(word) = rsvp.pages.get(p).and_then(|words| words.get(w)) else { break };
+        kept.push(word.clone());
+    }
+    kept
+}
+
+/// Keeps the context lines in lockstep with the focus word, within the current page.
+fn context_lines_system(
+    rsvp: Res<RsvpState>,
+    windows: Query<&Window>,
+    mut lines: Query<(&ContextLine, &mut Text, &mut TextFont, &mut TextColor, &mut Node)>
+) {
+    if !rsvp.is_changed() {
+        return;
+    }
+
+    let (page, start, end) = rsvp.displayed_chunk.unwrap_or_else(|| {
+        let (page, word) = current_position(&rsvp);
+        (page, word, word)
+    });
+    let words = rsvp.pages.get(page).map_or(&[][..], |p| p.as_slice());
+    let start = start.min(words.len());
+    let end = end.clamp(start, words.len());
+    let (flat_start, flat_end) = (rsvp.flat_index(page, start), rsvp.flat_index(page, end));
+
+    let context_size = rsvp.font_size * CONTEXT_FONT_SCALE;
+    // Clear the focus word's half-height plus half a context line
+    let offset = (rsvp.font_size * 0.75 + context_size * 0.75) * rsvp.context_line_spacing;
+
+    let window_width = windows.get_single().map_or(1280.0, |w| w.width());
+    let column_px = window_width * rsvp.context_max_width / 100.0;
+    let max_chars = (column_px / (context_size * AVERAGE_GLYPH_EM)).max(1.0) as usize;
+
+    let base = rsvp.base_direction(page);
+    for (line, mut text, mut font, mut color, mut node) in lines.iter_mut() {
+        // The full context mode wins; the look-ahead alone only fills the line below
+        let after = if rsvp.peripheral_context { rsvp.context_after } else { rsvp.look_ahead_words };
+        let mut content = match line {
+            ContextLine::Above if rsvp.peripheral_context => {
+                let before = (flat_start.saturating_sub(rsvp.context_before)..flat_start).rev();
+                let mut kept = context_words(&rsvp, page, before);
+                kept.reverse();
+                fit_words(&kept, max_chars, true)
+            }
+            // Near the end of the document this simply shows the fewer words that remain
+            ContextLine::Below if rsvp.peripheral_context || rsvp.look_ahead => {
+                let following = flat_end..(flat_end + after).min(rsvp.total_words);
+                fit_words(&context_words(&rsvp, page, following), max_chars, false)
+            }
+            _ => String::new(),
+        };
+        if let Some(mark) = direction_mark(&content, &base) {
+            content.insert(0, mark);
+        }
+        if text.0 != content {
+            text.0 = content;
+        }
+
+        if font.font_size != context_size || font.font != rsvp.current_font_handle {
+            font.font_size = context_size;
+            font.font = rsvp.current_font_handle.clone();
+        }
+
+        // Everything above the focus word has been read; everything below is still to come
+        let [r, g, b, a] = match line {
+            ContextLine::Above => rsvp.context_read_color,
+            ContextLine::Below => rsvp.context_upcoming_color,
+        };
+        let line_color = Color::srgba_u8(r, g, b, a);
+        if color.0 != line_color {
+            color.0 = line_color;
+        }
+
+        // `top` follows the focus line; the margin stacks the context line above or below it
+        let top = Val::Percent(rsvp.vertical_position - 50.0);
+        let margin = match line {
+            ContextLine::Above => Val::Px(-offset),
+            ContextLine::Below => Val::Px(offset),
+        };
+        if node.top != top || node.margin.top != margin {
+            node.top = top;
+            node.margin.top = margin;
+        }
+    }
+}
+
+/// Debounces `wpm` changes into `wpm_history`.
+fn wpm_history_system(time: Res<Time>, mut rsvp: ResMut<RsvpState>, mut last_wpm: Local<Option<f32>>) {
+    let wpm = rsvp.wpm;
+    if wpm == rsvp.settled_wpm {
+        rsvp.wpm_unsettled_secs = 0.0;
+        *last_wpm = Some(wpm);
+        return;
+    }
+    // Still moving: restart the wait
+    if *last_wpm != Some(wpm) {
+        *last_wpm = Some(wpm);
+        rsvp.wpm_unsettled_secs = 0.0;
+        return;
+    }
+    rsvp.wpm_unsettled_secs += time.delta_secs();
+    if rsvp.wpm_unsettled_secs >= WPM_SETTLE_SECS {
+        let previous = rsvp.settled_wpm;
+        if rsvp.wpm_history.len() == WPM_HISTORY_LEN {
+            rsvp.wpm_history.remove(0);
+        }
+        rsvp.wpm_history.push(previous);
+        rsvp.settled_wpm = wpm;
+        rsvp.wpm_unsettled_secs = 0.0;
+    }
+}
+
+fn idle_message_system(mut rsvp: ResMut<RsvpState>) {
+    let Some(message) = PENDING_IDLE_MESSAGE.lock().unwrap().take() else { return };
+    rsvp.idle_message = message;
+    rsvp.show_idle_message();
+}
+
+fn word_durations_system(mut rsvp: ResMut<RsvpState>) {
+    let Some(durations) = PENDING_WORD_DURATIONS.lock().unwrap().take() else { return };
+    if !durations.is_empty() && durations.len() != rsvp.total_words {
+        warn!("Ignoring word durations: got {} entries for {} words", durations.len(), rsvp.total_words);
+        return;
+    }
+    if durations.iter().flatten().any(|ms| !ms.is_finite() || *ms < 0.0) {
+        warn!("Ignoring word durations: entries must be non-negative milliseconds");
+        return;
+    }
+    rsvp.word_durations = durations;
+    // Re-time the chunk on screen with the new table
+    rsvp.dwell_inputs = None;
+}
+
+/// Applies a seek from JS (`seek`). Like every other seek it goes through `jump_to`,
+/// which drops the chunk's elapsed time and any fade before showing the target word.
+fn seek_command_system(mut rsvp: ResMut<RsvpState>, mut display: ResMut<ReaderDisplay>, mut fade: ResMut<WordFade>) {
+    let Some(flat) = PENDING_SEEK.lock().unwrap().take() else { return };
+    if flat >= rsvp.total_words {
+        warn!("Ignoring seek to word {}: the document has {} words", flat, rsvp.total_words);
+        return;
+    }
+    let (page, word) = rsvp.position_from_flat(flat);
+    jump_to(&mut rsvp, &mut display, &mut fade, page, word);
+}
+
+fn js_command_system(mut rsvp: ResMut<RsvpState>, mut display: ResMut<ReaderDisplay>, mut fade: ResMut<WordFade>) {
+    let commands = std::mem::take(&mut *PENDING_COMMANDS.lock().unwrap());
+    if commands.is_empty() {
+        return;
+    }
+    if !rsvp.document_loaded || rsvp.pages.is_empty() {
+        warn!("Ignoring {} playback command(s): no document is open", commands.len());
+        return;
+    }
+    for command in commands {
+        match command {
+            JsCommand::SetPlaying(true) if rsvp.finished => {
+                jump_to(&mut rsvp, &mut display, &mut fade, 0, 0);
+                rsvp.is_playing = true;
+            }
+            JsCommand::SetPlaying(playing) => rsvp.is_playing = playing,
+            JsCommand::SetWpm(wpm) if wpm.is_finite() => rsvp.wpm = wpm.clamp(MIN_WPM, MAX_WPM),
+            JsCommand::SetWpm(wpm) => warn!("Ignoring speed of {} WPM", wpm),
+            JsCommand::SeekTo { page, word } => jump_to_page(&mut rsvp, &mut display, &mut fade, page, word),
         }
     }
 }
 
-fn rsvp_tick_system(
-    time: Res<Time>, 
-    mut rsvp: ResMut<RsvpState>, 
-    mut query: Query<&mut Text, With<ReaderText>>
+fn cues_system(mut rsvp: ResMut<RsvpState>) {
+    let Some(mut cues) = PENDING_CUES.lock().unwrap().take() else { return };
+    if let Some(bad) = cues.iter().find(|&&i| i >= rsvp.total_words) {
+        warn!("Ignoring cues: index {} is past the last word ({} words)", bad, rsvp.total_words);
+        return;
+    }
+    cues.sort_unstable();
+    cues.dedup();
+    rsvp.cues = cues;
+}
+
+/// Reports each newly shown chunk to the `set_on_word` callback.
+fn word_event_system(rsvp: Res<RsvpState>, mut last: Local<Option<(usize, usize, usize)>>) {
+    if !rsvp.is_changed() || rsvp.displayed_chunk == *last {
+        return;
+    }
+    *last = rsvp.displayed_chunk;
+    let Some((page, start, end)) = rsvp.displayed_chunk else { return };
+    let Some(words) = rsvp.pages.get(page).and_then(|p| p.get(start..end.min(p.len()))) else { return };
+    let first = rsvp.flat_index(page, start);
+    notify_word(first, &words.join(" "));
+
+    // Any cue inside the chunk, not just at its first word
+    let from = rsvp.cues.partition_point(|&cue| cue < first);
+    for &cue in rsvp.cues[from..].iter().take_while(|&&cue| cue < first + words.len()) {
+        notify_cue(cue, &words[cue - first]);
+    }
+}
+
+fn state_snapshot_system(rsvp: Res<RsvpState>) {
+    if !rsvp.is_changed() {
+        return;
+    }
+
+    let (page, word) = current_position(&rsvp);
+    let flat = rsvp.flat_index(rsvp.current_page_index, rsvp.current_word_index);
+    let snapshot = StateSnapshot {
+        page,
+        word,
+        total_pages: rsvp.pages.len(),
+        total_words: rsvp.total_words,
+        words_on_page: rsvp.pages.get(page).map_or(0, |p| p.len()),
+        wpm: rsvp.wpm,
+        is_playing: rsvp.is_playing,
+        finished: rsvp.finished,
+        font: rsvp.current_font_name.clone(),
+        progress: (flat as f32 / rsvp.total_words.max(1) as f32).min(1.0),
+    };
+    {
+        let mut stored = STATE_SNAPSHOT.lock().unwrap();
+        if stored.as_ref() == Some(&snapshot) {
+            return;
+        }
+        *stored = Some(snapshot.clone());
+    }
+    // After the lock is released, so the callback can call `get_state`
+    notify_state_change(&snapshot);
+}
+
+/// Whether chunks can hold more than one word, and so may need to wrap.
+fn multi_word_chunks(rsvp: &RsvpState) -> bool {
+    rsvp.chunk_mode != ChunkMode::Words || rsvp.words_per_frame > 1 || rsvp.merge_tiny_words
+}
+
+/// Moves the full-window reader node so its centered line lands at `vertical_position`,
+/// and the reading ruler just under that line (the focus guides either side of its center).
+fn reader_layout_system(
+    rsvp: Res<RsvpState>,
+    asset_server: Res<AssetServer>,
+    mut nodes: Query<(&mut Node, &mut TextLayout), With<ReaderText>>,
+    mut context_nodes: Query<(&mut Node, &mut TextLayout), (With<ContextLine>, Without<ReaderText>)>,
+    mut rulers: Query<(&mut Node, &mut BackgroundColor), (With<ReadingRuler>, Without<ReaderText>, Without<ContextLine>)>,
+    mut guides: Query<
+        (&FocusGuide, &mut Node, &mut BackgroundColor),
+        (Without<ReadingRuler>, Without<ReaderText>, Without<ContextLine>),
+    >,
+    mut clear_color: ResMut<ClearColor>,
+    mut font_was_loaded: Local<bool>,
 ) {
-    if !rsvp.is_playing || rsvp.pages.is_empty() {
+    // Wrapping needs font metrics; before they're there it can panic, so it waits for the load
+    let font_loaded = font_ready(&asset_server, &rsvp.current_font_handle)
+        && rsvp.bold_font_handle.as_ref().is_none_or(|bold| font_ready(&asset_server, bold));
+    if !rsvp.is_changed() && font_loaded == *font_was_loaded {
         return;
     }
+    *font_was_loaded = font_loaded;
 
-    let seconds_per_chunk = (60.0 / rsvp.wpm) * (rsvp.words_per_frame as f32);
-    
-    rsvp.timer.set_duration(Duration::from_secs_f32(seconds_per_chunk));
-    rsvp.timer.tick(time.delta());
+    let top = Val::Percent(rsvp.vertical_position - 50.0);
+    let align = rsvp.align;
+    for (mut node, mut layout) in context_nodes.iter_mut() {
+        if node.justify_content != align.justify_content() || node.padding.left != align.padding_left() {
+            node.justify_content = align.justify_content();
+            node.padding.left = align.padding_left();
+        }
+        if layout.justify != align.justify_text() {
+            layout.justify = align.justify_text();
+        }
+    }
+
+    let wrap = font_loaded && multi_word_chunks(&rsvp);
+    let (padding_left, padding_right) = if wrap {
+        align.column_padding(rsvp.reading_column_width.clamp(10.0, 100.0))
+    } else {
+        (align.padding_left(), Val::ZERO)
+    };
+    let linebreak = if wrap { LineBreak::WordBoundary } else { LineBreak::NoWrap };
+    for (mut node, mut layout) in nodes.iter_mut() {
+        if node.justify_content != align.justify_content() || node.padding.left != padding_left || node.padding.right != padding_right {
+            node.justify_content = align.justify_content();
+            node.padding.left = padding_left;
+            node.padding.right = padding_right;
+        }
+        if layout.justify != align.justify_text() || layout.linebreak != linebreak {
+            layout.justify = align.justify_text();
+            layout.linebreak = linebreak;
+        }
+        if node.top != top {
+            node.top = top;
+        }
+    }
+
+    let [r, g, b] = rsvp.background_color;
+    let background = Color::srgb_u8(r, g, b);
+    if clear_color.0 != background {
+        clear_color.0 = background;
+    }
+
+    let [r, g, b] = rsvp.ruler_color;
+    let color = Color::srgba_u8(r, g, b, (RULER_ALPHA * 255.0) as u8);
+    for (mut node, mut background) in rulers.iter_mut() {
+        let display = if rsvp.ruler { Display::Flex } else { Display::None };
+        let width = rsvp.ruler_width.clamp(0.0, 100.0);
+        // Half a line of text below the focus line's center
+        let (left, top, margin) = (
+            Val::Percent((100.0 - width) / 2.0),
+            Val::Percent(rsvp.vertical_position),
+            Val::Px(rsvp.font_size * 0.6),
+        );
+        if node.display != display || node.width != Val::Percent(width) || node.left != left || node.top != top || node.margin.top != margin {
+            node.display = display;
+            node.width = Val::Percent(width);
+            node.left = left;
+            node.top = top;
+            node.margin.top = margin;
+        }
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+
+    // Percent-positioned like the ruler, so they follow the window as it resizes; the pivot
+    // letter only sits at the center with centered text
+    let display = if rsvp.show_guides && align == ReaderAlign::Center { Display::Flex } else { Display::None };
+    let color = rsvp.text_color().with_alpha(RULER_ALPHA);
+    let length = rsvp.font_size * GUIDE_LENGTH;
+    for (guide, mut node, mut background) in guides.iter_mut() {
+        let margin = match guide {
+            FocusGuide::Above => -(rsvp.font_size * GUIDE_GAP + length),
+            FocusGuide::Below => rsvp.font_size * GUIDE_GAP,
+        };
+        let top = Val::Percent(rsvp.vertical_position);
+        if node.display != display || node.top != top || node.height != Val::Px(length) || node.margin.top != Val::Px(margin) {
+            node.display = display;
+            node.top = top;
+            node.height = Val::Px(length);
+            node.margin.top = Val::Px(margin);
+        }
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
+
+fn word_fade_system(
+    time: Res<Time>,
+    mut fade: ResMut<WordFade>,
+    mut display: ResMut<ReaderDisplay>,
+) {
+    let alpha = match fade.phase {
+        FadePhase::Idle => 1.0,
+        FadePhase::Out | FadePhase::In => {
+            fade.elapsed += time.delta_secs();
+            let half = (fade.duration / 2.0).max(f32::EPSILON);
 
-    if rsvp.timer.just_finished() {
-        let current_page = &rsvp.pages[rsvp.current_page_index];
+            if fade.phase == FadePhase::Out && fade.elapsed >= half {
+                // Midpoint: swap in the new word and start fading it up
+                if let Some(pending) = fade.pending.take() {
+                    display.set(pending);
+                }
+                fade.phase = FadePhase::In;
+                fade.elapsed -= half;
+            }
 
-        if rsvp.current_word_index < current_page.len() {
-            let end_index = (rsvp.current_word_index + rsvp.words_per_frame).min(current_page.len());
-            let chunk_text = current_page[rsvp.current_word_index..end_index].join(" ");
-            
-            for mut text in query.iter_mut() {
-                text.0 = chunk_text.clone();
+            match fade.phase {
+                FadePhase::Out => 1.0 - fade.elapsed / half,
+                _ if fade.elapsed >= half => {
+                    fade.phase = FadePhase::Idle;
+                    1.0
+                }
+                _ => fade.from + (1.0 - fade.from) * fade.elapsed / half,
             }
-            
-            rsvp.current_word_index += rsvp.words_per_frame;
-        } else {
-            if rsvp.current_page_index + 1 < rsvp.pages.len() {
-                rsvp.current_page_index += 1;
-                rsvp.current_word_index = 0;
-            } else {
-                rsvp.is_playing = false;
+        }
+    };
+
+    let alpha = alpha.clamp(0.0, 1.0);
+    if display.alpha != alpha {
+        display.alpha = alpha;
+    }
+}
+
+/// Pins the pivot letter to the window's horizontal center. The shift needs the glyph
+/// positions from this frame's text layout, so it runs after layout in `PostUpdate` and
+/// offsets the node's final transform, which the text is drawn from, rather than its `Node`.
+fn pivot_anchor_system(
+    rsvp: Res<RsvpState>,
+    display: Res<ReaderDisplay>,
+    mut nodes: Query<(&Transform, &mut GlobalTransform, &ComputedNode, &TextLayout, &bevy::text::TextLayoutInfo), With<ReaderText>>,
+) {
+    // Spans as the text block counts them: the root `Text` first, then each `ReaderSpan`
+    let pivots: Vec<usize> = display
+        .segments
+        .iter()
+        .take(READER_SPAN_COUNT)
+        .enumerate()
+        .filter(|(_, s)| s.color == Some(PIVOT_COLOR))
+        .map(|(i, _)| i + 1)
+        .collect();
+    let anchored = rsvp.orp_highlight && rsvp.orp_fixed_pivot && rsvp.align == ReaderAlign::Center;
+    for (transform, mut global, node, layout, info) in nodes.iter_mut() {
+        let pivot_span = pivots.get(pivots.len() / 2).filter(|_| anchored && layout.linebreak == LineBreak::NoWrap);
+        let mut glyphs = info.glyphs.iter().filter(|g| Some(&g.span_index) == pivot_span);
+        let offset = match (glyphs.next(), glyphs.next_back()) {
+            (Some(first), last) => {
+                let pivot_x = (first.position.x + last.unwrap_or(first).position.x) / 2.0;
+                node.size().x / 2.0 - pivot_x
             }
+            (None, _) => 0.0,
+        };
+        // Root node, so its global transform is its own; setting it whole never accumulates
+        let target = GlobalTransform::from(Transform::from_translation(transform.translation + Vec3::X * offset));
+        if *global != target {
+            *global = target;
+        }
+    }
+}
+
+/// Syncs `ReaderDisplay` into the `ReaderText` spans whenever it changes.
+fn reader_render_system(
+    display: Res<ReaderDisplay>,
+    rsvp: Res<RsvpState>,
+    mut spans: Query<(&ReaderSpan, &mut TextSpan, &mut TextColor, &mut TextFont)>,
+    mut text_color: Local<Option<[u8; 3]>>,
+) {
+    if !display.is_changed() && *text_color == Some(rsvp.text_color) {
+        return;
+    }
+    *text_color = Some(rsvp.text_color);
+
+    let segments = &display.segments;
+    for (span, mut text, mut color, mut font) in spans.iter_mut() {
+        let i = span.0;
+        let (content, base, bold) = match segments.get(i) {
+            // Overflow: fold every remaining segment into the last span
+            Some(seg) if i + 1 == READER_SPAN_COUNT && segments.len() > READER_SPAN_COUNT => (
+                segments[i..].iter().map(|s| s.text.as_str()).collect::<String>(),
+                seg.color,
+                seg.bold,
+            ),
+            Some(seg) => (seg.text.clone(), seg.color, seg.bold),
+            None => (String::new(), None, false),
+        };
+
+        if text.0 != content {
+            text.0 = content;
+        }
+        let face = match &rsvp.bold_font_handle {
+            Some(bold_face) if bold => bold_face,
+            _ => &rsvp.current_font_handle,
+        };
+        if font.font != *face {
+            font.font = face.clone();
+        }
+        let base = base.unwrap_or(rsvp.text_color());
+        color.0 = base.with_alpha(base.alpha() * display.alpha);
+    }
+}
+
+/// The reader as a plugin, for embedding it in another Bevy app: its state, the UI and every
+/// system, with fonts loaded from `fonts/` in the app's asset folder. The app supplies
+/// `DefaultPlugins` (or the parts it needs); egui is added unless it already is.
+pub struct RsvpReaderPlugin;
+
+impl Plugin for RsvpReaderPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
         }
+        app
+            .init_resource::<RsvpState>()
+            .init_resource::<WordFade>()
+            .init_resource::<ReaderDisplay>()
+            .init_resource::<OverlayMessage>()
+            .init_resource::<ReadingGoal>()
+            .init_resource::<FontPreviews>()
+            .init_resource::<PanelLayout>()
+            .init_resource::<DocumentPrefs>()
+            .init_resource::<Onboarding>()
+            .init_resource::<GutenbergFetch>()
+            .init_resource::<LazyPdf>()
+            .init_resource::<TextStream>()
+            .init_resource::<PdfExtraction>()
+            .init_resource::<AdaptiveSpeed>()
+            .init_resource::<Calibration>()
+            .init_resource::<Definitions>()
+            .init_resource::<TrainingPlan>()
+            .init_resource::<ComprehensionCheck>()
+            .init_resource::<FocusStreak>()
+            .init_resource::<IdlePause>()
+            .init_resource::<Toasts>()
+            .add_systems(Startup, setup)
+            .configure_sets(Update, (ReaderSet::Input, ReaderSet::Load, ReaderSet::Tick, ReaderSet::Present).chain())
+            .add_systems(Update, (
+                (
+                    // Mailboxes filled from JS and async tasks
+                    (
+                        file_listener_system,
+                        idle_message_system,
+                        word_durations_system,
+                        cues_system,
+                        seek_command_system,
+                        js_command_system,
+                        gutenberg_fetch_system,
+                        bookmark_import_system,
+                    ),
+                    ui_controls_system,
+                    onboarding_system,
+                    (calibration_system, definitions_system),
+                    (keyboard_input_system, gamepad_input_system),
+                    idle_pause_system.after(ui_controls_system).after(keyboard_input_system).after(gamepad_input_system),
+                    (overlay_message_system, toast_system, wpm_suggestion_system, focus_mode_system),
+                ).in_set(ReaderSet::Input),
+                (lazy_pdf_system, text_stream_system, custom_font_system).in_set(ReaderSet::Load),
+                rsvp_tick_system.in_set(ReaderSet::Tick),
+                (
+                    comprehension_system,
+                    (wpm_history_system, focus_streak_system),
+                    (reading_goal_system, training_plan_system),
+                    adaptive_speed_system,
+                    document_prefs_system,
+                    (register_font_previews_system, font_fallback_system),
+                    word_fade_system,
+                    reader_render_system.after(word_fade_system),
+                    context_lines_system,
+                    reader_layout_system,
+                    (state_snapshot_system, live_region_system),
+                    word_event_system,
+                ).in_set(ReaderSet::Present),
+            ));
+
+        app.add_systems(
+            PostUpdate,
+            pivot_anchor_system
+                .after(bevy::ui::UiSystem::PostLayout)
+                .after(bevy::transform::TransformSystem::TransformPropagate),
+        );
     }
 }
 
-#[wasm_bindgen(start)]
+/// Starts the reader with default settings.
+#[wasm_bindgen]
 pub fn start() {
+    run_app(StartOptions::default());
+}
+
+/// Starts the reader with an options object, e.g.
+/// `{ wpm: 400, font: "Tinos-Regular.ttf", theme: "light", minimalUi: true }`.
+#[wasm_bindgen]
+pub fn start_with_options(options: JsValue) -> Result<(), JsValue> {
+    let options: StartOptions = serde_wasm_bindgen::from_value(options)?;
+    run_app(options);
+    Ok(())
+}
+
+fn run_app(options: StartOptions) {
     console_error_panic_hook::set_once();
+    #[cfg(target_arch = "wasm32")]
+    install_paste_listener();
 
-    App::new()
+    let mut app = App::new();
+    app
         .add_plugins(DefaultPlugins
             .set(WindowPlugin {
                 primary_window: Some(Window {
                     canvas: Some("#bevy-canvas".into()),
                     fit_canvas_to_parent: true,
+                    // Natively, reopen at the size of the last run
+                    #[cfg(not(target_arch = "wasm32"))]
+                    resolution: window_size::saved_resolution().unwrap_or_default(),
                     ..default()
                 }),
                 ..default()
@@ -291,9 +4780,783 @@ pub fn start() {
                 ..default()
             })
         )
-        .add_plugins(EguiPlugin)
-        .init_resource::<RsvpState>()
-        .add_systems(Startup, setup)
-        .add_systems(Update, (file_listener_system, ui_controls_system, rsvp_tick_system))
-        .run();
-}
\ No newline at end of file
+        .add_plugins(RsvpReaderPlugin);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_systems(Update, window_size::window_size_system);
+
+    options.apply(app.world_mut());
+    app.run();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    /// A reader with one page per entry of `pages` open, nothing on screen yet.
+    fn reader(pages: &[&str]) -> (RsvpState, WordFade, ReaderDisplay) {
+        let mut rsvp = RsvpState::default();
+        let mut fade = WordFade::default();
+        let mut display = ReaderDisplay::default();
+        open_document(&mut rsvp, &mut fade, &mut display, pages.iter().map(|p| words(p)).collect(), Vec::new());
+        (rsvp, fade, display)
+    }
+
+    fn shown(display: &ReaderDisplay) -> String {
+        display.segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    /// Each chunk `advance` puts on screen until the document ends.
+    fn read_through(rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay) -> Vec<String> {
+        let mut flashes = Vec::new();
+        while !rsvp.finished {
+            let before = rsvp.displayed_chunk;
+            advance(rsvp, fade, display);
+            if let Some(pending) = fade.pending.take() {
+                display.set(pending);
+            }
+            // Turning the page is a step of its own, with the old chunk still up
+            if rsvp.displayed_chunk != before {
+                flashes.push(shown(display));
+            }
+        }
+        flashes
+    }
+
+    // Tests that go through the JS mailboxes (statics) take turns
+    static MAILBOXES: Mutex<()> = Mutex::new(());
+
+    fn lock_mailboxes() -> std::sync::MutexGuard<'static, ()> {
+        MAILBOXES.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// A PDF with one page per entry of `pages`, each a single line of text.
+    fn test_pdf(pages: &[&str]) -> Vec<u8> {
+        pdf_with_page_ids(pages, false)
+    }
+
+    /// `test_pdf`, with the page objects numbered last page first when `reversed`.
+    fn pdf_with_page_ids(pages: &[&str], reversed: bool) -> Vec<u8> {
+        use lopdf::content::{Content, Operation};
+        use lopdf::{dictionary, Object, Stream};
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! { "Type" => "Font", "Subtype" => "Type1", "BaseFont" => "Courier" });
+        let resources_id = doc.add_object(dictionary! { "Font" => dictionary! { "F1" => font_id } });
+        let mut page_ids: Vec<_> = pages.iter().map(|_| doc.new_object_id()).collect();
+        if reversed {
+            page_ids.reverse();
+        }
+        let mut kids: Vec<Object> = Vec::new();
+        for (text, &page_id) in pages.iter().zip(&page_ids) {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                    Operation::new("Td", vec![72.into(), 720.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(*text)]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            doc.objects.insert(
+                page_id,
+                Object::Dictionary(dictionary! { "Type" => "Page", "Parent" => pages_id, "Contents" => content_id }),
+            );
+            kids.push(page_id.into());
+        }
+        let count = kids.len() as i64;
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => count,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Just enough of the app to take uploads through `file_listener_system`.
+    fn upload_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<RsvpState>()
+            .init_resource::<WordFade>()
+            .init_resource::<ReaderDisplay>()
+            .init_resource::<DocumentPrefs>()
+            .init_resource::<LazyPdf>()
+            .init_resource::<TextStream>()
+            .init_resource::<PdfExtraction>()
+            .init_resource::<Toasts>()
+            .add_systems(Update, file_listener_system);
+        app
+    }
+
+    /// `reader` playing in a world whose `Update` runs `rsvp_tick_system` in the plugin's
+    /// `ReaderSet` order, with no countdown or rewind on the first tick.
+    fn playback_world(pages: &[&str]) -> World {
+        let (rsvp, fade, display) = reader(pages);
+        let rsvp = RsvpState { show_countdown: false, rewind_on_resume: 0, ..rsvp };
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        world.insert_resource(rsvp);
+        world.insert_resource(fade);
+        world.insert_resource(display);
+        let mut schedule = Schedule::new(Update);
+        schedule.configure_sets((ReaderSet::Input, ReaderSet::Load, ReaderSet::Tick, ReaderSet::Present).chain());
+        schedule.add_systems(rsvp_tick_system.in_set(ReaderSet::Tick));
+        world.add_schedule(schedule);
+        world
+    }
+
+    /// One frame of `ms` milliseconds.
+    fn tick(world: &mut World, ms: u64) {
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(ms));
+        world.run_schedule(Update);
+    }
+
+    fn duration_ms(rsvp: &RsvpState, word: &str) -> f32 {
+        word_duration_secs(rsvp, word, None, WordStyle::default()) * 1000.0
+    }
+
+    #[test]
+    fn word_durations_stay_within_the_clamps() {
+        let mut rsvp = RsvpState {
+            min_word_ms: 100.0,
+            max_word_ms: 400.0,
+            timing_model: TimingModel::Length,
+            long_word_extra_ms: 300.0,
+            // 67 ms a word, under the floor
+            effective_wpm: 900.0,
+            ..default()
+        };
+        assert_eq!(duration_ms(&rsvp, "a"), 100.0);
+        // 40 letters: +160% for length and the long-word pause, over the ceiling
+        rsvp.effective_wpm = 60.0;
+        let long_word = "x".repeat(40);
+        assert_eq!(duration_ms(&rsvp, &long_word), 400.0);
+
+        // In between, the clamps leave the time alone
+        rsvp.effective_wpm = 300.0;
+        assert_eq!(duration_ms(&rsvp, "word"), 200.0);
+    }
+
+    #[test]
+    fn a_ceiling_below_the_floor_gives_way_to_it() {
+        let rsvp = RsvpState { min_word_ms: 300.0, max_word_ms: 100.0, ..default() };
+        assert_eq!(duration_ms(&rsvp, "a"), 300.0);
+        assert_eq!(duration_ms(&rsvp, &"x".repeat(40)), 300.0);
+    }
+
+    #[test]
+    fn trailing_punctuation_classes() {
+        let cases = [
+            ("end.", Punctuation::Sentence),
+            ("really?", Punctuation::Sentence),
+            ("stop!", Punctuation::Sentence),
+            ("so…", Punctuation::Sentence),
+            ("said.\"", Punctuation::Sentence),
+            ("(aside.)", Punctuation::Sentence),
+            ("first,", Punctuation::Clause),
+            ("then;", Punctuation::Clause),
+            ("note:", Punctuation::Clause),
+            ("“quoted,”", Punctuation::Clause),
+            ("wait—", Punctuation::Dash),
+            ("pages–", Punctuation::Dash),
+            ("typed--", Punctuation::Dash),
+            ("word", Punctuation::None),
+            ("3.14", Punctuation::None),
+            ("", Punctuation::None),
+        ];
+        for (word, class) in cases {
+            assert_eq!(trailing_punctuation(word), class, "{:?}", word);
+        }
+    }
+
+    #[test]
+    fn each_punctuation_class_has_its_own_pause() {
+        let rsvp = RsvpState {
+            effective_wpm: 600.0,
+            sentence_pause_multiplier: 3.0,
+            clause_pause_multiplier: 2.0,
+            dash_pause_multiplier: 1.5,
+            ..default()
+        };
+        assert_eq!(duration_ms(&rsvp, "word"), 100.0);
+        assert_eq!(duration_ms(&rsvp, "end."), 300.0);
+        assert_eq!(duration_ms(&rsvp, "first,"), 200.0);
+        assert_eq!(duration_ms(&rsvp, "wait—"), 150.0);
+    }
+
+    #[test]
+    fn long_numbers_get_extra_time() {
+        let mut rsvp = RsvpState { effective_wpm: 600.0, numeric_multiplier: 1.5, ..default() };
+        assert_eq!(duration_ms(&rsvp, "1,234,567"), 100.0);
+
+        rsvp.numeric_slowdown = true;
+        // Seven digits: the base strength plus 10% for each of the four past three
+        assert!((duration_ms(&rsvp, "1,234,567") - 100.0 * 1.5 * 1.4).abs() < 0.01);
+        assert_eq!(duration_ms(&rsvp, "42"), 150.0);
+        // Mostly letters, so not a number
+        assert_eq!(duration_ms(&rsvp, "covid19"), 100.0);
+        assert_eq!(numeric_digit_count("3.14%"), 3);
+    }
+
+    #[test]
+    fn smart_chunks_stop_at_a_sentence_end() {
+        let (mut rsvp, mut fade, mut display) = reader(&["The story's end. Next one"]);
+        rsvp.words_per_frame = 2;
+        rsvp.smart_chunking = true;
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["The story's", "end.", "Next one"]);
+
+        let (mut rsvp, mut fade, mut display) = reader(&["The story's end. Next one"]);
+        rsvp.words_per_frame = 2;
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["The story's", "end. Next", "one"]);
+    }
+
+    #[test]
+    fn end_shows_the_last_word_and_home_the_first() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two", "three four"]);
+        jump_to_end(&mut rsvp, &mut display, &mut fade);
+        assert_eq!(shown(&display), "four");
+        assert!(!rsvp.finished);
+        // One more step finishes the document, with the last word left up
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert!(rsvp.finished);
+        assert_eq!(shown(&display), "four");
+
+        jump_to(&mut rsvp, &mut display, &mut fade, 0, 0);
+        assert_eq!(shown(&display), "one");
+        assert!(!rsvp.finished);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "two");
+    }
+
+    #[test]
+    fn a_new_upload_cancels_a_pdf_still_being_extracted() {
+        let _mailboxes = lock_mailboxes();
+        let mut app = upload_app();
+        let long: Vec<String> = (0..120).map(|i| format!("page{}", i)).collect();
+        let long: Vec<&str> = long.iter().map(String::as_str).collect();
+
+        queue_upload("long.pdf", &test_pdf(&long));
+        app.update();
+        assert!(app.world().resource::<PdfExtraction>().is_active());
+        assert!(!app.world().resource::<RsvpState>().is_playing);
+
+        // Arrives before the long PDF's extraction is done
+        queue_upload("short.txt", b"the second upload");
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(!app.world().resource::<PdfExtraction>().is_active());
+        let rsvp = app.world().resource::<RsvpState>();
+        assert_eq!(rsvp.pages, vec![words("the second upload")]);
+        assert!(rsvp.is_playing);
+    }
+
+    #[test]
+    fn a_long_pdf_opens_once_every_page_is_extracted() {
+        let _mailboxes = lock_mailboxes();
+        let mut app = upload_app();
+        let long: Vec<String> = (0..120).map(|i| format!("word{}", i)).collect();
+        let long: Vec<&str> = long.iter().map(String::as_str).collect();
+        // "word" on every page would be taken for a running header
+        app.world_mut().resource_mut::<RsvpState>().clean_extracted_text = false;
+
+        queue_upload("long.pdf", &test_pdf(&long));
+        app.update();
+        app.update();
+        assert!(shown(app.world().resource::<ReaderDisplay>()).contains("/120"));
+        for _ in 0..10 {
+            app.update();
+        }
+        let rsvp = app.world().resource::<RsvpState>();
+        assert_eq!(rsvp.pages.len(), 120);
+        assert_eq!(rsvp.pages[119], words("word119"));
+        assert!(rsvp.is_playing);
+    }
+
+    #[test]
+    fn a_one_word_document_reads_normally() {
+        let (mut rsvp, mut fade, mut display) = reader(&["Hello."]);
+        assert_eq!((page_progress(&rsvp), document_progress(&rsvp)), (0.0, (0.0, 1)));
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["Hello."]);
+        assert_eq!(shown(&display), "Hello.");
+        assert_eq!((page_progress(&rsvp), document_progress(&rsvp)), (1.0, (1.0, 0)));
+
+        // Seeking around one word stays on it
+        step_words(&mut rsvp, &mut display, &mut fade, 5);
+        step_words(&mut rsvp, &mut display, &mut fade, -5);
+        assert_eq!(current_position(&rsvp), (0, 0));
+        jump_to_end(&mut rsvp, &mut display, &mut fade);
+        assert_eq!(shown(&display), "Hello.");
+    }
+
+    #[test]
+    fn a_one_page_document_reads_normally() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two three four"]);
+        advance(&mut rsvp, &mut fade, &mut display);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(page_progress(&rsvp), 0.5);
+        assert_eq!(document_progress(&rsvp), (0.25, 3));
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["three", "four"]);
+        assert_eq!(rsvp.current_page_index, 0);
+    }
+
+    #[test]
+    fn progress_of_an_empty_document_is_a_number() {
+        let (mut rsvp, _, _) = reader(&[]);
+        assert_eq!((page_progress(&rsvp), document_progress(&rsvp)), (0.0, (0.0, 0)));
+        rsvp.finished = true;
+        assert_eq!(document_progress(&rsvp), (1.0, 0));
+    }
+
+    #[test]
+    fn changing_the_chunk_size_mid_read_keeps_the_place() {
+        let (mut rsvp, mut fade, mut display) = reader(&["a b c d e f g h", "i j k l"]);
+        rsvp.words_per_frame = 3;
+        advance(&mut rsvp, &mut fade, &mut display);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "d e f");
+
+        rsvp.words_per_frame = 1;
+        rechunk_in_place(&mut rsvp, &mut display, &mut fade);
+        assert_eq!(shown(&display), "d");
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "e");
+
+        rsvp.words_per_frame = 4;
+        rsvp.smart_chunking = true;
+        rechunk_in_place(&mut rsvp, &mut display, &mut fade);
+        assert_eq!(shown(&display), "e f g h");
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["i j k l"]);
+    }
+
+    #[test]
+    fn the_dwell_is_only_recomputed_when_its_inputs_change() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two three"]);
+        advance(&mut rsvp, &mut fade, &mut display);
+        let mut recomputed = 0;
+        let mut frame = |rsvp: &mut RsvpState| recomputed += sync_dwell(rsvp) as u32;
+
+        for _ in 0..10 {
+            frame(&mut rsvp);
+        }
+        rsvp.effective_wpm = 400.0;
+        for _ in 0..10 {
+            frame(&mut rsvp);
+        }
+        advance(&mut rsvp, &mut fade, &mut display);
+        frame(&mut rsvp);
+        frame(&mut rsvp);
+        // The first chunk, the speed change and the next chunk
+        assert_eq!(recomputed, 3);
+    }
+
+    #[test]
+    fn only_words_past_the_threshold_get_the_long_word_pause() {
+        let rsvp = RsvpState { effective_wpm: 600.0, long_word_len_threshold: 8, long_word_extra_ms: 250.0, ..default() };
+        assert_eq!(duration_ms(&rsvp, "abcdefg"), 100.0);
+        assert_eq!(duration_ms(&rsvp, "abcdefgh"), 100.0);
+        assert_eq!(duration_ms(&rsvp, "abcdefghi"), 350.0);
+        // Letters are counted, not the punctuation around them
+        assert_eq!(duration_ms(&rsvp, "“abcdefgh”"), 100.0);
+
+        // Added after the multipliers, not scaled by them
+        let rsvp = RsvpState { sentence_pause_multiplier: 2.0, ..rsvp };
+        assert_eq!(duration_ms(&rsvp, "abcdefghi."), 450.0);
+    }
+
+    #[test]
+    fn tiny_words_ride_along_with_the_next_one() {
+        let (mut rsvp, mut fade, mut display) = reader(&["a quick brown fox ran out of a barn."]);
+        rsvp.merge_tiny_words = true;
+        let flashes = read_through(&mut rsvp, &mut fade, &mut display);
+        // "of a" keeps going; "out" is past the two-letter limit
+        assert_eq!(flashes, ["a quick", "brown", "fox", "ran", "out", "of a barn."]);
+        // Merged chunks still count every word they hold
+        assert_eq!(rsvp.words_advanced, 9);
+
+        let (mut rsvp, mut fade, mut display) = reader(&["a quick brown fox"]);
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display), ["a", "quick", "brown", "fox"]);
+    }
+
+    #[test]
+    fn mixed_direction_chunks_keep_the_page_direction_and_a_logical_pivot() {
+        let (mut rsvp, _, _) = reader(&["في عام PDF3 كان"]);
+        rsvp.orp_highlight = true;
+        // Opens with a Latin word inside an Arabic page
+        let segments = chunk_segments(&rsvp, 0, 2, 4);
+        assert_eq!(segments[0].text, "\u{200F}");
+        // The pivot is picked on the characters as stored, not as laid out
+        let pivot = segments.iter().find(|s| s.color == Some(PIVOT_COLOR)).unwrap();
+        assert_eq!(pivot.text, split_at_pivot("PDF3", rsvp.orp_ratio).1);
+
+        let segments = chunk_segments(&rsvp, 0, 0, 2);
+        assert_ne!(segments[0].text, "\u{200F}");
+    }
+
+    #[test]
+    fn back_returns_to_where_a_jump_left_from() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two three", "four five six"]);
+        advance(&mut rsvp, &mut fade, &mut display);
+        advance(&mut rsvp, &mut fade, &mut display);
+        // Reading on doesn't go on the history
+        assert!(rsvp.nav_history.is_empty());
+
+        jump_to(&mut rsvp, &mut display, &mut fade, 1, 2);
+        assert_eq!(shown(&display), "six");
+        jump_to_end(&mut rsvp, &mut display, &mut fade);
+        // Each jump stacks the place it left
+        assert_eq!(rsvp.nav_history, vec![(0, 1), (1, 2)]);
+
+        assert!(go_back(&mut rsvp, &mut display, &mut fade));
+        assert!(go_back(&mut rsvp, &mut display, &mut fade));
+        assert_eq!(current_position(&rsvp), (0, 1));
+        assert_eq!(shown(&display), "two");
+        assert!(!go_back(&mut rsvp, &mut display, &mut fade));
+    }
+
+    #[test]
+    fn line_mode_flashes_a_stanza_line_by_line() {
+        let verse = "I wandered lonely as a cloud\nThat floats on high o'er vales and hills\n\nWhen all at once I saw a crowd\nA host of golden daffodils\n";
+        let (pages, styles) = parse_plain_text(verse, 500);
+        let mut rsvp = RsvpState { chunk_mode: ChunkMode::Lines, ..default() };
+        let mut fade = WordFade::default();
+        let mut display = ReaderDisplay::default();
+        open_document(&mut rsvp, &mut fade, &mut display, pages, styles);
+        assert_eq!(
+            read_through(&mut rsvp, &mut fade, &mut display),
+            [
+                "I wandered lonely as a cloud",
+                "That floats on high o'er vales and hills",
+                "When all at once I saw a crowd",
+                "A host of golden daffodils",
+            ]
+        );
+
+        // The line closing the stanza holds a little longer than the one opening the next
+        let stanza_end = chunk_duration_secs(&rsvp, 0, 6, 14);
+        let next_line = chunk_duration_secs(&rsvp, 0, 14, 22);
+        assert!(stanza_end > next_line);
+    }
+
+    #[test]
+    fn pdf_pages_follow_the_page_tree_not_object_numbers() {
+        let bytes = pdf_with_page_ids(&["first page", "second page", "third page"], true);
+        let parsed = parse_pdf(bytes, PdfLoadMode::Eager, false).unwrap();
+        assert_eq!(parsed.pages, vec![words("first page"), words("second page"), words("third page")]);
+    }
+
+    /// A two-page reader with `behavior` at page ends, advanced to the end of the first page.
+    fn at_first_page_end(behavior: EndOfPageBehavior) -> (RsvpState, WordFade, ReaderDisplay) {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two", "three four"]);
+        rsvp.end_of_page = behavior;
+        advance(&mut rsvp, &mut fade, &mut display);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "two");
+        (rsvp, fade, display)
+    }
+
+    #[test]
+    fn auto_advance_turns_the_page_and_reads_on() {
+        let (mut rsvp, mut fade, mut display) = at_first_page_end(EndOfPageBehavior::AutoAdvance);
+        advance(&mut rsvp, &mut fade, &mut display);
+        // The turn is a step of its own
+        assert_eq!((rsvp.current_page_index, shown(&display).as_str()), (1, "two"));
+        assert!(rsvp.is_playing);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "three");
+    }
+
+    #[test]
+    fn pause_each_page_stops_until_play() {
+        let (mut rsvp, mut fade, mut display) = at_first_page_end(EndOfPageBehavior::PauseEachPage);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(rsvp.current_page_index, 1);
+        assert!(!rsvp.is_playing && rsvp.waiting_at_page);
+        assert_eq!(shown(&display), "Page 2");
+
+        rsvp.is_playing = true;
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(shown(&display), "three");
+        // Only page ends stop, not the document's end
+        advance(&mut rsvp, &mut fade, &mut display);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert!(rsvp.finished);
+        assert_eq!(shown(&display), "four");
+    }
+
+    #[test]
+    fn continuous_reads_straight_onto_the_next_page() {
+        let (mut rsvp, mut fade, mut display) = at_first_page_end(EndOfPageBehavior::Continuous);
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!((rsvp.current_page_index, shown(&display).as_str()), (1, "three"));
+        assert!(rsvp.is_playing);
+    }
+
+    #[test]
+    fn a_slow_frame_advances_once_per_dwell_it_covers() {
+        let mut world = playback_world(&["ab cd ef gh ij kl mn op qr st uv wx yz"]);
+        // 300 WPM: 200ms a word
+        tick(&mut world, 1);
+        tick(&mut world, 1000);
+        let rsvp = world.resource::<RsvpState>();
+        assert_eq!(rsvp.words_advanced, 5);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "ij");
+
+        // A stalled tab catches up by at most a frame's cap and drops the rest
+        tick(&mut world, 60_000);
+        let rsvp = world.resource::<RsvpState>();
+        assert_eq!(rsvp.words_advanced, 5 + u64::from(MAX_ADVANCES_PER_FRAME));
+        assert!(rsvp.timer.elapsed() <= rsvp.timer.duration());
+    }
+
+    #[test]
+    fn the_last_word_stays_on_screen_at_the_end() {
+        let mut world = playback_world(&["One page.", "The end."]);
+        {
+            // Both would otherwise blank the screen just as the document runs out
+            let mut rsvp = world.resource_mut::<RsvpState>();
+            rsvp.sentence_gap = true;
+            rsvp.page_transition_pause_ms = 300.0;
+        }
+        for i in 0..READER_SPAN_COUNT {
+            world.spawn((TextSpan::default(), TextFont::default(), TextColor(TEXT_COLOR), ReaderSpan(i)));
+        }
+        world.schedule_scope(Update, |_, schedule| {
+            schedule.add_systems(reader_render_system.in_set(ReaderSet::Present));
+        });
+
+        for _ in 0..50 {
+            tick(&mut world, 100);
+        }
+        let rsvp = world.resource::<RsvpState>();
+        assert!(rsvp.finished && !rsvp.is_playing);
+        let mut spans: Vec<_> = world.query::<(&ReaderSpan, &TextSpan)>().iter(&world).map(|(s, t)| (s.0, t.0.clone())).collect();
+        spans.sort();
+        assert_eq!(spans.into_iter().map(|(_, text)| text).collect::<String>(), "end.");
+    }
+
+    #[test]
+    fn sentence_mode_flashes_a_paragraph_sentence_by_sentence() {
+        let paragraph = "It was late. The rain had stopped, and the streets were quiet! Was anyone there? Nobody came";
+        let (mut rsvp, mut fade, mut display) = reader(&[paragraph]);
+        rsvp.chunk_mode = ChunkMode::Sentences;
+        assert_eq!(
+            read_through(&mut rsvp, &mut fade, &mut display),
+            ["It was late.", "The rain had stopped, and the streets were quiet!", "Was anyone there?", "Nobody came"]
+        );
+
+        // Each sentence's dwell is its words' times added up, so the pace holds
+        let short = chunk_duration_secs(&rsvp, 0, 0, 3);
+        let long = chunk_duration_secs(&rsvp, 0, 3, 12);
+        assert!(long > short * 2.0);
+
+        // A run-on with no terminator is split so it still fits
+        let run_on = vec!["and"; MAX_SENTENCE_WORDS + 5].join(" ");
+        let (mut rsvp, mut fade, mut display) = reader(&[&run_on]);
+        rsvp.chunk_mode = ChunkMode::Sentences;
+        assert_eq!(read_through(&mut rsvp, &mut fade, &mut display).len(), 2);
+    }
+
+    #[test]
+    fn a_seek_mid_fade_leaves_a_clean_state() {
+        let _lock = lock_mailboxes();
+        let mut world = playback_world(&["w0 w1 w2 w3 w4 w5 w6 w7"]);
+        {
+            let mut rsvp = world.resource_mut::<RsvpState>();
+            rsvp.fade_transitions = true;
+            rsvp.fade_ms = 200.0;
+            rsvp.wpm = 60.0;
+            rsvp.effective_wpm = 60.0;
+        }
+        world.schedule_scope(Update, |_, schedule| {
+            schedule.add_systems((seek_command_system.in_set(ReaderSet::Input), word_fade_system.in_set(ReaderSet::Present)));
+        });
+        while world.resource::<WordFade>().phase == FadePhase::Idle {
+            tick(&mut world, 16);
+        }
+        tick(&mut world, 16);
+        assert!(world.resource::<ReaderDisplay>().alpha < 1.0);
+
+        seek(5);
+        tick(&mut world, 16);
+        let fade = world.resource::<WordFade>();
+        assert!(fade.phase == FadePhase::Idle && fade.pending.is_none());
+        let display = world.resource::<ReaderDisplay>();
+        assert_eq!((shown(display).as_str(), display.alpha), ("w5", 1.0));
+        let rsvp = world.resource::<RsvpState>();
+        assert_eq!(rsvp.displayed_chunk, Some((0, 5, 6)));
+        assert!(rsvp.timer.elapsed().is_zero() && !rsvp.in_sentence_gap);
+
+        // The target word gets its whole dwell from the next frame on
+        tick(&mut world, 16);
+        assert_eq!(world.resource::<RsvpState>().timer.elapsed(), Duration::from_millis(16));
+    }
+
+    #[test]
+    fn the_same_upload_again_is_only_parsed_once() {
+        let _mailboxes = lock_mailboxes();
+        let mut app = upload_app();
+        let bytes = b"uploaded twice by a change event that fires again";
+        queue_upload("twice.txt", bytes);
+        app.update();
+        {
+            let mut rsvp = app.world_mut().resource_mut::<RsvpState>();
+            rsvp.current_word_index = 4;
+            // Only a fresh parse would bring "uploaded" back
+            rsvp.pages[0][0] = "marker".to_string();
+        }
+
+        queue_upload("twice.txt", bytes);
+        app.update();
+        let rsvp = app.world().resource::<RsvpState>();
+        assert_eq!((rsvp.pages[0][0].as_str(), rsvp.current_word_index), ("marker", 4));
+
+        // A setting that changes the parse is a reason to upload it again
+        app.world_mut().resource_mut::<RsvpState>().math_cleanup = true;
+        queue_upload("twice.txt", bytes);
+        app.update();
+        assert_eq!(app.world().resource::<RsvpState>().pages[0][0], "uploaded");
+    }
+
+    /// The chunks shown over `steps` calls to `advance`, page turns included.
+    fn advance_by(rsvp: &mut RsvpState, fade: &mut WordFade, display: &mut ReaderDisplay, steps: usize) -> Vec<String> {
+        (0..steps)
+            .map(|_| {
+                advance(rsvp, fade, display);
+                shown(display)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_loop_repeats_the_page_without_recounting_it() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two", "three four"]);
+        rsvp.loop_mode = LoopMode::Page;
+        assert_eq!(advance_by(&mut rsvp, &mut fade, &mut display, 5), ["one", "two", "one", "two", "one"]);
+        assert_eq!((rsvp.current_page_index, rsvp.loop_passes), (0, 2));
+        assert!(rsvp.is_playing && !rsvp.finished);
+        assert_eq!(rsvp.words_advanced, 2);
+
+        rsvp.count_looped_words = true;
+        advance(&mut rsvp, &mut fade, &mut display);
+        assert_eq!(rsvp.words_advanced, 3);
+    }
+
+    #[test]
+    fn document_loop_starts_over_at_the_end() {
+        let (mut rsvp, mut fade, mut display) = reader(&["one two", "three four"]);
+        rsvp.loop_mode = LoopMode::Document;
+        assert_eq!(
+            advance_by(&mut rsvp, &mut fade, &mut display, 6),
+            ["one", "two", "two", "three", "four", "one"]
+        );
+        assert_eq!((rsvp.current_page_index, rsvp.loop_passes), (0, 1));
+        assert!(rsvp.is_playing && !rsvp.finished);
+        assert_eq!(rsvp.words_advanced, 4);
+        // Progress goes back to the start with the reader
+        assert_eq!(document_progress(&rsvp), (0.0, 4));
+    }
+
+    #[test]
+    fn a_seek_wins_over_the_tick_in_the_same_frame() {
+        let _lock = lock_mailboxes();
+        let mut world = playback_world(&["w0 w1 w2 w3 w4 w5 w6 w7"]);
+        world.schedule_scope(Update, |_, schedule| {
+            schedule.add_systems(seek_command_system.in_set(ReaderSet::Input));
+        });
+        // 300 WPM: 200ms a word
+        tick(&mut world, 1);
+        tick(&mut world, 200);
+        tick(&mut world, 150);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w0");
+
+        // Long enough to tick past the target if the tick ran on after the seek
+        seek(5);
+        tick(&mut world, 500);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w5");
+        assert_eq!(world.resource::<RsvpState>().displayed_chunk, Some((0, 5, 6)));
+        tick(&mut world, 16);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w5");
+    }
+
+    #[test]
+    fn text_that_isnt_utf8_still_opens() {
+        let _mailboxes = lock_mailboxes();
+        let mut app = upload_app();
+        // Latin-1 "café crème", which isn't valid UTF-8
+        queue_upload("latin1.txt", b"caf\xe9 cr\xe8me");
+        app.update();
+        assert_eq!(app.world().resource::<RsvpState>().pages, vec![words("caf\u{FFFD} cr\u{FFFD}me")]);
+    }
+
+    #[test]
+    fn unnamed_text_that_isnt_utf8_still_opens() {
+        let rsvp = RsvpState::default();
+        let upload = Upload { name: String::new(), bytes: b"caf\xe9 cr\xe8me".to_vec() };
+        let parsed = parse_upload(upload, &rsvp).unwrap();
+        assert_eq!(parsed.pages, vec![words("caf\u{FFFD} cr\u{FFFD}me")]);
+        assert!(parsed.not_utf8);
+
+        // Bytes that aren't text in any encoding are still refused
+        let upload = Upload { name: String::new(), bytes: (0x80..=0xFF).collect() };
+        assert!(matches!(parse_upload(upload, &rsvp), Err(ParseError::NotAPdf(_))));
+    }
+
+    #[test]
+    fn a_speed_change_mid_word_keeps_the_share_of_the_dwell_already_had() {
+        let mut world = playback_world(&["w0 w1 w2 w3"]);
+        // 300 WPM: 200ms a word, half of w0's had when the speed doubles
+        tick(&mut world, 1);
+        tick(&mut world, 200);
+        tick(&mut world, 99);
+        {
+            let mut rsvp = world.resource_mut::<RsvpState>();
+            rsvp.wpm = 600.0;
+            rsvp.effective_wpm = 600.0;
+        }
+        tick(&mut world, 1);
+        let rsvp = world.resource::<RsvpState>();
+        let rounded_ms = |d: Duration| (d.as_secs_f64() * 1000.0).round();
+        assert_eq!((rounded_ms(rsvp.timer.elapsed()), rounded_ms(rsvp.timer.duration())), (51.0, 100.0));
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w0");
+
+        // The other half plays at the new pace
+        tick(&mut world, 47);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w0");
+        tick(&mut world, 3);
+        assert_eq!(shown(world.resource::<ReaderDisplay>()), "w1");
+
+        let ms = Duration::from_millis;
+        assert_eq!(rescaled_elapsed(ms(150), ms(200), ms(400)), ms(300));
+        assert_eq!(rescaled_elapsed(ms(50), Duration::ZERO, ms(40)), ms(40));
+    }
+
+    #[test]
+    fn semicolons_and_colons_pause_like_a_sentence_end() {
+        let rsvp = RsvpState { effective_wpm: 600.0, ..default() };
+        assert_eq!(duration_ms(&rsvp, "word"), 100.0);
+        assert_eq!(duration_ms(&rsvp, "first;"), 200.0);
+        assert_eq!(duration_ms(&rsvp, "follows:"), 200.0);
+        assert_eq!(duration_ms(&rsvp, "(aside;)"), 200.0);
+        assert_eq!(duration_ms(&rsvp, "first,"), 150.0);
+        // Still clauses everywhere else
+        assert_eq!(trailing_punctuation("first;"), Punctuation::Clause);
+    }
+}
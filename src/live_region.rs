@@ -0,0 +1,100 @@
+// Screen reader announcements. The reader draws into a canvas that assistive tech can't
+// see into, so a visually hidden aria-live region in the page carries a text version of
+// where the reader is: page and percentage while playing, plus the sentence on screen once
+// paused. Updates during playback are throttled; a screen reader interrupted every few
+// seconds can't be listened to.
+
+use bevy::prelude::*;
+
+use crate::i18n::tr;
+use crate::{current_position, trailing_punctuation, Punctuation, RsvpState};
+
+// While playing, the region changes at most this often
+const PLAYING_INTERVAL_SECS: f32 = 15.0;
+// Longest sentence read out when paused
+const MAX_SENTENCE_WORDS: usize = 60;
+
+#[cfg(target_arch = "wasm32")]
+const REGION_ID: &str = "rsvp-live-region";
+
+#[derive(Default)]
+pub struct LiveRegion {
+    text: String,
+    since_update: f32,
+}
+
+pub fn live_region_system(time: Res<Time>, rsvp: Res<RsvpState>, mut region: Local<LiveRegion>) {
+    region.since_update += time.delta_secs();
+    if !rsvp.announce_progress || !rsvp.document_loaded || rsvp.total_words == 0 {
+        return;
+    }
+    if rsvp.is_playing && region.since_update < PLAYING_INTERVAL_SECS {
+        return;
+    }
+
+    let text = announcement(&rsvp);
+    if text != region.text {
+        set_region_text(&text);
+        region.text = text;
+        region.since_update = 0.0;
+    }
+}
+
+fn announcement(rsvp: &RsvpState) -> String {
+    let lang = rsvp.lang;
+    if rsvp.finished {
+        return tr(lang, "Finished").to_string();
+    }
+    let (page, word) = current_position(rsvp);
+    let percent = (rsvp.flat_index(page, word) * 100 / rsvp.total_words.max(1)).min(100);
+    let progress = format!(
+        "{} {} {} {}, {} {}",
+        tr(lang, "Page"),
+        page + 1,
+        tr(lang, "of"),
+        rsvp.pages.len(),
+        percent,
+        tr(lang, "percent")
+    );
+    if rsvp.is_playing {
+        return progress;
+    }
+    format!("{}. {}. {}", tr(lang, "Paused"), progress, current_sentence(rsvp, page, word))
+}
+
+/// The sentence around `word` on `page`, cut short at `MAX_SENTENCE_WORDS` either side.
+fn current_sentence(rsvp: &RsvpState, page: usize, word: usize) -> String {
+    let Some(words) = rsvp.pages.get(page) else { return String::new() };
+    let ends = |i: usize| trailing_punctuation(&words[i]) == Punctuation::Sentence || rsvp.word_style(page, i).paragraph_end;
+    let word = word.min(words.len().saturating_sub(1));
+    let earliest = word.saturating_sub(MAX_SENTENCE_WORDS);
+    let latest = words.len().min(word + MAX_SENTENCE_WORDS);
+    let start = (earliest..word).rev().find(|&i| ends(i)).map_or(earliest, |i| i + 1);
+    let end = (word..latest).find(|&i| ends(i)).map_or(latest, |i| i + 1);
+    words[start..end].join(" ")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn set_region_text(text: &str) {
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else { return };
+    if document.get_element_by_id(REGION_ID).is_none() {
+        let Some(body) = document.body() else { return };
+        // Off-screen rather than display:none, which would hide it from screen readers too
+        let html = format!(
+            "<div id=\"{}\" role=\"status\" aria-live=\"polite\" aria-atomic=\"true\" \
+             style=\"position:absolute;left:-10000px;width:1px;height:1px;overflow:hidden\"></div>",
+            REGION_ID
+        );
+        let _ = body.insert_adjacent_html("beforeend", &html);
+    }
+    if let Some(region) = document.get_element_by_id(REGION_ID).and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok()) {
+        region.set_inner_text(text);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn set_region_text(text: &str) {
+    debug!("Live region: {}", text);
+}
@@ -0,0 +1,41 @@
+// Markdown documents: syntax is stripped so only readable text is flashed, while headings,
+// emphasis and paragraph breaks carry over as word styles for the pacing rules.
+// Each top-level heading starts a new page.
+
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+
+use crate::styled_text::StyledTextBuilder;
+use crate::WordStyle;
+
+/// Splits Markdown into pages of readable words plus a parallel style for each word.
+pub fn parse_markdown(text: &str) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let mut b = StyledTextBuilder::default();
+
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                if level == HeadingLevel::H1 {
+                    b.end_page();
+                }
+                b.flush_word();
+                b.heading = true;
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                b.end_block();
+                b.heading = false;
+            }
+            Event::Start(Tag::Strong) => b.strong_depth += 1,
+            Event::End(TagEnd::Strong) => b.strong_depth = b.strong_depth.saturating_sub(1),
+            Event::Start(Tag::Emphasis) => b.emphasis_depth += 1,
+            Event::End(TagEnd::Emphasis) => b.emphasis_depth = b.emphasis_depth.saturating_sub(1),
+            // Alt text of images isn't part of the prose
+            Event::Start(Tag::Image { .. }) => b.hidden_depth += 1,
+            Event::End(TagEnd::Image) => b.hidden_depth = b.hidden_depth.saturating_sub(1),
+            Event::End(TagEnd::Paragraph | TagEnd::Item | TagEnd::CodeBlock | TagEnd::TableRow) => b.end_block(),
+            Event::Text(text) | Event::Code(text) => b.push_text(&text),
+            Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::TableCell) => b.flush_word(),
+            _ => {}
+        }
+    }
+    b.finish()
+}
@@ -0,0 +1,219 @@
+// Cleanup for math and chemistry that PDF extraction has pulled apart. Text extracted from
+// scientific papers often puts subscripts and superscripts in tokens of their own ("H ₂ O",
+// "x 2 + y 2") and glues operators onto their operands ("∑xᵢ"). This pass puts the pieces
+// of a formula back into one token and gives big operators a token of their own, so the
+// reader shows "H₂O" and "x²" whole and stops on "∑" long enough to take it in.
+
+use crate::WordStyle;
+
+// Symbols that read as a word of their own: big operators, relations, set and logic signs
+const MATH_SYMBOLS: &[char] = &[
+    '∑', '∏', '∫', '∮', '√', '∞', '∂', '∇', '∆', '≈', '≠', '≡', '≤', '≥', '≪', '≫', '±', '∓', '×', '÷', '∝',
+    '∈', '∉', '∋', '⊂', '⊃', '⊆', '⊇', '∪', '∩', '∅', '∀', '∃', '∄', '¬', '∧', '∨', '→', '←', '↔', '⇒', '⇐',
+    '⇔', '↦', '∘', '⊕', '⊗', '⊥', '∥', '∠', '°',
+];
+// Operators extraction tends to glue to the operand after them
+const PREFIX_OPERATORS: &[char] = &['∑', '∏', '∫', '∮', '√', '∂', '∇', '∀', '∃', '¬'];
+// Plain-ASCII operators, which only count as math between tokens, never inside a word
+const ASCII_OPERATORS: &[&str] = &["=", "+", "-", "<", ">", "/", "*", "^"];
+
+fn to_subscript(c: char) -> Option<char> {
+    let digit = c.to_digit(10)?;
+    char::from_u32(0x2080 + digit)
+}
+
+fn to_superscript(c: char) -> Option<char> {
+    match c {
+        '1' => Some('¹'),
+        '2' => Some('²'),
+        '3' => Some('³'),
+        _ => char::from_u32(0x2070 + c.to_digit(10)?),
+    }
+}
+
+fn is_script_char(c: char) -> bool {
+    matches!(c, '⁰' | '¹' | '²' | '³' | '⁴'..='⁹' | '⁺' | '⁻' | '⁼' | '⁽' | '⁾' | 'ⁿ' | 'ⁱ' | '₀'..='₎' | 'ₐ'..='ₜ')
+}
+
+/// Whether `word` is nothing but math symbols (and the ASCII operators around them), the kind
+/// of token that warrants extra dwell.
+pub fn is_math_symbol(word: &str) -> bool {
+    word.chars().any(|c| MATH_SYMBOLS.contains(&c))
+        && word.chars().all(|c| MATH_SYMBOLS.contains(&c) || "=+-<>/*^()".contains(c))
+}
+
+fn is_operator(word: &str) -> bool {
+    is_math_symbol(word) || ASCII_OPERATORS.contains(&word)
+}
+
+// A sub- or superscript detached from what it belongs to, on its own or leading the rest of
+// a formula ("₂O")
+fn is_detached_script(word: &str) -> bool {
+    let rest = word.trim_start_matches(is_script_char);
+    rest.len() < word.len() && (rest.is_empty() || is_formula_part(rest))
+}
+
+// An element symbol or a run of them with counts, like "H", "CO", "NaCl" or "C6H12"
+fn is_formula_part(word: &str) -> bool {
+    let mut chars = word.chars().peekable();
+    let mut elements = 0;
+    while let Some(c) = chars.next() {
+        if !c.is_ascii_uppercase() {
+            return false;
+        }
+        if chars.peek().is_some_and(char::is_ascii_lowercase) {
+            chars.next();
+        }
+        while chars.peek().is_some_and(|c| c.is_ascii_digit() || is_script_char(*c)) {
+            chars.next();
+        }
+        elements += 1;
+    }
+    elements > 0 && word.len() <= 8
+}
+
+fn is_single_digit(word: &str) -> bool {
+    word.len() == 1 && word.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_variable(word: &str) -> bool {
+    word.chars().count() == 1 && word.chars().all(|c| c.is_alphabetic())
+}
+
+/// Splits a big operator glued to its operand ("∑xᵢ") into two tokens.
+fn split_prefix_operator(word: &str) -> Option<(String, String)> {
+    let first = word.chars().next()?;
+    let rest = &word[first.len_utf8()..];
+    (PREFIX_OPERATORS.contains(&first) && rest.chars().next().is_some_and(char::is_alphanumeric))
+        .then(|| (first.to_string(), rest.to_string()))
+}
+
+/// One page of cleanup. `styles` may be shorter than `words`; a merged token keeps the style
+/// of its first piece, with the paragraph and line ends of its last.
+fn clean_math_page(words: Vec<String>, styles: Vec<WordStyle>) -> (Vec<String>, Vec<WordStyle>) {
+    let styled = styles.len();
+    let mut out_words: Vec<String> = Vec::with_capacity(words.len());
+    let mut out_styles: Vec<WordStyle> = Vec::with_capacity(styled);
+    let merge_last = |out_words: &mut Vec<String>, out_styles: &mut Vec<WordStyle>, piece: &str, i: usize| {
+        if let Some(last) = out_words.last_mut() {
+            last.push_str(piece);
+        }
+        if let (Some(last), Some(style)) = (out_styles.last_mut(), styles.get(i)) {
+            last.paragraph_end = style.paragraph_end;
+            last.line_end = style.line_end;
+        }
+    };
+
+    // Whether the last token was just given a subscript, so an element after it continues the formula
+    let mut in_formula = false;
+    for (i, word) in words.iter().enumerate() {
+        let prev = out_words.last().map(String::as_str);
+        let next = words.get(i + 1).map(String::as_str);
+        // Only merge within the same paragraph, so a heading's number stays put
+        let joinable = i > 0 && !styles.get(i - 1).is_some_and(|s| s.paragraph_end || s.line_end);
+
+        if joinable && is_detached_script(word) && prev.is_some_and(|p| p.chars().last().is_some_and(char::is_alphanumeric)) {
+            // "H" "₂O", "x" "²"
+            merge_last(&mut out_words, &mut out_styles, word, i);
+            in_formula = word.chars().last().is_some_and(|c| matches!(c, '₀'..='₉'));
+            continue;
+        }
+        if joinable && is_single_digit(word) && prev.is_some_and(is_formula_part) && prev.is_some_and(|p| p.len() > 1 || next.is_some_and(is_formula_part)) {
+            // "H 2 O" and "CO 2", but not "I 2" or "A 4" on their own
+            let sub: String = word.chars().filter_map(to_subscript).collect();
+            merge_last(&mut out_words, &mut out_styles, &sub, i);
+            in_formula = true;
+            continue;
+        }
+        let before_prev = out_words.len().checked_sub(2).and_then(|j| out_words.get(j)).map(String::as_str);
+        if joinable && is_single_digit(word) && prev.is_some_and(is_variable) && (before_prev.is_some_and(is_operator) || next.is_some_and(is_operator)) {
+            // "x 2 + y 2" is x² + y²
+            let sup: String = word.chars().filter_map(to_superscript).collect();
+            merge_last(&mut out_words, &mut out_styles, &sup, i);
+            in_formula = false;
+            continue;
+        }
+        // The rest of a formula split at its subscript: "H₂" "O"
+        if joinable && in_formula && is_formula_part(word) {
+            merge_last(&mut out_words, &mut out_styles, word, i);
+            continue;
+        }
+        in_formula = false;
+
+        match split_prefix_operator(word) {
+            Some((operator, operand)) => {
+                out_words.push(operator);
+                out_words.push(operand);
+                if let Some(style) = styles.get(i) {
+                    out_styles.push(WordStyle { paragraph_end: false, line_end: false, ..*style });
+                    out_styles.push(*style);
+                }
+            }
+            None => {
+                out_words.push(word.clone());
+                if let Some(style) = styles.get(i) {
+                    out_styles.push(*style);
+                }
+            }
+        }
+    }
+    (out_words, out_styles)
+}
+
+/// `clean_math_page` over a whole document; `styles` may have fewer pages than `pages`.
+pub fn clean_math_pages(pages: Vec<Vec<String>>, styles: Vec<Vec<WordStyle>>) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+    let page_count = styles.len();
+    let mut styles = styles.into_iter();
+    let (pages, mut cleaned_styles): (Vec<_>, Vec<_>) =
+        pages.into_iter().map(|words| clean_math_page(words, styles.next().unwrap_or_default())).unzip();
+    cleaned_styles.truncate(page_count);
+    (pages, cleaned_styles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(text: &str) -> Vec<String> {
+        let words = text.split(' ').map(String::from).collect();
+        clean_math_page(words, Vec::new()).0
+    }
+
+    #[test]
+    fn detached_subscripts_rejoin_their_formula() {
+        assert_eq!(clean("water is H ₂O here"), ["water", "is", "H₂O", "here"]);
+        assert_eq!(clean("H ₂ O"), ["H₂O"]);
+        assert_eq!(clean("exhaled CO 2 gas"), ["exhaled", "CO₂", "gas"]);
+        assert_eq!(clean("H 2 O"), ["H₂O"]);
+    }
+
+    #[test]
+    fn digits_after_a_variable_become_powers_only_in_a_formula() {
+        assert_eq!(clean("x 2 + y 2 = r 2"), ["x²", "+", "y²", "=", "r²"]);
+        // Prose that merely looks like it stays as it is
+        assert_eq!(clean("part I 2 of A 4 paper"), ["part", "I", "2", "of", "A", "4", "paper"]);
+    }
+
+    #[test]
+    fn big_operators_get_a_token_of_their_own() {
+        assert_eq!(clean("the sum ∑xᵢ over i"), ["the", "sum", "∑", "xᵢ", "over", "i"]);
+        assert!(is_math_symbol("∑") && is_math_symbol("≤") && is_math_symbol("(≠)"));
+        assert!(!is_math_symbol("=") && !is_math_symbol("x≤y"));
+    }
+
+    #[test]
+    fn merges_stop_at_line_and_paragraph_ends() {
+        let words = ["Chapter", "H", "2"].map(String::from).to_vec();
+        let styles = vec![WordStyle::default(), WordStyle { paragraph_end: true, ..WordStyle::default() }, WordStyle::default()];
+        let (words, styles) = clean_math_page(words, styles);
+        assert_eq!(words, ["Chapter", "H", "2"]);
+        assert_eq!(styles.len(), 3);
+
+        let words = ["H", "₂O"].map(String::from).to_vec();
+        let styles = vec![WordStyle::default(), WordStyle { line_end: true, ..WordStyle::default() }];
+        let (words, styles) = clean_math_page(words, styles);
+        // The merged token ends the line its last piece did
+        assert_eq!(words, ["H₂O"]);
+        assert!(styles[0].line_end);
+    }
+}
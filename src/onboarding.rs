@@ -0,0 +1,82 @@
+// First-run tutorial: a small stepped window explaining the reader, shown until the user
+// finishes or skips it once.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::i18n::tr;
+use crate::{open_document, storage, ReaderDisplay, RsvpState, WordFade};
+
+const STORAGE_KEY: &str = "rsvp_onboarding_done";
+
+const STEPS: &[&str] = &[
+    "Welcome! Words are shown one at a time in the center of the screen.",
+    "Upload a PDF with the button above the reader, or try the sample text.",
+    "Use the Speed slider, or keys 1–5, to choose how many words per minute you read.",
+    "The red letter marks the focus point. Keep your eyes on it and let the words come to you.",
+];
+
+const SAMPLE_TEXT: &str = "Rapid serial visual presentation shows one word at a time in the same place. \
+    Because your eyes no longer travel across the line, you can read faster with less effort. \
+    Start at a comfortable speed, then raise it a little each day.";
+
+/// Current tutorial step, or `None` once it has been dismissed.
+#[derive(Resource)]
+pub struct Onboarding {
+    step: Option<usize>,
+}
+
+impl Default for Onboarding {
+    fn default() -> Self {
+        let done = storage::load(STORAGE_KEY).is_some();
+        Self { step: if done { None } else { Some(0) } }
+    }
+}
+
+impl Onboarding {
+    fn dismiss(&mut self) {
+        self.step = None;
+        storage::save(STORAGE_KEY, "1");
+    }
+}
+
+pub fn onboarding_system(
+    mut contexts: EguiContexts,
+    mut onboarding: ResMut<Onboarding>,
+    mut rsvp: ResMut<RsvpState>,
+    mut fade: ResMut<WordFade>,
+    mut display: ResMut<ReaderDisplay>,
+) {
+    let Some(step) = onboarding.step else { return };
+    let lang = rsvp.lang;
+
+    // Anchored low and non-modal so the reader stays visible and usable behind it
+    egui::Window::new(tr(lang, "Getting started"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr(lang, STEPS[step]));
+            ui.label(format!("{} / {}", step + 1, STEPS.len()));
+
+            ui.horizontal(|ui| {
+                if step == 1 && ui.button(tr(lang, "Load sample")).clicked() {
+                    let words = SAMPLE_TEXT.split_whitespace().map(String::from).collect();
+                    open_document(&mut rsvp, &mut fade, &mut display, vec![words], Vec::new());
+                }
+                if step > 0 && ui.button(tr(lang, "Back")).clicked() {
+                    onboarding.step = Some(step - 1);
+                }
+                if step + 1 < STEPS.len() {
+                    if ui.button(tr(lang, "Next")).clicked() {
+                        onboarding.step = Some(step + 1);
+                    }
+                    if ui.button(tr(lang, "Skip")).clicked() {
+                        onboarding.dismiss();
+                    }
+                } else if ui.button(tr(lang, "Done")).clicked() {
+                    onboarding.dismiss();
+                }
+            });
+        });
+}
@@ -0,0 +1,91 @@
+// Page furniture in extracted PDF text: running headers and footers, and bare page numbers.
+// Read in a stream they flash up as stray words between every pair of pages. lopdf ends a
+// line at each text object, which is how layout programs set them apart from the body, so
+// they show up as whole lines at a page's top or bottom edge. Only those edge lines are
+// looked at: a page number has to stand alone on its line, and a header has to recur on a
+// good share of the pages, so body text that happens to start or end a page stays put.
+
+use std::collections::HashMap;
+
+// Lines examined at each edge of a page (a header can sit above or below the page number)
+const EDGE_LINES: usize = 2;
+// A running header recurs on at least this many pages, and this share of them
+const MIN_HEADER_PAGES: usize = 3;
+const MIN_HEADER_SHARE: f32 = 0.3;
+// Headers are a title or a name, not a sentence
+const MAX_HEADER_WORDS: usize = 12;
+
+/// Whether `line` is nothing but a page number: "12", "- 12 -", "Page 12", "12 of 300", "[12]".
+fn is_page_number_line(line: &str) -> bool {
+    let line = line.trim().to_lowercase();
+    let line = line.trim_matches(|c: char| c.is_whitespace() || "-–—|·•[]()".contains(c));
+    let line = ["page ", "pg. ", "pg ", "p. "].iter().find_map(|p| line.strip_prefix(p)).unwrap_or(line).trim();
+    let number = match line.split_once(" of ").or_else(|| line.split_once('/')) {
+        Some((number, total)) if is_short_number(total.trim()) => number.trim(),
+        _ => line,
+    };
+    is_short_number(number)
+}
+
+fn is_short_number(s: &str) -> bool {
+    (1..=4).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// What a header line has in common across pages: its words without the page numbers mixed
+/// into many of them. `None` for lines too short or too long to be a header.
+fn header_key(line: &str) -> Option<String> {
+    let key: String = line.chars().filter(|c| !c.is_ascii_digit()).collect::<String>().to_lowercase();
+    let words: Vec<&str> = key.split_whitespace().collect();
+    let letters = key.chars().filter(|c| c.is_alphabetic()).count();
+    (letters >= 3 && words.len() <= MAX_HEADER_WORDS).then(|| words.join(" "))
+}
+
+/// Indices of the non-blank lines within EDGE_LINES of the top, and of the bottom (lowest
+/// first). A short page's lines can be in both.
+fn edge_lines(lines: &[&str]) -> (Vec<usize>, Vec<usize>) {
+    let content: Vec<usize> = (0..lines.len()).filter(|&i| !lines[i].trim().is_empty()).collect();
+    let top = content.iter().take(EDGE_LINES).copied().collect();
+    let bottom = content.iter().rev().take(EDGE_LINES).copied().collect();
+    (top, bottom)
+}
+
+/// `text` with the edge lines `is_furniture` picks out dropped. Lines are only dropped
+/// working in from an edge, so a page number in the middle of a page is kept.
+fn strip_edges(text: &str, is_furniture: impl Fn(&str) -> bool) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let (top, bottom) = edge_lines(&lines);
+    let mut dropped = vec![false; lines.len()];
+    for edge in [top, bottom] {
+        for i in edge.into_iter().take_while(|&i| is_furniture(lines[i])) {
+            dropped[i] = true;
+        }
+    }
+    lines.iter().zip(dropped).filter(|(_, dropped)| !dropped).map(|(line, _)| *line).collect::<Vec<_>>().join("\n")
+}
+
+/// One page on its own (pages extracted as they're reached): only page numbers can be told
+/// apart without the other pages to compare with.
+pub fn strip_page_numbers(text: &str) -> String {
+    strip_edges(text, is_page_number_line)
+}
+
+/// Every page of a document: page numbers, and header or footer lines that recur across pages.
+pub fn strip_furniture(texts: &mut [String]) {
+    let mut pages_with: HashMap<String, usize> = HashMap::new();
+    for text in texts.iter() {
+        let lines: Vec<&str> = text.lines().collect();
+        let (top, bottom) = edge_lines(&lines);
+        let mut keys: Vec<String> = top.into_iter().chain(bottom).filter_map(|i| header_key(lines[i])).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            *pages_with.entry(key).or_default() += 1;
+        }
+    }
+    let min_pages = MIN_HEADER_PAGES.max((texts.len() as f32 * MIN_HEADER_SHARE).ceil() as usize);
+    let is_header = |line: &str| header_key(line).is_some_and(|key| pages_with.get(&key).is_some_and(|&n| n >= min_pages));
+
+    for text in texts.iter_mut() {
+        *text = strip_edges(text, |line| is_page_number_line(line) || is_header(line));
+    }
+}
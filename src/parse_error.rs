@@ -0,0 +1,89 @@
+// Why a document couldn't be loaded. The built-in UI only needs the message for its toast,
+// but an embedding page wants something it can branch on (to show its own localized text,
+// or offer a retry after a network failure), so every failure in the upload and Gutenberg
+// paths ends up as one of these and is also reported to the `set_on_error` callback.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::Serialize;
+
+// Replacement characters above this share of a text upload mean it isn't UTF-8 at all
+// (another encoding, or a binary file), rather than UTF-8 with a few stray bytes
+const MAX_REPLACEMENT_SHARE: f32 = 0.05;
+// Past this share it isn't text in any encoding: random bytes decode about half to
+// replacement characters, while accented prose in Latin-1 stays well under it
+const BINARY_REPLACEMENT_SHARE: f32 = 0.3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Not any format the reader knows, and not a PDF either.
+    NotAPdf(String),
+    /// A PDF that needs a password to read.
+    Encrypted,
+    /// Opened fine but has no words to show.
+    NoText,
+    /// A PDF whose pages have no text layer at all, most likely scanned images.
+    ImageOnly,
+    /// Fetching a book failed, with what went wrong.
+    NetworkError(String),
+    /// A recognized format that's damaged or uses something the parsers don't handle.
+    Unsupported(String),
+}
+
+/// What the `set_on_error` callback receives.
+#[derive(Serialize)]
+pub struct ErrorReport {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Stable identifier for embedders; unlike the message it never changes wording.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::NotAPdf(_) => "NOT_A_PDF",
+            ParseError::Encrypted => "ENCRYPTED",
+            ParseError::NoText => "NO_TEXT",
+            ParseError::ImageOnly => "IMAGE_ONLY",
+            ParseError::NetworkError(_) => "NETWORK_ERROR",
+            ParseError::Unsupported(_) => "UNSUPPORTED",
+        }
+    }
+
+    pub fn report(&self) -> ErrorReport {
+        ErrorReport { code: self.code(), message: self.to_string() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Encrypted => write!(f, "The PDF is password-protected"),
+            ParseError::NoText => write!(f, "Document contained no text"),
+            ParseError::ImageOnly => write!(f, "No selectable text found; this may be a scanned PDF"),
+            ParseError::NotAPdf(detail) | ParseError::NetworkError(detail) | ParseError::Unsupported(detail) => {
+                write!(f, "{}", detail)
+            }
+        }
+    }
+}
+
+fn replacement_share(text: &str) -> f32 {
+    let chars = text.chars().count().max(1);
+    let replaced = text.chars().filter(|&c| c == char::REPLACEMENT_CHARACTER).count();
+    replaced as f32 / chars as f32
+}
+
+/// `bytes` as text with invalid bytes replaced, and whether so much of it was invalid that
+/// it isn't really UTF-8 and will read partly as garbage.
+pub fn decode_text(bytes: &[u8]) -> (Cow<'_, str>, bool) {
+    let text = String::from_utf8_lossy(bytes);
+    let mostly_invalid = matches!(&text, Cow::Owned(decoded) if replacement_share(decoded) > MAX_REPLACEMENT_SHARE);
+    (text, mostly_invalid)
+}
+
+/// Whether decoded `text` is a binary file rather than text in some other encoding.
+pub fn looks_binary(text: &str) -> bool {
+    replacement_share(text) > BINARY_REPLACEMENT_SHARE
+}
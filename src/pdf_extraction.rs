@@ -0,0 +1,84 @@
+// Extracting a large PDF's text over several frames. Pulling the text out of a page takes a
+// few milliseconds, so doing a thousand-page book in one go freezes the tab for seconds.
+// Loading modes that extract every page on upload hand long documents over here instead: a
+// batch of pages is extracted each frame while the reader shows how far along it is, and the
+// upload is opened once the last page is in.
+
+use bevy::prelude::*;
+use lopdf::Document;
+
+use crate::lazy_pdf::{extract_page_text, PdfLoadMode};
+use crate::{finish_pdf, ParseError, ParsedDocument, UploadId};
+
+// Documents with more pages than this are extracted across frames
+const FRAME_THRESHOLD_PAGES: usize = 50;
+const PAGES_PER_FRAME: usize = 20;
+
+pub fn should_extract_in_frames(page_count: usize) -> bool {
+    page_count > FRAME_THRESHOLD_PAGES
+}
+
+/// A PDF whose pages are still to be extracted, with what's needed to finish loading it.
+pub struct PdfJob {
+    doc: Document,
+    page_numbers: Vec<u32>,
+    texts: Vec<String>,
+    mode: PdfLoadMode,
+    clean: bool,
+}
+
+impl PdfJob {
+    pub fn new(doc: Document, page_numbers: Vec<u32>, mode: PdfLoadMode, clean: bool) -> Self {
+        let texts = Vec::with_capacity(page_numbers.len());
+        Self { doc, page_numbers, texts, mode, clean }
+    }
+}
+
+struct Pending {
+    job: PdfJob,
+    // Everything but the pages, filled in up front (e.g. the outline)
+    parsed: ParsedDocument,
+    upload_id: UploadId,
+    name: String,
+}
+
+/// Finished extraction: the parse result, the upload it belongs to and its display name.
+pub type Extracted = (Result<ParsedDocument, ParseError>, UploadId, String);
+
+#[derive(Resource, Default)]
+pub struct PdfExtraction {
+    pending: Option<Pending>,
+}
+
+impl PdfExtraction {
+    pub fn start(&mut self, job: PdfJob, parsed: ParsedDocument, upload_id: UploadId, name: String) {
+        self.pending = Some(Pending { job, parsed, upload_id, name });
+    }
+
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Pages extracted so far and the document's page count.
+    pub fn progress(&self) -> (usize, usize) {
+        self.pending.as_ref().map_or((0, 0), |p| (p.job.texts.len(), p.job.page_numbers.len()))
+    }
+
+    /// Extracts the next batch of pages; once the last one is in, the finished document.
+    pub fn step(&mut self) -> Option<Extracted> {
+        let job = &mut self.pending.as_mut()?.job;
+        let done = job.texts.len();
+        let batch = job.page_numbers[done..].iter().take(PAGES_PER_FRAME);
+        let texts: Vec<String> = batch.map(|&page_num| extract_page_text(&job.doc, page_num)).collect();
+        job.texts.extend(texts);
+        if job.texts.len() < job.page_numbers.len() {
+            return None;
+        }
+        let Pending { job, parsed, upload_id, name } = self.pending.take()?;
+        Some((finish_pdf(parsed, job.texts, &job.page_numbers, job.mode, job.clean), upload_id, name))
+    }
+}
@@ -0,0 +1,40 @@
+// Finding text in the loaded document. Words are compared without case and without the
+// punctuation stuck to them, so "whale" finds "Whale," and "whale's". A query of several
+// words matches them as a phrase on consecutive words of one page; its first word may end
+// a longer word and its last may begin one, the way a single word matches anywhere inside one.
+
+/// Lowercased, with punctuation trimmed from both ends.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+fn matches_at(words: &[String], terms: &[String]) -> bool {
+    let last = terms.len() - 1;
+    terms.iter().zip(words).enumerate().all(|(i, (term, word))| {
+        let word = normalize(word);
+        match (i == 0, i == last) {
+            (true, true) => word.contains(term.as_str()),
+            (true, false) => word.ends_with(term.as_str()),
+            (false, true) => word.starts_with(term.as_str()),
+            (false, false) => word == *term,
+        }
+    })
+}
+
+/// (page, word) of the first word of each case-insensitive match of `query`, in reading
+/// order. Empty for a query with no letters or digits.
+pub fn find_matches(pages: &[Vec<String>], query: &str) -> Vec<(usize, usize)> {
+    let terms: Vec<String> = query.split_whitespace().map(normalize).filter(|t| !t.is_empty()).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for (page, words) in pages.iter().enumerate() {
+        for start in 0..words.len().saturating_sub(terms.len() - 1) {
+            if matches_at(&words[start..], &terms) {
+                matches.push((page, start));
+            }
+        }
+    }
+    matches
+}
@@ -0,0 +1,36 @@
+// Session reading statistics: words shown and time spent actually reading since the page
+// loaded (or the last reset), and the average speed those add up to. Time only runs while
+// playback does, so pauses, the countdown and a finished document don't water the average down.
+
+use bevy_egui::egui;
+
+use crate::i18n::{tr, Lang};
+
+#[derive(Default)]
+pub struct ReadingStats {
+    // Counted like `RsvpState::words_advanced`, so looped words only with `count_looped_words`
+    pub words: u64,
+    pub active_secs: f64,
+}
+
+impl ReadingStats {
+    /// Words per minute over the active time; zero before any has passed.
+    pub fn average_wpm(&self) -> f64 {
+        if self.active_secs <= 0.0 {
+            return 0.0;
+        }
+        self.words as f64 * 60.0 / self.active_secs
+    }
+}
+
+pub fn stats_controls(ui: &mut egui::Ui, stats: &mut ReadingStats, lang: Lang) {
+    egui::CollapsingHeader::new(tr(lang, "Reading statistics")).show(ui, |ui| {
+        let secs = stats.active_secs as u64;
+        ui.label(format!("{}: {}", tr(lang, "Words read"), stats.words));
+        ui.label(format!("{}: {}:{:02}:{:02}", tr(lang, "Reading time"), secs / 3600, secs / 60 % 60, secs % 60));
+        ui.label(format!("{}: {:.0} WPM", tr(lang, "Average speed"), stats.average_wpm()));
+        if ui.button(tr(lang, "Reset stats")).clicked() {
+            *stats = ReadingStats::default();
+        }
+    });
+}
@@ -0,0 +1,67 @@
+// Browser localStorage access. Native builds have no browser, so values live in a map
+// that is mirrored to a JSON file in the working directory instead.
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, value);
+    }
+}
+
+/// Local calendar date as "YYYY-MM-DD".
+#[cfg(target_arch = "wasm32")]
+pub fn today() -> String {
+    let now = js_sys::Date::new_0();
+    format!("{:04}-{:02}-{:02}", now.get_full_year(), now.get_month() + 1, now.get_date())
+}
+
+// Written through on every save and read back on the next run
+#[cfg(not(target_arch = "wasm32"))]
+const NATIVE_STORAGE_FILE: &str = "rsvp_reader_storage.json";
+
+#[cfg(not(target_arch = "wasm32"))]
+static MEMORY: std::sync::LazyLock<std::sync::Mutex<std::collections::HashMap<String, String>>> =
+    std::sync::LazyLock::new(|| {
+        // Tests start from empty storage and never touch the file
+        let saved = if cfg!(test) { None } else { std::fs::read_to_string(NATIVE_STORAGE_FILE).ok() };
+        std::sync::Mutex::new(saved.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default())
+    });
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(key: &str) -> Option<String> {
+    MEMORY.lock().ok()?.get(key).cloned()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(key: &str, value: &str) {
+    let Ok(mut map) = MEMORY.lock() else { return };
+    if map.get(key).map(String::as_str) == Some(value) {
+        return;
+    }
+    map.insert(key.to_string(), value.to_string());
+    if cfg!(test) {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(&*map) {
+        let _ = std::fs::write(NATIVE_STORAGE_FILE, json);
+    }
+}
+
+/// UTC date as a day number; good enough to detect a day change natively.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format!("day-{}", secs / 86_400)
+}
@@ -0,0 +1,49 @@
+// Focus streak: how many words have played since the reader last paused or moved the
+// position by hand. Rather than resetting from every pause and seek call site, the count
+// follows the chunks on screen: one that doesn't pick up where the last left off (a seek,
+// a rewind, a page slider drag) or a stop in playback starts it over. Loop wraps continue it.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::i18n::tr;
+use crate::{bottom_inset, RsvpState};
+
+#[derive(Resource, Default)]
+pub struct FocusStreak {
+    words: usize,
+    // Flat index just past the last chunk counted, and the loop pass it was on
+    expected_start: Option<usize>,
+    loop_passes: u32,
+}
+
+pub fn focus_streak_system(mut contexts: EguiContexts, rsvp: Res<RsvpState>, mut streak: ResMut<FocusStreak>) {
+    if !rsvp.is_playing {
+        *streak = FocusStreak::default();
+    } else if let Some((page, start, end)) = rsvp.displayed_chunk {
+        let first = rsvp.flat_index(page, start);
+        let follows = streak.expected_start.is_none_or(|e| e == first) || streak.loop_passes != rsvp.loop_passes;
+        let already_counted = streak.expected_start == Some(rsvp.flat_index(page, end));
+        if !already_counted {
+            streak.words = if follows { streak.words + (end - start) } else { 0 };
+            streak.expected_start = Some(rsvp.flat_index(page, end));
+            streak.loop_passes = rsvp.loop_passes;
+        }
+    }
+
+    if !rsvp.show_streak || streak.words == 0 {
+        return;
+    }
+    let lang = rsvp.lang;
+    let ctx = contexts.ctx_mut();
+    egui::Area::new(egui::Id::new("focus_streak"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -10.0 - bottom_inset(ctx)])
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!("{} {}", streak.words, tr(lang, "words without a break")))
+                    .small()
+                    .color(egui::Color32::from_gray(160)),
+            );
+        });
+}
@@ -0,0 +1,176 @@
+// Shared by the markup parsers: turns a stream of text runs and markup state into pages
+// of words with a parallel style for each word.
+
+use std::borrow::Cow;
+
+use crate::WordStyle;
+
+#[derive(Default)]
+pub struct StyledTextBuilder {
+    pages: Vec<Vec<String>>,
+    styles: Vec<Vec<WordStyle>>,
+    words: Vec<String>,
+    word_styles: Vec<WordStyle>,
+    // Text events can split a word (`foo*bar*`), so characters collect here until whitespace
+    pending: String,
+    pending_style: WordStyle,
+    // Markup the parser is currently inside; applied to each character pushed
+    pub heading: bool,
+    pub strong_depth: usize,
+    pub emphasis_depth: usize,
+    // Inside content that isn't prose (image alt text, scripts): text is dropped
+    pub hidden_depth: usize,
+    // Start a new page after this many words (0 = pages only end where the format says)
+    pub max_page_words: usize,
+}
+
+/// Characters that render as nothing but survive `split_whitespace` (soft hyphens, a BOM at
+/// the start of a page, zero-width spaces, stray control codes). Unicode spaces such as
+/// no-break and ideographic ones are whitespace already, so they split words as usual.
+fn is_invisible(c: char) -> bool {
+    c.is_control() || matches!(c, '\u{00AD}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
+
+/// Invisible characters that mean nothing inside a word either. Zero-width (non-)joiners
+/// are kept there: they shape emoji sequences and Persian and Indic words.
+fn is_stray_inside(c: char) -> bool {
+    is_invisible(c) && !matches!(c, '\u{200C}' | '\u{200D}')
+}
+
+/// A token without invisible characters at its edges or stray ones inside (extraction leaves
+/// zero-width spaces and control codes mid-word, which show as boxes); punctuation and
+/// accents are left alone. Empty means the token should be dropped.
+pub fn clean_token(word: &str) -> Cow<'_, str> {
+    let word = word.trim_matches(is_invisible);
+    if word.contains(is_stray_inside) {
+        Cow::Owned(word.chars().filter(|&c| !is_stray_inside(c)).collect())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+impl StyledTextBuilder {
+    pub fn with_page_limit(max_page_words: usize) -> Self {
+        Self { max_page_words, ..Default::default() }
+    }
+
+    pub fn push_text(&mut self, text: &str) {
+        if self.hidden_depth > 0 {
+            return;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.flush_word();
+            } else {
+                self.pending.push(c);
+                self.pending_style.heading |= self.heading;
+                self.pending_style.bold |= self.strong_depth > 0;
+                self.pending_style.italic |= self.emphasis_depth > 0;
+            }
+        }
+    }
+
+    pub fn flush_word(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let style = std::mem::take(&mut self.pending_style);
+        let word = clean_token(&pending);
+        if word.is_empty() {
+            return;
+        }
+        self.words.push(word.into_owned());
+        self.word_styles.push(style);
+        if self.max_page_words > 0 && self.words.len() >= self.max_page_words {
+            self.end_page();
+        }
+    }
+
+    /// Closes a paragraph, heading or list item: the last word gets the paragraph pause.
+    pub fn end_block(&mut self) {
+        self.flush_word();
+        if let Some(last) = self.word_styles.last_mut() {
+            last.paragraph_end = true;
+        }
+    }
+
+    /// Marks a line break in the source: the last word ends a line for line mode.
+    pub fn end_line(&mut self) {
+        self.flush_word();
+        if let Some(last) = self.word_styles.last_mut() {
+            last.line_end = true;
+        }
+    }
+
+    pub fn end_page(&mut self) {
+        self.flush_word();
+        if !self.words.is_empty() {
+            self.pages.push(std::mem::take(&mut self.words));
+            self.styles.push(std::mem::take(&mut self.word_styles));
+        }
+    }
+
+    /// Hands over the pages completed so far, keeping the one still being filled.
+    pub fn take_pages(&mut self) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+        (std::mem::take(&mut self.pages), std::mem::take(&mut self.styles))
+    }
+
+    /// Closes the last page and returns every page with its parallel styles.
+    pub fn finish(mut self) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+        self.end_page();
+        (self.pages, self.styles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soft_hyphens_and_byte_order_marks_are_trimmed() {
+        assert_eq!(clean_token("\u{00AD}"), "");
+        assert_eq!(clean_token("\u{FEFF}Chapter"), "Chapter");
+        assert_eq!(clean_token("word\u{00AD}"), "word");
+        assert_eq!(clean_token("co\u{00AD}operate"), "cooperate");
+    }
+
+    #[test]
+    fn surrounding_quotes_are_kept() {
+        assert_eq!(clean_token("\"Hello,\""), "\"Hello,\"");
+        assert_eq!(clean_token("\u{FEFF}\u{201C}end.\u{201D}"), "\u{201C}end.\u{201D}");
+        assert!(matches!(clean_token("'quoted'"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn tokens_left_empty_are_dropped() {
+        let mut b = StyledTextBuilder::default();
+        b.push_text("\u{FEFF}one \u{00AD} two\u{00AD} \u{FEFF}");
+        let (pages, styles) = b.finish();
+        assert_eq!(pages, vec![vec!["one".to_string(), "two".to_string()]]);
+        assert_eq!(styles[0].len(), 2);
+    }
+
+    #[test]
+    fn zero_width_and_control_characters_are_stripped() {
+        assert_eq!(clean_token("zero\u{200B}width"), "zerowidth");
+        assert_eq!(clean_token("\u{200B}"), "");
+        assert_eq!(clean_token("tab\u{0007}\u{001B}bed"), "tabbed");
+        assert_eq!(clean_token("\u{0000}start"), "start");
+        assert_eq!(clean_token("\u{FEFF}\u{00AD}"), "");
+    }
+
+    #[test]
+    fn joiners_inside_emoji_and_accents_are_kept() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(clean_token(family), family);
+        // A joiner left dangling at the edge means nothing
+        assert_eq!(clean_token("\u{200D}word\u{200D}"), "word");
+        assert_eq!(clean_token("naïve—déjà-vu!"), "naïve—déjà-vu!");
+    }
+
+    #[test]
+    fn exotic_spaces_split_words_like_plain_ones() {
+        let mut b = StyledTextBuilder::default();
+        b.push_text("no\u{00A0}break\u{3000}ideographic\u{2009}thin \u{200B} end");
+        let (pages, _) = b.finish();
+        assert_eq!(pages, vec![["no", "break", "ideographic", "thin", "end"].map(String::from).to_vec()]);
+    }
+}
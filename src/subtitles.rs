@@ -0,0 +1,120 @@
+// Subtitle export: replays the document's timing offline, from the first word at the current
+// speed and settings, and writes the chunks (or whole sentences) out as SRT or WebVTT cues.
+// Durations come from `chunk_duration_secs`, the same as playback, and sentence gaps and page
+// pauses leave matching silences between cues. Pages that wait for Play add no time.
+
+use crate::{chunk_duration_secs, chunk_end, trailing_punctuation, EndOfPageBehavior, Punctuation, RsvpState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    pub const ALL: [SubtitleFormat; 2] = [SubtitleFormat::Srt, SubtitleFormat::WebVtt];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "SRT",
+            SubtitleFormat::WebVtt => "WebVTT",
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "reading.srt",
+            SubtitleFormat::WebVtt => "reading.vtt",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "application/x-subrip",
+            SubtitleFormat::WebVtt => "text/vtt",
+        }
+    }
+}
+
+struct Cue {
+    start: f32,
+    end: f32,
+    text: String,
+}
+
+/// Times every chunk of the document as playback would show it, one cue per chunk or, with
+/// `sentences`, one per sentence.
+fn cues(rsvp: &RsvpState, sentences: bool) -> Vec<Cue> {
+    let gap = if rsvp.sentence_gap { rsvp.sentence_gap_ms / 1000.0 } else { 0.0 };
+    let page_pause = match rsvp.end_of_page {
+        EndOfPageBehavior::AutoAdvance => rsvp.page_transition_pause_ms / 1000.0,
+        EndOfPageBehavior::PauseEachPage | EndOfPageBehavior::Continuous => 0.0,
+    };
+
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut time = 0.0;
+    // Open sentence cue that later chunks are still being added to
+    let mut open = false;
+    for (page, words) in rsvp.pages.iter().enumerate() {
+        let mut start = 0;
+        while start < words.len() {
+            let end = chunk_end(rsvp, page, start).max(start + 1);
+            let text = words[start..end].join(" ");
+            let dwell = chunk_duration_secs(rsvp, page, start, end);
+            match cues.last_mut() {
+                Some(cue) if open => {
+                    cue.text.push(' ');
+                    cue.text.push_str(&text);
+                    cue.end = time + dwell;
+                }
+                _ => cues.push(Cue { start: time, end: time + dwell, text }),
+            }
+            time += dwell;
+
+            let ends_sentence = trailing_punctuation(&words[end - 1]) == Punctuation::Sentence;
+            open = sentences && !ends_sentence && !rsvp.word_style(page, end - 1).paragraph_end && end < words.len();
+            if ends_sentence {
+                time += gap;
+            }
+            start = end;
+        }
+        open = false;
+        if page + 1 < rsvp.pages.len() {
+            time += page_pause;
+        }
+    }
+    cues
+}
+
+fn timestamp(secs: f32, millis_separator: char) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        millis_separator,
+        ms % 1000
+    )
+}
+
+/// The whole document as a subtitle file in `format`.
+pub fn export_subtitles(rsvp: &RsvpState, format: SubtitleFormat, sentences: bool) -> String {
+    let mut out = String::new();
+    if format == SubtitleFormat::WebVtt {
+        out.push_str("WEBVTT\n\n");
+    }
+    for (i, cue) in cues(rsvp, sentences).iter().enumerate() {
+        match format {
+            SubtitleFormat::Srt => {
+                out.push_str(&format!("{}\n{} --> {}\n", i + 1, timestamp(cue.start, ','), timestamp(cue.end, ',')));
+            }
+            SubtitleFormat::WebVtt => {
+                out.push_str(&format!("{} --> {}\n", timestamp(cue.start, '.'), timestamp(cue.end, '.')));
+            }
+        }
+        out.push_str(&cue.text);
+        out.push_str("\n\n");
+    }
+    out
+}
@@ -0,0 +1,95 @@
+// Lightweight English syllable estimator for pacing. Heuristic, not a dictionary:
+// count vowel groups, then correct for the most common silent/merged endings.
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+/// Estimated syllable count of `word`; punctuation and digits are ignored. Never returns 0
+/// for a word with letters.
+pub fn estimate_syllables(word: &str) -> usize {
+    let letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+
+    if letters.is_empty() {
+        return 0;
+    }
+    if letters.len() <= 3 {
+        return 1;
+    }
+
+    let mut count = 0;
+    let mut prev_vowel = false;
+    for &c in &letters {
+        let vowel = is_vowel(c);
+        if vowel && !prev_vowel {
+            count += 1;
+        }
+        prev_vowel = vowel;
+    }
+
+    let n = letters.len();
+    let ends_with = |suffix: &str| {
+        let s: Vec<char> = suffix.chars().collect();
+        n >= s.len() && letters[n - s.len()..] == s[..]
+    };
+    let consonant_at = |i: usize| !is_vowel(letters[i]);
+
+    // Silent final "e" ("make"), but "-le" after a consonant is voiced ("table")
+    let voiced_le = ends_with("le") && consonant_at(n - 3);
+    if ends_with("e") && !voiced_le && count > 1 {
+        count -= 1;
+    }
+
+    // "-ed" is silent unless it follows t/d ("walked" vs "wanted")
+    if ends_with("ed") && n > 2 && !matches!(letters[n - 3], 't' | 'd') && count > 1 {
+        count -= 1;
+    }
+
+    // "-es" is silent after most consonants ("makes"), voiced after sibilants ("boxes")
+    if ends_with("es") && n > 2 && !matches!(letters[n - 3], 's' | 'x' | 'z' | 'c' | 'g' | 'h') && count > 1 {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_syllables;
+
+    #[test]
+    fn known_words() {
+        assert_eq!(estimate_syllables("strawberry"), 3);
+        assert_eq!(estimate_syllables("queue"), 1);
+        assert_eq!(estimate_syllables("reading"), 2);
+    }
+
+    #[test]
+    fn silent_and_voiced_endings() {
+        // Silent final "e"
+        assert_eq!(estimate_syllables("make"), 1);
+        assert_eq!(estimate_syllables("stone"), 1);
+        // "-le" after a consonant is its own syllable
+        assert_eq!(estimate_syllables("table"), 2);
+        assert_eq!(estimate_syllables("little"), 2);
+        // "-ed" and "-es" only count after t/d and sibilants
+        assert_eq!(estimate_syllables("walked"), 1);
+        assert_eq!(estimate_syllables("wanted"), 2);
+        assert_eq!(estimate_syllables("makes"), 1);
+        assert_eq!(estimate_syllables("boxes"), 2);
+    }
+
+    #[test]
+    fn short_words_and_tokens_without_letters() {
+        assert_eq!(estimate_syllables("a"), 1);
+        assert_eq!(estimate_syllables("I"), 1);
+        assert_eq!(estimate_syllables("the"), 1);
+        assert_eq!(estimate_syllables("Hello,"), 2);
+        assert_eq!(estimate_syllables("42"), 0);
+        assert_eq!(estimate_syllables("—"), 0);
+    }
+}
@@ -0,0 +1,129 @@
+// Incremental ingestion for very large plain-text files. Instead of decoding the whole
+// upload into one String at once, a slice is tokenized each frame into pages of
+// `words_per_page` and appended to the document, so reading can start at once and the
+// UI stays responsive while the rest loads.
+
+use bevy::prelude::*;
+
+use crate::styled_text::StyledTextBuilder;
+use crate::{RsvpState, WordStyle};
+
+const STREAM_THRESHOLD_BYTES: usize = 4 * 1024 * 1024;
+const CHUNK_BYTES: usize = 256 * 1024;
+
+/// Feeds plain text into `b`, treating blank lines as paragraph (stanza) ends and keeping
+/// single line breaks as line ends. Lines may end in `\r\n`, `\n` or a lone `\r`, and a
+/// form feed starts a new page.
+pub fn push_plain_text(b: &mut StyledTextBuilder, text: &str) {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut sheets = text.split('\x0C').peekable();
+    while let Some(sheet) = sheets.next() {
+        push_paragraphs(b, sheet);
+        if sheets.peek().is_some() {
+            b.end_page();
+        }
+    }
+}
+
+fn push_paragraphs(b: &mut StyledTextBuilder, text: &str) {
+    let mut paragraphs = text.split("\n\n").peekable();
+    while let Some(paragraph) = paragraphs.next() {
+        // The last piece may be a line cut short by the end of a streamed slice
+        let mut lines = paragraph.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            b.push_text(line);
+            if lines.peek().is_some() {
+                b.end_line();
+            }
+        }
+        if paragraphs.peek().is_some() {
+            b.end_block();
+        }
+    }
+}
+
+pub fn should_stream(bytes: &[u8]) -> bool {
+    bytes.len() > STREAM_THRESHOLD_BYTES
+}
+
+#[derive(Resource, Default)]
+pub struct TextStream {
+    bytes: Vec<u8>,
+    // Bytes before this have been tokenized
+    pos: usize,
+    builder: StyledTextBuilder,
+}
+
+impl TextStream {
+    /// Begins streaming `bytes` in pages of `words_per_page`, returning the first pages to read.
+    pub fn start(&mut self, bytes: Vec<u8>, words_per_page: usize) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+        *self = Self { bytes, builder: StyledTextBuilder::with_page_limit(words_per_page), ..default() };
+        loop {
+            let (pages, styles) = self.next_chunk();
+            if !pages.is_empty() || !self.is_active() {
+                return (pages, styles);
+            }
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+
+    fn is_active(&self) -> bool {
+        self.pos < self.bytes.len()
+    }
+
+    /// Tokenizes the next slice and returns any pages it completed (the rest at the end).
+    fn next_chunk(&mut self) -> (Vec<Vec<String>>, Vec<Vec<WordStyle>>) {
+        let rest = &self.bytes[self.pos..];
+        // Cut after an ASCII whitespace byte: it can't sit inside a UTF-8 sequence or a word
+        let end = if rest.len() <= CHUNK_BYTES {
+            rest.len()
+        } else {
+            rest[..CHUNK_BYTES]
+                .iter()
+                .rposition(|b| b.is_ascii_whitespace())
+                .map_or(CHUNK_BYTES, |i| i + 1)
+        };
+
+        push_plain_text(&mut self.builder, &String::from_utf8_lossy(&rest[..end]));
+        self.pos += end;
+
+        if self.is_active() {
+            self.builder.take_pages()
+        } else {
+            let finished = std::mem::take(&mut self.builder).finish();
+            self.bytes = Vec::new();
+            self.pos = 0;
+            finished
+        }
+    }
+}
+
+pub fn text_stream_system(mut stream: ResMut<TextStream>, mut rsvp: ResMut<RsvpState>) {
+    if !stream.is_active() {
+        return;
+    }
+    // Pages still to come follow the setting if it changes mid-stream
+    stream.builder.max_page_words = rsvp.words_per_page;
+    let (pages, styles) = stream.next_chunk();
+    rsvp.append_pages(pages, styles);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_feeds_split_pages_and_line_endings_are_normalized() {
+        let mut b = StyledTextBuilder::default();
+        push_plain_text(&mut b, "first page\r\nline two\r\n\x0Csecond page\rend\n");
+        let (pages, styles) = b.finish();
+        let words = |text: &str| text.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(pages, vec![words("first page line two"), words("second page end")]);
+        // Each kind of line ending still ends the line before it
+        assert!(styles[0][1].line_end && styles[1][1].line_end);
+        assert!(!styles[0][2].line_end);
+    }
+}
@@ -0,0 +1,146 @@
+// Toasts: short notices stacked in the bottom-right corner that dismiss themselves, for
+// feedback on actions (a file loaded, a bookmark added, an import failed). Unlike the
+// overlay message over the reader, several can be visible at once.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+const MAX_VISIBLE: usize = 4;
+// Prompts wait longer than plain notices, since they ask for a decision
+const PROMPT_SECS: f64 = 10.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> egui::Color32 {
+        match self {
+            ToastKind::Info => egui::Color32::from_gray(220),
+            ToastKind::Success => egui::Color32::from_rgb(130, 210, 140),
+            ToastKind::Warning => egui::Color32::from_rgb(240, 200, 100),
+            ToastKind::Error => egui::Color32::from_rgb(255, 120, 110),
+        }
+    }
+
+    fn secs(self) -> f64 {
+        match self {
+            ToastKind::Info | ToastKind::Success => 3.0,
+            // Warnings and errors stay up longer so there's time to read them
+            ToastKind::Warning | ToastKind::Error => 6.0,
+        }
+    }
+}
+
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    // In `Time::elapsed_secs_f64` terms
+    pub expires_at: f64,
+    // Button label and the id reported through `take_accepted` when it's clicked
+    pub action: Option<(String, u64)>,
+}
+
+#[derive(Resource, Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+    // Clock of the last `toast_system` run, so pushing doesn't need `Time`
+    now: f64,
+    next_id: u64,
+    accepted: Vec<u64>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>) {
+        let message = message.into();
+        match kind {
+            ToastKind::Warning => warn!("{}", message),
+            ToastKind::Error => error!("{}", message),
+            _ => info!("{}", message),
+        }
+        self.toasts.push(Toast { message, kind, expires_at: self.now + kind.secs(), action: None });
+        if self.toasts.len() > MAX_VISIBLE {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// A notice with a button; poll `take_accepted` or `is_pending` with the returned id.
+    pub fn prompt(&mut self, message: impl Into<String>, action_label: impl Into<String>) -> u64 {
+        self.info(message);
+        self.next_id += 1;
+        if let Some(toast) = self.toasts.last_mut() {
+            toast.action = Some((action_label.into(), self.next_id));
+            toast.expires_at = self.now + PROMPT_SECS;
+        }
+        self.next_id
+    }
+
+    /// Whether the prompt's button was clicked since the last call.
+    pub fn take_accepted(&mut self, id: u64) -> bool {
+        let before = self.accepted.len();
+        self.accepted.retain(|&a| a != id);
+        self.accepted.len() != before
+    }
+
+    /// Whether the prompt is still showing (neither answered, dismissed nor expired).
+    pub fn is_pending(&self, id: u64) -> bool {
+        self.toasts.iter().any(|t| t.action.as_ref().is_some_and(|(_, a)| *a == id))
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Info, message);
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Success, message);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Error, message);
+    }
+}
+
+pub fn toast_system(mut contexts: EguiContexts, time: Res<Time>, mut toasts: ResMut<Toasts>) {
+    let now = time.elapsed_secs_f64();
+    toasts.now = now;
+    toasts.toasts.retain(|t| t.expires_at > now);
+    if toasts.toasts.is_empty() {
+        return;
+    }
+
+    let mut dismissed = None;
+    let mut accepted = None;
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+        .order(egui::Order::Foreground)
+        .show(contexts.ctx_mut(), |ui| {
+            for (i, toast) in toasts.toasts.iter().enumerate() {
+                let frame = egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(toast.kind.color(), &toast.message);
+                    if let Some((label, id)) = &toast.action {
+                        if ui.button(label).clicked() {
+                            accepted = Some(*id);
+                            dismissed = Some(i);
+                        }
+                    }
+                });
+                if frame.response.interact(egui::Sense::click()).clicked() {
+                    dismissed = Some(i);
+                }
+            }
+        });
+    if let Some(i) = dismissed {
+        toasts.toasts.remove(i);
+    }
+    if let Some(id) = accepted {
+        toasts.accepted.push(id);
+    }
+}
@@ -0,0 +1,77 @@
+// Table of contents: a PDF's outline (the bookmarks pane of a desktop viewer), mapped onto
+// the reader's pages. lopdf's own `get_toc` sorts entries by title and panics on malformed
+// destinations, so the outline tree is walked here instead, keeping document order and
+// skipping entries it can't place.
+
+use std::collections::BTreeMap;
+
+use lopdf::{Document, Object, ObjectId, Outline};
+
+#[derive(Clone)]
+pub struct TocEntry {
+    pub title: String,
+    pub page: usize,
+    pub word: usize,
+    // Nesting depth in the outline, 0 for top-level entries
+    pub level: usize,
+}
+
+/// An outline entry as the PDF has it: title, PDF page number (from 1) and nesting depth.
+pub type OutlineEntry = (String, u32, usize);
+
+/// PDF text strings are UTF-16 when they start with a byte order mark, otherwise (close
+/// enough to) single-byte text.
+fn decode_text_string(bytes: &[u8]) -> String {
+    let utf16 = |to_unit: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes[2..].chunks_exact(2).map(|c| to_unit([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    };
+    match bytes {
+        [0xfe, 0xff, ..] => utf16(u16::from_be_bytes),
+        [0xff, 0xfe, ..] => utf16(u16::from_le_bytes),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+fn walk(outlines: &[Outline], level: usize, page_numbers: &BTreeMap<ObjectId, u32>, out: &mut Vec<OutlineEntry>) {
+    for outline in outlines {
+        match outline {
+            Outline::Destination(destination) => {
+                let Some(Object::String(title, _)) = destination.title() else { continue };
+                let page = match destination.page() {
+                    Some(Object::Reference(id)) => page_numbers.get(id).copied(),
+                    // Destinations into other files give a page index instead
+                    Some(Object::Integer(index)) => u32::try_from(*index).ok().map(|i| i + 1),
+                    _ => None,
+                };
+                let title = decode_text_string(title).trim().to_string();
+                if let (Some(page), false) = (page, title.is_empty()) {
+                    out.push((title, page, level));
+                }
+            }
+            Outline::SubOutlines(children) => walk(children, level + 1, page_numbers, out),
+        }
+    }
+}
+
+/// The document's outline in reading order; empty when it has none.
+pub fn pdf_outline(doc: &Document) -> Vec<OutlineEntry> {
+    let mut named_destinations = BTreeMap::new();
+    let Ok(Some(outlines)) = doc.get_outlines(None, None, &mut named_destinations) else { return Vec::new() };
+    let page_numbers: BTreeMap<ObjectId, u32> = doc.get_pages().into_iter().map(|(number, id)| (id, number)).collect();
+    let mut entries = Vec::new();
+    walk(&outlines, 0, &page_numbers, &mut entries);
+    entries
+}
+
+/// Places outline entries on the reader's pages, given each page's PDF page number. An entry
+/// pointing at a blank page that was skipped on load lands on the next page with words.
+pub fn map_outline(outline: Vec<OutlineEntry>, source_pages: &[u32]) -> Vec<TocEntry> {
+    outline
+        .into_iter()
+        .filter_map(|(title, pdf_page, level)| {
+            let page = source_pages.partition_point(|&p| p < pdf_page);
+            (page < source_pages.len()).then_some(TocEntry { title, page, word: 0, level })
+        })
+        .collect()
+}
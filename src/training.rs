@@ -0,0 +1,184 @@
+// Training plan: a multi-day program that starts at the calibrated speed and raises the
+// target a little after each completed session. A session is SESSION_WORDS read at or near
+// the day's target, at most one per day. The plan, its history and today's progress are
+// persisted so reloads don't lose them.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::calibration::DEFAULT_WPM_KEY;
+use crate::i18n::{tr, Lang};
+use crate::{storage, OverlayMessage, RsvpState, MAX_WPM};
+
+const STORAGE_KEY: &str = "rsvp_training_plan";
+const SAVE_INTERVAL_SECS: f32 = 2.0;
+
+const SESSION_WORDS: usize = 1500;
+// Each completed session raises the target by this fraction
+const STEP_FRACTION: f32 = 0.03;
+// Words count toward the session when playing at least this share of the target
+const TARGET_TOLERANCE: f32 = 0.97;
+// Sessions per level, so there's a milestone between single steps
+const SESSIONS_PER_LEVEL: usize = 5;
+
+#[derive(Serialize, Deserialize)]
+struct CompletedSession {
+    day: String,
+    target_wpm: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Plan {
+    target_wpm: f32,
+    history: Vec<CompletedSession>,
+    // Days with any reading at the target, completed or not, for adherence
+    practice_days: usize,
+    today: String,
+    words_today: usize,
+}
+
+impl Plan {
+    fn done_today(&self) -> bool {
+        self.history.last().is_some_and(|s| s.day == self.today)
+    }
+}
+
+#[derive(Resource)]
+pub struct TrainingPlan {
+    plan: Option<Plan>,
+    // RsvpState::words_advanced at the last frame, to count only new words
+    last_advanced: u64,
+    dirty: bool,
+    save_timer: f32,
+}
+
+impl Default for TrainingPlan {
+    fn default() -> Self {
+        let plan = storage::load(STORAGE_KEY).and_then(|json| serde_json::from_str(&json).ok());
+        Self { plan, last_advanced: 0, dirty: false, save_timer: 0.0 }
+    }
+}
+
+impl TrainingPlan {
+    /// Starts a plan at the calibrated speed, or `current_wpm` if there's no calibration.
+    fn start(&mut self, current_wpm: f32) {
+        let target_wpm = storage::load(DEFAULT_WPM_KEY).and_then(|v| v.parse().ok()).unwrap_or(current_wpm);
+        self.plan = Some(Plan {
+            target_wpm,
+            history: Vec::new(),
+            practice_days: 0,
+            today: storage::today(),
+            words_today: 0,
+        });
+        self.save();
+    }
+
+    fn end(&mut self) {
+        self.plan = None;
+        storage::save(STORAGE_KEY, "");
+    }
+
+    fn save(&self) {
+        let Some(plan) = &self.plan else { return };
+        if let Ok(json) = serde_json::to_string(plan) {
+            storage::save(STORAGE_KEY, &json);
+        }
+    }
+}
+
+pub fn training_plan_system(
+    time: Res<Time>,
+    rsvp: Res<RsvpState>,
+    mut training: ResMut<TrainingPlan>,
+    mut overlay: ResMut<OverlayMessage>,
+) {
+    let new_words = rsvp.words_advanced.saturating_sub(training.last_advanced) as usize;
+    training.last_advanced = rsvp.words_advanced;
+    let Some(plan) = training.plan.as_mut() else { return };
+    let mut dirty = false;
+
+    let today = storage::today();
+    if plan.today != today {
+        plan.today = today;
+        plan.words_today = 0;
+        dirty = true;
+    }
+
+    let at_target = rsvp.effective_wpm >= plan.target_wpm * TARGET_TOLERANCE;
+    if new_words > 0 && at_target && !plan.done_today() {
+        if plan.words_today == 0 {
+            plan.practice_days += 1;
+        }
+        plan.words_today += new_words;
+        dirty = true;
+
+        if plan.words_today >= SESSION_WORDS {
+            plan.history.push(CompletedSession { day: plan.today.clone(), target_wpm: plan.target_wpm });
+            plan.target_wpm = (plan.target_wpm * (1.0 + STEP_FRACTION)).round().min(MAX_WPM);
+            let lang = rsvp.lang;
+            overlay.show(format!("{}: {:.0} WPM", tr(lang, "Session complete! Next target"), plan.target_wpm), 4.0);
+            training.save();
+            return;
+        }
+    }
+
+    training.dirty |= dirty;
+    training.save_timer += time.delta_secs();
+    if training.dirty && training.save_timer >= SAVE_INTERVAL_SECS {
+        training.save();
+        training.dirty = false;
+        training.save_timer = 0.0;
+    }
+}
+
+/// Playback-panel section: start the plan, or today's target and progress while it runs.
+pub fn training_controls(ui: &mut egui::Ui, training: &mut TrainingPlan, rsvp: &mut RsvpState) {
+    let lang = rsvp.lang;
+    egui::CollapsingHeader::new(tr(lang, "Training plan")).show(ui, |ui| {
+        let Some(plan) = &training.plan else {
+            ui.label(tr(lang, "Raises your target speed a little after each day's session."));
+            if ui.button(tr(lang, "Start training plan")).clicked() {
+                training.start(rsvp.wpm);
+            }
+            return;
+        };
+
+        plan_summary(ui, plan, lang);
+        let mut end = false;
+        ui.horizontal(|ui| {
+            if ui.button(tr(lang, "Read at target")).clicked() {
+                rsvp.wpm = plan.target_wpm;
+            }
+            end = ui.button(tr(lang, "End plan")).clicked();
+        });
+        if end {
+            training.end();
+        }
+    });
+}
+
+fn plan_summary(ui: &mut egui::Ui, plan: &Plan, lang: Lang) {
+    let sessions = plan.history.len();
+    ui.label(format!("{}: {:.0} WPM", tr(lang, "Today's target"), plan.target_wpm));
+    if plan.done_today() {
+        ui.label(tr(lang, "Today's session is done."));
+    } else {
+        let fraction = plan.words_today as f32 / SESSION_WORDS as f32;
+        ui.add(egui::ProgressBar::new(fraction).text(format!("{} / {} {}", plan.words_today, SESSION_WORDS, tr(lang, "words"))));
+    }
+    ui.label(format!(
+        "{} {}: {} / {} {}",
+        tr(lang, "Level"),
+        sessions / SESSIONS_PER_LEVEL + 1,
+        sessions % SESSIONS_PER_LEVEL,
+        SESSIONS_PER_LEVEL,
+        tr(lang, "sessions")
+    ));
+    if plan.practice_days > 0 {
+        ui.label(format!("{}: {} / {}", tr(lang, "Sessions completed on practice days"), sessions, plan.practice_days));
+    }
+    if let Some(first) = plan.history.first() {
+        ui.label(format!("{} {:.0} WPM", tr(lang, "Started at"), first.target_wpm));
+    }
+}
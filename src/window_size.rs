@@ -0,0 +1,36 @@
+// Window size memory. Native builds reopen at the last size the window was resized to;
+// the web build skips this entirely, since its canvas is sized by `fit_canvas_to_parent`
+// to whatever the page layout gives it.
+
+use bevy::prelude::*;
+use bevy::window::{WindowResized, WindowResolution};
+
+use crate::storage;
+
+const STORAGE_KEY: &str = "rsvp_window_size";
+// Resizes arrive every frame while dragging; save once they settle
+const SAVE_DELAY_SECS: f32 = 0.5;
+
+/// Resolution saved by a previous run, as "width|height" in logical pixels.
+pub fn saved_resolution() -> Option<WindowResolution> {
+    let saved = storage::load(STORAGE_KEY)?;
+    let (width, height) = saved.split_once('|')?;
+    let (width, height): (f32, f32) = (width.parse().ok()?, height.parse().ok()?);
+    (width >= 200.0 && height >= 150.0).then(|| WindowResolution::new(width, height))
+}
+
+pub fn window_size_system(
+    time: Res<Time>,
+    mut resized: EventReader<WindowResized>,
+    mut pending: Local<Option<(Vec2, f32)>>,
+) {
+    if let Some(event) = resized.read().last() {
+        *pending = Some((Vec2::new(event.width, event.height), 0.0));
+    }
+    let Some((size, waited)) = pending.as_mut() else { return };
+    *waited += time.delta_secs();
+    if *waited >= SAVE_DELAY_SECS {
+        storage::save(STORAGE_KEY, &format!("{}|{}", size.x.round(), size.y.round()));
+        *pending = None;
+    }
+}